@@ -0,0 +1,102 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+use ross_protocol::convert_packet::ConvertPacket;
+use ross_protocol::event::analog::*;
+use ross_protocol::event::bcm::*;
+use ross_protocol::event::bootloader::*;
+use ross_protocol::event::button::*;
+use ross_protocol::event::configurator::*;
+use ross_protocol::event::cover::*;
+use ross_protocol::event::diagnostics::*;
+use ross_protocol::event::encoder::*;
+use ross_protocol::event::error::*;
+use ross_protocol::event::gateway::*;
+use ross_protocol::event::general::*;
+use ross_protocol::event::group::*;
+use ross_protocol::event::internal::*;
+use ross_protocol::event::message::*;
+use ross_protocol::event::parameter::*;
+use ross_protocol::event::programmer::*;
+use ross_protocol::event::relay::*;
+use ross_protocol::event::schedule::*;
+use ross_protocol::event::sensor::*;
+use ross_protocol::event::time::*;
+use ross_protocol::packet::Packet;
+
+// byte 0:      IS_ERROR (lowest bit)
+// bytes 1 - 2: DEVICE_ADDRESS
+// bytes 3 - n: DATA
+fuzz_target!(|input: &[u8]| {
+    if input.len() < 3 {
+        return;
+    }
+
+    let packet = Packet {
+        is_error: input[0] & 0x01 != 0,
+        device_address: u16::from_be_bytes([input[1], input[2]]),
+        data: input[3..].to_vec(),
+    };
+
+    let _ = BootloaderHelloEvent::try_from_packet(&packet);
+    let _ = ProgrammerHelloEvent::try_from_packet(&packet);
+    let _ = ProgrammerStartFirmwareUpgradeEvent::try_from_packet(&packet);
+    let _ = ProgrammerStartConfigUpgradeEvent::try_from_packet(&packet);
+    let _ = ProgrammerSetDeviceAddressEvent::try_from_packet(&packet);
+    let _ = AckEvent::try_from_packet(&packet);
+    let _ = ExtendedAckEvent::try_from_packet(&packet);
+    let _ = DataEvent::try_from_packet(&packet);
+    let _ = SequencedEvent::try_from_packet(&packet);
+    let _ = ConfiguratorHelloEvent::try_from_packet(&packet);
+    let _ = BcmChangeBrightnessEvent::try_from_packet(&packet);
+    let _ = BcmAnimateBrightnessEvent::try_from_packet(&packet);
+    let _ = BcmAnimateSequenceEvent::try_from_packet(&packet);
+    let _ = ButtonPressedEvent::try_from_packet(&packet);
+    let _ = ButtonReleasedEvent::try_from_packet(&packet);
+    let _ = SystemTickEvent::try_from_packet(&packet);
+    let _ = MessageEvent::try_from_packet(&packet);
+    let _ = RelaySetValueEvent::try_from_packet(&packet);
+    let _ = RelayGetValueEvent::try_from_packet(&packet);
+    let _ = RelayReportValueEvent::try_from_packet(&packet);
+    let _ = RelayValueChangedEvent::try_from_packet(&packet);
+    let _ = RelayReportPowerEvent::try_from_packet(&packet);
+    let _ = RelayReportEnergyEvent::try_from_packet(&packet);
+    let _ = RelayOverCurrentEvent::try_from_packet(&packet);
+    let _ = RelayResetEnergyEvent::try_from_packet(&packet);
+    let _ = GatewayDiscoverEvent::try_from_packet(&packet);
+    let _ = ErrorEvent::try_from_packet(&packet);
+    let _ = CoverSetPositionEvent::try_from_packet(&packet);
+    let _ = CoverSetTiltEvent::try_from_packet(&packet);
+    let _ = CoverStopEvent::try_from_packet(&packet);
+    let _ = CoverCalibrateEvent::try_from_packet(&packet);
+    let _ = CoverReportPositionEvent::try_from_packet(&packet);
+    let _ = BcmGetValueEvent::try_from_packet(&packet);
+    let _ = BcmReportValueEvent::try_from_packet(&packet);
+    let _ = BcmStoreSceneEvent::try_from_packet(&packet);
+    let _ = BcmRecallSceneEvent::try_from_packet(&packet);
+    let _ = ButtonClickedEvent::try_from_packet(&packet);
+    let _ = ButtonDoubleClickedEvent::try_from_packet(&packet);
+    let _ = ButtonLongPressedEvent::try_from_packet(&packet);
+    let _ = ButtonHeldEvent::try_from_packet(&packet);
+    let _ = EncoderRotatedEvent::try_from_packet(&packet);
+    let _ = AnalogValueEvent::try_from_packet(&packet);
+    let _ = SensorGetReadingEvent::try_from_packet(&packet);
+    let _ = SensorReadingEvent::try_from_packet(&packet);
+    let _ = TimeGetEvent::try_from_packet(&packet);
+    let _ = TimeSyncEvent::try_from_packet(&packet);
+    let _ = ScheduleAddEvent::try_from_packet(&packet);
+    let _ = ScheduleRemoveEvent::try_from_packet(&packet);
+    let _ = ScheduleListEvent::try_from_packet(&packet);
+    let _ = ScheduleReportEvent::try_from_packet(&packet);
+    let _ = GroupAddEvent::try_from_packet(&packet);
+    let _ = GroupRemoveEvent::try_from_packet(&packet);
+    let _ = GroupGetEvent::try_from_packet(&packet);
+    let _ = GroupReportEvent::try_from_packet(&packet);
+    let _ = ParameterReadEvent::try_from_packet(&packet);
+    let _ = ParameterWriteEvent::try_from_packet(&packet);
+    let _ = ParameterReportEvent::try_from_packet(&packet);
+    let _ = ParameterDescribeEvent::try_from_packet(&packet);
+    let _ = ParameterDescriptionEvent::try_from_packet(&packet);
+    let _ = DiagnosticsGetEvent::try_from_packet(&packet);
+    let _ = DiagnosticsReportEvent::try_from_packet(&packet);
+});
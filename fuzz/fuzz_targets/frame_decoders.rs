@@ -0,0 +1,57 @@
+#![no_main]
+use bxcan::{Data, ExtendedId, Frame as BxFrame};
+use libfuzzer_sys::fuzz_target;
+
+use ross_protocol::frame::Frame;
+use ross_protocol::packet::PacketBuilder;
+
+// The input is split into chunks, the first byte of every chunk selects the decoder and the
+// chunk length, the decoded frames are then fed into a packet builder
+fuzz_target!(|input: &[u8]| {
+    let mut packet_builder: Option<PacketBuilder> = None;
+    let mut input = input;
+
+    while !input.is_empty() {
+        let header = input[0];
+        let length = ((header & 0x0f) as usize).min(input.len() - 1);
+        let chunk = &input[1..=length];
+        input = &input[length + 1..];
+
+        let frame = if header & 0x80 != 0 {
+            if chunk.len() < 4 {
+                continue;
+            }
+
+            let id = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]) & 0x1fff_ffff;
+            let data = &chunk[4..chunk.len().min(12)];
+
+            Frame::from_bxcan_frame(BxFrame::new_data(
+                ExtendedId::new(id).unwrap(),
+                Data::new(data).unwrap(),
+            ))
+        } else {
+            Frame::from_usart_frame(chunk.to_vec())
+        };
+
+        let frame = match frame {
+            Ok(frame) => frame,
+            Err(_) => continue,
+        };
+
+        match packet_builder {
+            Some(ref mut builder) => {
+                if builder.add_frame(frame).is_err() {
+                    packet_builder = None;
+                }
+            }
+            None => packet_builder = PacketBuilder::new(frame).ok(),
+        }
+
+        if let Some(ref builder) = packet_builder {
+            if builder.frames_left() == 0 {
+                builder.build().unwrap();
+                packet_builder = None;
+            }
+        }
+    }
+});
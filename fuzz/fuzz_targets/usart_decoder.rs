@@ -0,0 +1,14 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+use ross_protocol::interface::usart_decoder::{UsartDecoder, UsartFraming};
+
+fuzz_target!(|input: &[u8]| {
+    for framing in [UsartFraming::LengthPrefixed, UsartFraming::ZeroDelimited].iter() {
+        let mut decoder = UsartDecoder::with_framing(*framing);
+
+        for byte in input.iter() {
+            let _ = decoder.decode_byte(*byte);
+        }
+    }
+});
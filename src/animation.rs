@@ -0,0 +1,369 @@
+use crate::event::bcm::{BcmEasing, BcmKeyframe, BcmValue};
+
+/// Fixed point representation of 1.0 used for animation progress
+pub const PROGRESS_ONE: u32 = 1 << 16;
+
+/// Applies an easing curve to linear progress between 0 and `PROGRESS_ONE`
+///
+/// `BcmEasing::Gamma` does not change the progress, it changes how channel values are blended
+/// instead (see `interpolate`).
+pub fn ease(easing: BcmEasing, progress: u32) -> u32 {
+    let progress = progress.min(PROGRESS_ONE);
+
+    match easing {
+        BcmEasing::Linear | BcmEasing::Gamma => progress,
+        BcmEasing::EaseIn => square(progress),
+        BcmEasing::EaseOut => PROGRESS_ONE - square(PROGRESS_ONE - progress),
+        BcmEasing::EaseInOut => {
+            if progress < PROGRESS_ONE / 2 {
+                2 * square(progress)
+            } else {
+                PROGRESS_ONE - 2 * square(PROGRESS_ONE - progress)
+            }
+        }
+    }
+}
+
+/// Returns the value of an animation from `from` to `to` after `elapsed` out of `duration`
+/// milliseconds
+///
+/// Binary values switch once the animation is over. Values of different kinds can't be blended,
+/// so the animation jumps to `to` right away.
+pub fn interpolate(
+    from: BcmValue,
+    to: BcmValue,
+    easing: BcmEasing,
+    elapsed: u32,
+    duration: u32,
+) -> BcmValue {
+    if elapsed >= duration {
+        return to;
+    }
+
+    let progress = ease(
+        easing,
+        (elapsed as u64 * PROGRESS_ONE as u64 / duration as u64) as u32,
+    );
+
+    if progress == 0 {
+        return from;
+    }
+
+    let blend = |from: u8, to: u8| {
+        if easing == BcmEasing::Gamma {
+            from_perceived(lerp(to_perceived(from), to_perceived(to), progress))
+        } else {
+            lerp(from, to, progress)
+        }
+    };
+
+    match (from, to) {
+        (BcmValue::Binary(_), BcmValue::Binary(_)) => from,
+        (BcmValue::Single(from), BcmValue::Single(to)) => BcmValue::Single(blend(from, to)),
+        (BcmValue::Rgb(fr, fg, fb), BcmValue::Rgb(tr, tg, tb)) => {
+            BcmValue::Rgb(blend(fr, tr), blend(fg, tg), blend(fb, tb))
+        }
+        (BcmValue::RgbB(fr, fg, fb, fbr), BcmValue::RgbB(tr, tg, tb, tbr)) => {
+            BcmValue::RgbB(blend(fr, tr), blend(fg, tg), blend(fb, tb), blend(fbr, tbr))
+        }
+        (BcmValue::Rgbw(fr, fg, fb, fw), BcmValue::Rgbw(tr, tg, tb, tw)) => {
+            BcmValue::Rgbw(blend(fr, tr), blend(fg, tg), blend(fb, tb), blend(fw, tw))
+        }
+        (BcmValue::RgbwB(fr, fg, fb, fw, fbr), BcmValue::RgbwB(tr, tg, tb, tw, tbr)) => {
+            BcmValue::RgbwB(
+                blend(fr, tr),
+                blend(fg, tg),
+                blend(fb, tb),
+                blend(fw, tw),
+                blend(fbr, tbr),
+            )
+        }
+        (
+            BcmValue::ColorTemperature(from_kelvin, from_brightness),
+            BcmValue::ColorTemperature(to_kelvin, to_brightness),
+        ) => BcmValue::ColorTemperature(
+            lerp_u16(from_kelvin, to_kelvin, progress),
+            blend(from_brightness, to_brightness),
+        ),
+        (BcmValue::WarmColdWhite(fw, fc), BcmValue::WarmColdWhite(tw, tc)) => {
+            BcmValue::WarmColdWhite(blend(fw, tw), blend(fc, tc))
+        }
+        (BcmValue::Hsv(fh, fs, fv), BcmValue::Hsv(th, ts, tv)) => {
+            BcmValue::Hsv(lerp_hue(fh, th, progress), blend(fs, ts), blend(fv, tv))
+        }
+        _ => to,
+    }
+}
+
+/// Returns the value of a keyframe sequence `elapsed` milliseconds after it was started from
+/// `start`, and whether the sequence has finished
+///
+/// Every loop after the first one starts from the value of the last keyframe. A `loop_count` of 0
+/// loops forever.
+pub fn sequence_value(
+    start: BcmValue,
+    keyframes: &[BcmKeyframe],
+    loop_count: u8,
+    elapsed: u32,
+) -> (BcmValue, bool) {
+    let last_value = match keyframes.last() {
+        Some(keyframe) => keyframe.value,
+        None => return (start, true),
+    };
+
+    let loop_duration: u64 = keyframes
+        .iter()
+        .map(|keyframe| keyframe.duration as u64)
+        .sum();
+
+    if loop_duration == 0 {
+        return (last_value, true);
+    }
+
+    let loop_index = elapsed as u64 / loop_duration;
+
+    if loop_count != 0 && loop_index >= loop_count as u64 {
+        return (last_value, true);
+    }
+
+    let mut time = (elapsed as u64 % loop_duration) as u32;
+    let mut from = if loop_index == 0 { start } else { last_value };
+
+    for keyframe in keyframes.iter() {
+        if time < keyframe.duration {
+            return (
+                interpolate(
+                    from,
+                    keyframe.value,
+                    keyframe.easing,
+                    time,
+                    keyframe.duration,
+                ),
+                false,
+            );
+        }
+
+        time -= keyframe.duration;
+        from = keyframe.value;
+    }
+
+    (last_value, false)
+}
+
+fn square(progress: u32) -> u32 {
+    ((progress as u64 * progress as u64) >> 16) as u32
+}
+
+fn lerp(from: u8, to: u8, progress: u32) -> u8 {
+    ((from as u32 * (PROGRESS_ONE - progress) + to as u32 * progress + PROGRESS_ONE / 2) >> 16)
+        as u8
+}
+
+fn lerp_u16(from: u16, to: u16, progress: u32) -> u16 {
+    ((from as u64 * (PROGRESS_ONE - progress) as u64
+        + to as u64 * progress as u64
+        + PROGRESS_ONE as u64 / 2)
+        >> 16) as u16
+}
+
+/// Interpolates between two hues in degrees along the shorter way around the colour wheel
+fn lerp_hue(from: u16, to: u16, progress: u32) -> u16 {
+    let from = (from % 360) as i64;
+    let difference = ((to % 360) as i64 - from + 540) % 360 - 180;
+
+    (from
+        + (difference * progress as i64 + PROGRESS_ONE as i64 / 2).div_euclid(PROGRESS_ONE as i64))
+    .rem_euclid(360) as u16
+}
+
+/// Converts a duty cycle to perceived brightness, assuming a gamma of 2
+fn to_perceived(value: u8) -> u8 {
+    isqrt(value as u32 * 255) as u8
+}
+
+fn from_perceived(value: u8) -> u8 {
+    ((value as u32 * value as u32 + 127) / 255) as u8
+}
+
+fn isqrt(value: u32) -> u32 {
+    let mut result = 0;
+    let mut bit = 1 << 30;
+
+    while bit > value {
+        bit >>= 2;
+    }
+
+    let mut value = value;
+
+    while bit != 0 {
+        if value >= result + bit {
+            value -= result + bit;
+            result = (result >> 1) + bit;
+        } else {
+            result >>= 1;
+        }
+
+        bit >>= 2;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ease_test() {
+        for easing in [
+            BcmEasing::Linear,
+            BcmEasing::EaseIn,
+            BcmEasing::EaseOut,
+            BcmEasing::EaseInOut,
+            BcmEasing::Gamma,
+        ]
+        .iter()
+        {
+            assert_eq!(ease(*easing, 0), 0);
+            assert_eq!(ease(*easing, PROGRESS_ONE), PROGRESS_ONE);
+        }
+
+        assert_eq!(ease(BcmEasing::EaseIn, PROGRESS_ONE / 2), PROGRESS_ONE / 4);
+        assert_eq!(
+            ease(BcmEasing::EaseOut, PROGRESS_ONE / 2),
+            PROGRESS_ONE * 3 / 4
+        );
+        assert_eq!(
+            ease(BcmEasing::EaseInOut, PROGRESS_ONE / 2),
+            PROGRESS_ONE / 2
+        );
+    }
+
+    #[test]
+    fn interpolate_test() {
+        let from = BcmValue::Rgb(0x00, 0xff, 0x80);
+        let to = BcmValue::Rgb(0xff, 0x00, 0x80);
+
+        assert_eq!(interpolate(from, to, BcmEasing::Linear, 0, 1000), from);
+        assert_eq!(
+            interpolate(from, to, BcmEasing::Linear, 500, 1000),
+            BcmValue::Rgb(0x80, 0x80, 0x80)
+        );
+        assert_eq!(interpolate(from, to, BcmEasing::Linear, 1000, 1000), to);
+        assert_eq!(interpolate(from, to, BcmEasing::Linear, 5, 0), to);
+    }
+
+    #[test]
+    fn interpolate_gamma_test() {
+        let from = BcmValue::Single(0x00);
+        let to = BcmValue::Single(0xff);
+
+        // Half of the perceived brightness is a quarter of the duty cycle
+        assert_eq!(
+            interpolate(from, to, BcmEasing::Gamma, 500, 1000),
+            BcmValue::Single(0x40)
+        );
+    }
+
+    #[test]
+    fn interpolate_hsv_test() {
+        assert_eq!(
+            interpolate(
+                BcmValue::Hsv(350, 0xff, 0xff),
+                BcmValue::Hsv(30, 0xff, 0xff),
+                BcmEasing::Linear,
+                500,
+                1000
+            ),
+            BcmValue::Hsv(10, 0xff, 0xff)
+        );
+        assert_eq!(
+            interpolate(
+                BcmValue::Hsv(30, 0xff, 0xff),
+                BcmValue::Hsv(350, 0xff, 0xff),
+                BcmEasing::Linear,
+                750,
+                1000
+            ),
+            BcmValue::Hsv(0, 0xff, 0xff)
+        );
+        assert_eq!(
+            interpolate(
+                BcmValue::ColorTemperature(2700, 0x00),
+                BcmValue::ColorTemperature(6500, 0xff),
+                BcmEasing::Linear,
+                500,
+                1000
+            ),
+            BcmValue::ColorTemperature(4600, 0x80)
+        );
+    }
+
+    #[test]
+    fn interpolate_mismatched_test() {
+        assert_eq!(
+            interpolate(
+                BcmValue::Binary(false),
+                BcmValue::Binary(true),
+                BcmEasing::Linear,
+                999,
+                1000
+            ),
+            BcmValue::Binary(false)
+        );
+        assert_eq!(
+            interpolate(
+                BcmValue::Single(0x00),
+                BcmValue::Rgb(0xff, 0xff, 0xff),
+                BcmEasing::Linear,
+                1,
+                1000
+            ),
+            BcmValue::Rgb(0xff, 0xff, 0xff)
+        );
+    }
+
+    #[test]
+    fn sequence_value_test() {
+        let keyframes = [
+            BcmKeyframe {
+                duration: 1000,
+                easing: BcmEasing::Linear,
+                value: BcmValue::Single(0xff),
+            },
+            BcmKeyframe {
+                duration: 1000,
+                easing: BcmEasing::Linear,
+                value: BcmValue::Single(0x00),
+            },
+        ];
+        let start = BcmValue::Single(0x80);
+
+        assert_eq!(
+            sequence_value(start, &keyframes, 2, 0),
+            (BcmValue::Single(0x80), false)
+        );
+        assert_eq!(
+            sequence_value(start, &keyframes, 2, 1000),
+            (BcmValue::Single(0xff), false)
+        );
+        assert_eq!(
+            sequence_value(start, &keyframes, 2, 1500),
+            (BcmValue::Single(0x80), false)
+        );
+        // The second loop starts from the last keyframe
+        assert_eq!(
+            sequence_value(start, &keyframes, 2, 2500),
+            (BcmValue::Single(0x80), false)
+        );
+        assert_eq!(
+            sequence_value(start, &keyframes, 2, 4000),
+            (BcmValue::Single(0x00), true)
+        );
+        assert_eq!(
+            sequence_value(start, &keyframes, 0, 400_500),
+            (BcmValue::Single(0x80), false)
+        );
+        assert_eq!(sequence_value(start, &[], 0, 0), (start, true));
+    }
+}
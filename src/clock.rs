@@ -0,0 +1,220 @@
+use crate::convert_packet::ConvertPacket;
+use crate::event::time::TimeSyncEvent;
+use crate::packet::Packet;
+
+/// Largest drift between the local clock and the time master that is believed, in parts per
+/// million
+///
+/// Bigger differences between two synchronisations are treated as the time master's clock being
+/// set, not as drift.
+pub const MAX_DRIFT_PPM: i32 = 50_000;
+
+/// Shortest local time between two synchronisations for them to be used to estimate the drift, in
+/// milliseconds
+pub const MIN_DRIFT_INTERVAL: u32 = 10_000;
+
+/// Wall-clock time of a device, synchronised to the `TimeSyncEvent`s of a time master
+///
+/// The clock is driven by a local millisecond counter (which may wrap around) that is passed to
+/// every call. Synchronisations estimate how fast the local counter runs compared to the time
+/// master, so the time stays accurate between synchronisations. Synchronisations have to be
+/// less than 49 days apart for the wrapping counter to be usable.
+pub struct SyncedClock {
+    reference: Option<(u32, u64)>,
+    /// Synchronisation the next drift estimate is measured from, which is only moved on once it
+    /// is at least `MIN_DRIFT_INTERVAL` old
+    drift_anchor: Option<(u32, u64)>,
+    drift_ppm: Option<i32>,
+}
+
+impl SyncedClock {
+    pub fn new() -> Self {
+        SyncedClock {
+            reference: None,
+            drift_anchor: None,
+            drift_ppm: None,
+        }
+    }
+
+    pub fn is_synchronized(&self) -> bool {
+        self.reference.is_some()
+    }
+
+    /// Returns by how many parts per million the local counter runs faster than the time master,
+    /// or `None` if it hasn't been estimated yet
+    pub fn drift_ppm(&self) -> Option<i32> {
+        self.drift_ppm
+    }
+
+    /// Sets the clock to `timestamp` milliseconds since the unix epoch at local time `now`
+    pub fn synchronize(&mut self, timestamp: u64, now: u32) {
+        match self.drift_anchor {
+            Some((anchor_local, anchor_timestamp)) => {
+                let local_elapsed = now.wrapping_sub(anchor_local);
+                let master_elapsed = timestamp as i64 - anchor_timestamp as i64;
+
+                if local_elapsed >= MIN_DRIFT_INTERVAL {
+                    if master_elapsed > 0 {
+                        self.estimate_drift(local_elapsed, master_elapsed);
+                    }
+
+                    self.drift_anchor = Some((now, timestamp));
+                }
+            }
+            None => self.drift_anchor = Some((now, timestamp)),
+        }
+
+        self.reference = Some((now, timestamp));
+    }
+
+    fn estimate_drift(&mut self, local_elapsed: u32, master_elapsed: i64) {
+        let measured = (local_elapsed as i64 - master_elapsed) * 1_000_000 / master_elapsed;
+
+        if measured.abs() <= MAX_DRIFT_PPM as i64 {
+            let measured = measured as i32;
+
+            // Smooth out the latency jitter of single synchronisations
+            self.drift_ppm = Some(match self.drift_ppm {
+                Some(drift_ppm) => (drift_ppm * 3 + measured) / 4,
+                None => measured,
+            });
+        }
+    }
+
+    /// Synchronises the clock if the packet is a `TimeSyncEvent`, returning whether it was one
+    pub fn handle_packet(&mut self, packet: &Packet, now: u32) -> bool {
+        if let Ok(event) = TimeSyncEvent::try_from_packet(packet) {
+            self.synchronize(event.timestamp_millis(), now);
+
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the time in milliseconds since the unix epoch at local time `now`, or `None` if the
+    /// clock hasn't been synchronised yet
+    pub fn now(&self, now: u32) -> Option<u64> {
+        let (reference_local, reference_timestamp) = self.reference?;
+        let elapsed = now.wrapping_sub(reference_local) as i64;
+        let drift_ppm = self.drift_ppm.unwrap_or(0) as i64;
+        let corrected = elapsed * 1_000_000 / (1_000_000 + drift_ppm);
+
+        Some((reference_timestamp as i64 + corrected) as u64)
+    }
+}
+
+impl Default for SyncedClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    const START_TIMESTAMP: u64 = 1_700_000_000_000;
+
+    /// Local counter of a device whose clock runs `drift_ppm` faster than the time master,
+    /// `elapsed` milliseconds of master time after the counter was at `start`
+    fn local_time(start: u32, drift_ppm: i64, elapsed: u64) -> u32 {
+        start.wrapping_add((elapsed as i64 * (1_000_000 + drift_ppm) / 1_000_000) as u32)
+    }
+
+    #[test]
+    fn unsynchronized_test() {
+        let clock = SyncedClock::new();
+
+        assert!(!clock.is_synchronized());
+        assert_eq!(clock.now(1234), None);
+    }
+
+    #[test]
+    fn synchronize_test() {
+        let mut clock = SyncedClock::new();
+
+        clock.synchronize(START_TIMESTAMP, 5000);
+
+        assert!(clock.is_synchronized());
+        assert_eq!(clock.drift_ppm(), None);
+        assert_eq!(clock.now(5000), Some(START_TIMESTAMP));
+        assert_eq!(clock.now(6500), Some(START_TIMESTAMP + 1500));
+    }
+
+    #[test]
+    fn drift_test() {
+        for &(start, drift_ppm) in [(0, 200), (u32::MAX - 100_000, -350), (1234, 0)].iter() {
+            let mut clock = SyncedClock::new();
+
+            for sync in 0..10 {
+                let elapsed = sync * 60_000;
+
+                clock.synchronize(
+                    START_TIMESTAMP + elapsed,
+                    local_time(start, drift_ppm, elapsed),
+                );
+            }
+
+            assert_eq!(clock.drift_ppm(), Some(drift_ppm as i32));
+
+            // One hour after the last synchronisation the clock is still on time
+            let elapsed = 9 * 60_000 + 3_600_000;
+            let now = clock.now(local_time(start, drift_ppm, elapsed)).unwrap();
+
+            assert!((now as i64 - (START_TIMESTAMP + elapsed) as i64).abs() <= 1);
+        }
+    }
+
+    #[test]
+    fn frequent_synchronize_test() {
+        let mut clock = SyncedClock::new();
+
+        // The time master synchronises more often than `MIN_DRIFT_INTERVAL`
+        for sync in 0..=30 {
+            let elapsed = sync * 2_000;
+
+            clock.synchronize(START_TIMESTAMP + elapsed, local_time(0, 200, elapsed));
+
+            if elapsed < MIN_DRIFT_INTERVAL as u64 {
+                assert_eq!(clock.drift_ppm(), None);
+            }
+        }
+
+        assert_eq!(clock.drift_ppm(), Some(200));
+    }
+
+    #[test]
+    fn time_jump_test() {
+        let mut clock = SyncedClock::new();
+
+        clock.synchronize(START_TIMESTAMP, 0);
+        clock.synchronize(START_TIMESTAMP + 60_000, 60_006);
+        assert_eq!(clock.drift_ppm(), Some(100));
+
+        // The time master was set one hour ahead
+        clock.synchronize(START_TIMESTAMP + 3_720_000, 120_012);
+        assert_eq!(clock.drift_ppm(), Some(100));
+        assert_eq!(clock.now(120_012), Some(START_TIMESTAMP + 3_720_000));
+    }
+
+    #[test]
+    fn handle_packet_test() {
+        let mut clock = SyncedClock::new();
+
+        let event = TimeSyncEvent::from_timestamp_millis(0xffff, 0x0123, START_TIMESTAMP);
+
+        assert!(clock.handle_packet(&event.to_packet(), 100));
+        assert_eq!(clock.now(100), Some(START_TIMESTAMP));
+
+        let packet = Packet {
+            is_error: false,
+            device_address: 0xffff,
+            data: vec![0x00, 0x00],
+        };
+
+        assert!(!clock.handle_packet(&packet, 200));
+    }
+}
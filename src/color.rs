@@ -0,0 +1,285 @@
+use crate::event::bcm::BcmValue;
+
+/// Colour temperature of the warm channel of a warm/cold white light
+pub const DEFAULT_WARM_WHITE_KELVIN: u16 = 2700;
+/// Colour temperature of the cold channel of a warm/cold white light
+pub const DEFAULT_COLD_WHITE_KELVIN: u16 = 6500;
+
+/// RGB colour of black body radiation, from 1000 K to 10000 K in steps of 500 K
+const KELVIN_TABLE: [(u8, u8, u8); 19] = [
+    (255, 68, 0),
+    (255, 108, 0),
+    (255, 137, 14),
+    (255, 159, 70),
+    (255, 177, 110),
+    (255, 193, 141),
+    (255, 206, 166),
+    (255, 218, 187),
+    (255, 228, 206),
+    (255, 237, 222),
+    (255, 246, 237),
+    (255, 254, 250),
+    (243, 242, 255),
+    (230, 235, 255),
+    (221, 230, 255),
+    (215, 226, 255),
+    (210, 223, 255),
+    (205, 220, 255),
+    (202, 218, 255),
+];
+const KELVIN_TABLE_START: u16 = 1000;
+const KELVIN_TABLE_STEP: u16 = 500;
+
+/// Converts a colour temperature to an RGB colour at full brightness
+///
+/// Temperatures outside of 1000 K - 10000 K are clamped.
+pub fn kelvin_to_rgb(kelvin: u16) -> (u8, u8, u8) {
+    let last_index = KELVIN_TABLE.len() - 1;
+    let max_kelvin = KELVIN_TABLE_START + KELVIN_TABLE_STEP * last_index as u16;
+    let offset = kelvin.clamp(KELVIN_TABLE_START, max_kelvin) - KELVIN_TABLE_START;
+
+    let index = (offset / KELVIN_TABLE_STEP) as usize;
+    let remainder = (offset % KELVIN_TABLE_STEP) as u32;
+
+    if index == last_index {
+        return KELVIN_TABLE[last_index];
+    }
+
+    let from = KELVIN_TABLE[index];
+    let to = KELVIN_TABLE[index + 1];
+    let mix = |from: u8, to: u8| {
+        ((from as u32 * (KELVIN_TABLE_STEP as u32 - remainder) + to as u32 * remainder)
+            / KELVIN_TABLE_STEP as u32) as u8
+    };
+
+    (mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
+}
+
+/// Converts a hue in degrees, saturation and value to an RGB colour
+pub fn hsv_to_rgb(hue: u16, saturation: u8, value: u8) -> (u8, u8, u8) {
+    let hue = hue % 360;
+    let region = hue / 60;
+    let remainder = (hue % 60) as u32 * 255 / 60;
+
+    let saturation = saturation as u32;
+    let value_u32 = value as u32;
+
+    let p = (value_u32 * (255 - saturation) / 255) as u8;
+    let q = (value_u32 * (255 - saturation * remainder / 255) / 255) as u8;
+    let t = (value_u32 * (255 - saturation * (255 - remainder) / 255) / 255) as u8;
+
+    match region {
+        0 => (value, t, p),
+        1 => (q, value, p),
+        2 => (p, value, t),
+        3 => (p, q, value),
+        4 => (t, p, value),
+        _ => (value, p, q),
+    }
+}
+
+/// Splits an RGB colour into RGB and white, putting the part all channels share into white
+pub fn rgb_to_rgbw(red: u8, green: u8, blue: u8) -> (u8, u8, u8, u8) {
+    let white = red.min(green).min(blue);
+
+    (red - white, green - white, blue - white, white)
+}
+
+/// Converts a colour temperature and brightness to warm and cold white channel values
+pub fn color_temperature_to_warm_cold_white(
+    kelvin: u16,
+    brightness: u8,
+    warm_kelvin: u16,
+    cold_kelvin: u16,
+) -> (u8, u8) {
+    if cold_kelvin <= warm_kelvin {
+        return (brightness, 0);
+    }
+
+    let kelvin = kelvin.clamp(warm_kelvin, cold_kelvin);
+    let cold = (brightness as u32 * (kelvin - warm_kelvin) as u32
+        / (cold_kelvin - warm_kelvin) as u32) as u8;
+
+    (brightness - cold, cold)
+}
+
+/// Converts warm and cold white channel values to a colour temperature and brightness
+pub fn warm_cold_white_to_color_temperature(
+    warm: u8,
+    cold: u8,
+    warm_kelvin: u16,
+    cold_kelvin: u16,
+) -> (u16, u8) {
+    let total = warm as u32 + cold as u32;
+
+    if total == 0 {
+        return (warm_kelvin, 0);
+    }
+
+    let kelvin =
+        warm_kelvin as i32 + (cold_kelvin as i32 - warm_kelvin as i32) * cold as i32 / total as i32;
+
+    (kelvin as u16, total.min(255) as u8)
+}
+
+/// Converts any `BcmValue` to RGB channel values
+///
+/// Warm/cold white values are assumed to use `DEFAULT_WARM_WHITE_KELVIN` and
+/// `DEFAULT_COLD_WHITE_KELVIN`.
+pub fn to_rgb(value: BcmValue) -> (u8, u8, u8) {
+    match value {
+        BcmValue::Binary(value) => {
+            let value = if value { 0xff } else { 0x00 };
+
+            (value, value, value)
+        }
+        BcmValue::Single(value) => (value, value, value),
+        BcmValue::Rgb(red, green, blue) => (red, green, blue),
+        BcmValue::RgbB(red, green, blue, brightness) => (
+            scale(red, brightness),
+            scale(green, brightness),
+            scale(blue, brightness),
+        ),
+        BcmValue::Rgbw(red, green, blue, white) => (
+            red.saturating_add(white),
+            green.saturating_add(white),
+            blue.saturating_add(white),
+        ),
+        BcmValue::RgbwB(red, green, blue, white, brightness) => (
+            scale(red.saturating_add(white), brightness),
+            scale(green.saturating_add(white), brightness),
+            scale(blue.saturating_add(white), brightness),
+        ),
+        BcmValue::ColorTemperature(kelvin, brightness) => {
+            let (red, green, blue) = kelvin_to_rgb(kelvin);
+
+            (
+                scale(red, brightness),
+                scale(green, brightness),
+                scale(blue, brightness),
+            )
+        }
+        BcmValue::WarmColdWhite(warm, cold) => {
+            let (kelvin, brightness) = warm_cold_white_to_color_temperature(
+                warm,
+                cold,
+                DEFAULT_WARM_WHITE_KELVIN,
+                DEFAULT_COLD_WHITE_KELVIN,
+            );
+
+            to_rgb(BcmValue::ColorTemperature(kelvin, brightness))
+        }
+        BcmValue::Hsv(hue, saturation, value) => hsv_to_rgb(hue, saturation, value),
+    }
+}
+
+/// Converts any `BcmValue` to RGBW channel values
+pub fn to_rgbw(value: BcmValue) -> (u8, u8, u8, u8) {
+    match value {
+        BcmValue::Binary(value) => (0x00, 0x00, 0x00, if value { 0xff } else { 0x00 }),
+        BcmValue::Single(value) => (0x00, 0x00, 0x00, value),
+        BcmValue::Rgbw(red, green, blue, white) => (red, green, blue, white),
+        BcmValue::RgbwB(red, green, blue, white, brightness) => (
+            scale(red, brightness),
+            scale(green, brightness),
+            scale(blue, brightness),
+            scale(white, brightness),
+        ),
+        value => {
+            let (red, green, blue) = to_rgb(value);
+
+            rgb_to_rgbw(red, green, blue)
+        }
+    }
+}
+
+/// Converts tunable white values to warm and cold white channel values
+///
+/// Returns `None` for values that are not tunable white.
+pub fn to_warm_cold_white(value: BcmValue, warm_kelvin: u16, cold_kelvin: u16) -> Option<(u8, u8)> {
+    match value {
+        BcmValue::ColorTemperature(kelvin, brightness) => Some(
+            color_temperature_to_warm_cold_white(kelvin, brightness, warm_kelvin, cold_kelvin),
+        ),
+        BcmValue::WarmColdWhite(warm, cold) => Some((warm, cold)),
+        _ => None,
+    }
+}
+
+fn scale(value: u8, brightness: u8) -> u8 {
+    ((value as u32 * brightness as u32 + 127) / 255) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kelvin_to_rgb_test() {
+        assert_eq!(kelvin_to_rgb(500), (255, 68, 0));
+        assert_eq!(kelvin_to_rgb(6500), (255, 254, 250));
+        assert_eq!(kelvin_to_rgb(6750), (249, 248, 252));
+        assert_eq!(kelvin_to_rgb(10000), (202, 218, 255));
+        assert_eq!(kelvin_to_rgb(20000), (202, 218, 255));
+    }
+
+    #[test]
+    fn hsv_to_rgb_test() {
+        assert_eq!(hsv_to_rgb(0, 255, 255), (255, 0, 0));
+        assert_eq!(hsv_to_rgb(120, 255, 255), (0, 255, 0));
+        assert_eq!(hsv_to_rgb(240, 255, 255), (0, 0, 255));
+        assert_eq!(hsv_to_rgb(60, 255, 255), (255, 255, 0));
+        assert_eq!(hsv_to_rgb(360, 255, 255), (255, 0, 0));
+        assert_eq!(hsv_to_rgb(200, 0, 128), (128, 128, 128));
+    }
+
+    #[test]
+    fn warm_cold_white_test() {
+        assert_eq!(
+            color_temperature_to_warm_cold_white(2700, 200, 2700, 6500),
+            (200, 0)
+        );
+        assert_eq!(
+            color_temperature_to_warm_cold_white(4600, 200, 2700, 6500),
+            (100, 100)
+        );
+        assert_eq!(
+            color_temperature_to_warm_cold_white(9000, 200, 2700, 6500),
+            (0, 200)
+        );
+        assert_eq!(
+            warm_cold_white_to_color_temperature(100, 100, 2700, 6500),
+            (4600, 200)
+        );
+        assert_eq!(
+            warm_cold_white_to_color_temperature(0, 0, 2700, 6500),
+            (2700, 0)
+        );
+    }
+
+    #[test]
+    fn to_rgbw_test() {
+        assert_eq!(
+            to_rgbw(BcmValue::Rgb(0xff, 0x80, 0x40)),
+            (0xbf, 0x40, 0x00, 0x40)
+        );
+        assert_eq!(to_rgbw(BcmValue::Single(0x80)), (0x00, 0x00, 0x00, 0x80));
+        assert_eq!(to_rgbw(BcmValue::Hsv(0, 0, 0xff)), (0x00, 0x00, 0x00, 0xff));
+        assert_eq!(
+            to_rgbw(BcmValue::ColorTemperature(6500, 0xff)),
+            (0x05, 0x04, 0x00, 0xfa)
+        );
+    }
+
+    #[test]
+    fn to_warm_cold_white_test() {
+        assert_eq!(
+            to_warm_cold_white(BcmValue::ColorTemperature(4600, 200), 2700, 6500),
+            Some((100, 100))
+        );
+        assert_eq!(
+            to_warm_cold_white(BcmValue::Rgb(0x00, 0x00, 0x00), 2700, 6500),
+            None
+        );
+    }
+}
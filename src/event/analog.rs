@@ -0,0 +1,123 @@
+use alloc::vec;
+use core::convert::TryInto;
+
+use crate::convert_packet::{ConvertPacket, ConvertPacketError};
+use crate::event::event_code::*;
+use crate::event::EventError;
+use crate::packet::Packet;
+
+/// Reports the raw value of an analog input
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct AnalogValueEvent {
+    pub receiver_address: u16,
+    pub analog_address: u16,
+    pub index: u8,
+    pub value: u16,
+}
+
+impl ConvertPacket<AnalogValueEvent> for AnalogValueEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 7 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != ANALOG_VALUE_EVENT_CODE {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let receiver_address = packet.device_address;
+        let analog_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = packet.data[4];
+        let value = u16::from_be_bytes(packet.data[5..=6].try_into().unwrap());
+
+        Ok(Self {
+            receiver_address,
+            analog_address,
+            index,
+            value,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(ANALOG_VALUE_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.analog_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.index);
+
+        for byte in u16::to_be_bytes(self.value).iter() {
+            data.push(*byte);
+        }
+
+        Packet {
+            is_error: false,
+            device_address: self.receiver_address,
+            data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EVENT_PACKET: Packet = Packet {
+        is_error: false,
+        device_address: 0xabab,
+        data: vec![],
+    };
+
+    #[test]
+    fn value_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((ANALOG_VALUE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (ANALOG_VALUE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                          // analog address
+            0x23,                                          // analog address
+            0x45,                                          // index
+            0x67,                                          // value
+            0x89,                                          // value
+        ];
+
+        let event = AnalogValueEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.receiver_address, 0xabab);
+        assert_eq!(event.analog_address, 0x0123);
+        assert_eq!(event.index, 0x45);
+        assert_eq!(event.value, 0x6789);
+    }
+
+    #[test]
+    fn value_to_packet_test() {
+        let event = AnalogValueEvent {
+            receiver_address: 0xabab,
+            analog_address: 0x0123,
+            index: 0x45,
+            value: 0x6789,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((ANALOG_VALUE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (ANALOG_VALUE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                          // analog address
+            0x23,                                          // analog address
+            0x45,                                          // index
+            0x67,                                          // value
+            0x89,                                          // value
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+}
@@ -15,6 +15,12 @@ pub enum BcmValue {
     RgbB(u8, u8, u8, u8),
     Rgbw(u8, u8, u8, u8),
     RgbwB(u8, u8, u8, u8, u8),
+    /// Tunable white given by its colour temperature in kelvin and brightness
+    ColorTemperature(u16, u8),
+    /// Tunable white given by the warm and cold white channel values
+    WarmColdWhite(u8, u8),
+    /// Hue in degrees (0 - 359), saturation and value
+    Hsv(u16, u8, u8),
 }
 
 impl BcmValue {
@@ -30,6 +36,35 @@ impl BcmValue {
             Self::RgbwB(red, green, blue, white, brightness) => {
                 vec![0x05, red, green, blue, white, brightness]
             }
+            Self::ColorTemperature(kelvin, brightness) => {
+                let kelvin = u16::to_be_bytes(kelvin);
+
+                vec![0x06, kelvin[0], kelvin[1], brightness]
+            }
+            Self::WarmColdWhite(warm, cold) => vec![0x07, warm, cold],
+            Self::Hsv(hue, saturation, value) => {
+                let hue = u16::to_be_bytes(hue);
+
+                vec![0x08, hue[0], hue[1], saturation, value]
+            }
+        }
+    }
+
+    /// Returns the length of the serialized value at the start of `data`
+    fn serialized_len(data: &[u8]) -> Result<usize, ConvertPacketError> {
+        if data.is_empty() {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        match data[0] {
+            0x00 | 0x01 => Ok(2),
+            0x02 => Ok(4),
+            0x03 | 0x04 => Ok(5),
+            0x05 => Ok(6),
+            0x06 => Ok(4),
+            0x07 => Ok(3),
+            0x08 => Ok(5),
+            _ => Err(ConvertPacketError::UnknownEnumVariant),
         }
     }
 
@@ -81,11 +116,81 @@ impl BcmValue {
 
                 Ok(Self::RgbwB(data[1], data[2], data[3], data[4], data[5]))
             }
+            0x06 => {
+                if data.len() != 4 {
+                    return Err(ConvertPacketError::WrongSize);
+                }
+
+                Ok(Self::ColorTemperature(
+                    u16::from_be_bytes(data[1..=2].try_into().unwrap()),
+                    data[3],
+                ))
+            }
+            0x07 => {
+                if data.len() != 3 {
+                    return Err(ConvertPacketError::WrongSize);
+                }
+
+                Ok(Self::WarmColdWhite(data[1], data[2]))
+            }
+            0x08 => {
+                if data.len() != 5 {
+                    return Err(ConvertPacketError::WrongSize);
+                }
+
+                Ok(Self::Hsv(
+                    u16::from_be_bytes(data[1..=2].try_into().unwrap()),
+                    data[3],
+                    data[4],
+                ))
+            }
+            _ => Err(ConvertPacketError::UnknownEnumVariant),
+        }
+    }
+}
+
+/// Curve used to animate from one `BcmValue` to another
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum BcmEasing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    /// Linear in perceived brightness instead of in duty cycle
+    Gamma,
+}
+
+impl BcmEasing {
+    fn serialize(self) -> u8 {
+        match self {
+            Self::Linear => 0x00,
+            Self::EaseIn => 0x01,
+            Self::EaseOut => 0x02,
+            Self::EaseInOut => 0x03,
+            Self::Gamma => 0x04,
+        }
+    }
+
+    fn deserialize(data: u8) -> Result<Self, ConvertPacketError> {
+        match data {
+            0x00 => Ok(Self::Linear),
+            0x01 => Ok(Self::EaseIn),
+            0x02 => Ok(Self::EaseOut),
+            0x03 => Ok(Self::EaseInOut),
+            0x04 => Ok(Self::Gamma),
             _ => Err(ConvertPacketError::UnknownEnumVariant),
         }
     }
 }
 
+/// Single step of a `BcmAnimateSequenceEvent`, animating to `value` over `duration` milliseconds
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct BcmKeyframe {
+    pub duration: u32,
+    pub easing: BcmEasing,
+    pub value: BcmValue,
+}
+
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub struct BcmChangeBrightnessEvent {
     pub bcm_address: u16,
@@ -145,6 +250,11 @@ impl ConvertPacket<BcmChangeBrightnessEvent> for BcmChangeBrightnessEvent {
     }
 }
 
+/// Animates a channel of a BCM to `target_value` over `duration` milliseconds
+///
+/// The easing is sent as an optional byte after the target value and left out for
+/// `BcmEasing::Linear`, so linear animations stay compatible with devices that don't know about
+/// easing.
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub struct BcmAnimateBrightnessEvent {
     pub bcm_address: u16,
@@ -152,6 +262,7 @@ pub struct BcmAnimateBrightnessEvent {
     pub index: u8,
     pub duration: u32,
     pub target_value: BcmValue,
+    pub easing: BcmEasing,
 }
 
 impl ConvertPacket<BcmAnimateBrightnessEvent> for BcmAnimateBrightnessEvent {
@@ -174,7 +285,17 @@ impl ConvertPacket<BcmAnimateBrightnessEvent> for BcmAnimateBrightnessEvent {
         let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
         let index = packet.data[4];
         let duration = u32::from_be_bytes(packet.data[5..=8].try_into().unwrap());
-        let target_value = BcmValue::deserialize(&packet.data[9..])?;
+        let value_len = BcmValue::serialized_len(&packet.data[9..])?;
+
+        if packet.data.len() < value_len + 9 || packet.data.len() > value_len + 10 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        let target_value = BcmValue::deserialize(&packet.data[9..9 + value_len])?;
+        let easing = match packet.data.get(9 + value_len) {
+            Some(easing) => BcmEasing::deserialize(*easing)?,
+            None => BcmEasing::Linear,
+        };
 
         Ok(Self {
             bcm_address,
@@ -182,6 +303,7 @@ impl ConvertPacket<BcmAnimateBrightnessEvent> for BcmAnimateBrightnessEvent {
             index,
             duration,
             target_value,
+            easing,
         })
     }
 
@@ -204,6 +326,363 @@ impl ConvertPacket<BcmAnimateBrightnessEvent> for BcmAnimateBrightnessEvent {
 
         data.append(&mut self.target_value.serialize());
 
+        if self.easing != BcmEasing::Linear {
+            data.push(self.easing.serialize());
+        }
+
+        Packet {
+            is_error: false,
+            device_address: self.bcm_address,
+            data,
+        }
+    }
+}
+
+/// Asks a BCM for the current value of one of its channels, answered with a `BcmReportValueEvent`
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct BcmGetValueEvent {
+    pub bcm_address: u16,
+    pub transmitter_address: u16,
+    pub index: u8,
+}
+
+impl ConvertPacket<BcmGetValueEvent> for BcmGetValueEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 5 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != BCM_GET_VALUE_EVENT_CODE {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let bcm_address = packet.device_address;
+        let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = packet.data[4];
+
+        Ok(Self {
+            bcm_address,
+            transmitter_address,
+            index,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(BCM_GET_VALUE_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.transmitter_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.index);
+
+        Packet {
+            is_error: false,
+            device_address: self.bcm_address,
+            data,
+        }
+    }
+}
+
+/// Reply to a `BcmGetValueEvent`
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct BcmReportValueEvent {
+    pub receiver_address: u16,
+    pub bcm_address: u16,
+    pub index: u8,
+    pub value: BcmValue,
+}
+
+impl ConvertPacket<BcmReportValueEvent> for BcmReportValueEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() < 7 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != BCM_REPORT_VALUE_EVENT_CODE
+        {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let receiver_address = packet.device_address;
+        let bcm_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = packet.data[4];
+        let value = BcmValue::deserialize(&packet.data[5..])?;
+
+        Ok(BcmReportValueEvent {
+            receiver_address,
+            bcm_address,
+            index,
+            value,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(BCM_REPORT_VALUE_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.bcm_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.index);
+        data.append(&mut self.value.serialize());
+
+        Packet {
+            is_error: false,
+            device_address: self.receiver_address,
+            data,
+        }
+    }
+}
+
+/// Stores the current values of all channels of a BCM as a scene
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct BcmStoreSceneEvent {
+    pub bcm_address: u16,
+    pub transmitter_address: u16,
+    pub scene: u8,
+}
+
+impl ConvertPacket<BcmStoreSceneEvent> for BcmStoreSceneEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 5 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != BCM_STORE_SCENE_EVENT_CODE
+        {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let bcm_address = packet.device_address;
+        let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let scene = packet.data[4];
+
+        Ok(Self {
+            bcm_address,
+            transmitter_address,
+            scene,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(BCM_STORE_SCENE_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.transmitter_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.scene);
+
+        Packet {
+            is_error: false,
+            device_address: self.bcm_address,
+            data,
+        }
+    }
+}
+
+/// Restores the values of all channels of a BCM from a scene
+///
+/// The channels are animated to the stored values over `duration` milliseconds, a duration of 0
+/// restores them immediately.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct BcmRecallSceneEvent {
+    pub bcm_address: u16,
+    pub transmitter_address: u16,
+    pub scene: u8,
+    pub duration: u32,
+}
+
+impl ConvertPacket<BcmRecallSceneEvent> for BcmRecallSceneEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 9 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != BCM_RECALL_SCENE_EVENT_CODE
+        {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let bcm_address = packet.device_address;
+        let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let scene = packet.data[4];
+        let duration = u32::from_be_bytes(packet.data[5..=8].try_into().unwrap());
+
+        Ok(Self {
+            bcm_address,
+            transmitter_address,
+            scene,
+            duration,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(BCM_RECALL_SCENE_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.transmitter_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.scene);
+
+        for byte in u32::to_be_bytes(self.duration).iter() {
+            data.push(*byte);
+        }
+
+        Packet {
+            is_error: false,
+            device_address: self.bcm_address,
+            data,
+        }
+    }
+}
+
+/// Animates a channel of a BCM through a list of keyframes
+///
+/// After the last keyframe the sequence starts over from the first one until it has been played
+/// `loop_count` times. A `loop_count` of 0 repeats the sequence until the channel is changed again.
+/// A sequence can hold at most `MAX_BCM_KEYFRAMES` keyframes, any further keyframes are left out
+/// when converting the event to a packet.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct BcmAnimateSequenceEvent {
+    pub bcm_address: u16,
+    pub transmitter_address: u16,
+    pub index: u8,
+    pub loop_count: u8,
+    pub keyframes: Vec<BcmKeyframe>,
+}
+
+/// Largest amount of keyframes in a `BcmAnimateSequenceEvent`
+pub const MAX_BCM_KEYFRAMES: usize = 255;
+
+impl ConvertPacket<BcmAnimateSequenceEvent> for BcmAnimateSequenceEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() < 7 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap())
+            != BCM_ANIMATE_SEQUENCE_EVENT_CODE
+        {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let bcm_address = packet.device_address;
+        let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = packet.data[4];
+        let loop_count = packet.data[5];
+        let keyframe_count = packet.data[6];
+
+        if keyframe_count as usize > MAX_BCM_KEYFRAMES {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        let mut keyframes = vec![];
+        let mut offset = 7;
+
+        for _ in 0..keyframe_count {
+            if packet.data.len() < offset + 5 {
+                return Err(ConvertPacketError::WrongSize);
+            }
+
+            let duration = u32::from_be_bytes(packet.data[offset..offset + 4].try_into().unwrap());
+            let easing = BcmEasing::deserialize(packet.data[offset + 4])?;
+            offset += 5;
+
+            let value_len = BcmValue::serialized_len(&packet.data[offset..])?;
+
+            if packet.data.len() < offset + value_len {
+                return Err(ConvertPacketError::WrongSize);
+            }
+
+            let value = BcmValue::deserialize(&packet.data[offset..offset + value_len])?;
+            offset += value_len;
+
+            keyframes.push(BcmKeyframe {
+                duration,
+                easing,
+                value,
+            });
+        }
+
+        if packet.data.len() != offset {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        Ok(Self {
+            bcm_address,
+            transmitter_address,
+            index,
+            loop_count,
+            keyframes,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(BCM_ANIMATE_SEQUENCE_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.transmitter_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.index);
+        data.push(self.loop_count);
+
+        // The keyframe count is a single byte, keyframes past `MAX_BCM_KEYFRAMES` are left out
+        let keyframes = &self.keyframes[..self.keyframes.len().min(MAX_BCM_KEYFRAMES)];
+        data.push(keyframes.len() as u8);
+
+        for keyframe in keyframes.iter() {
+            for byte in u32::to_be_bytes(keyframe.duration).iter() {
+                data.push(*byte);
+            }
+
+            data.push(keyframe.easing.serialize());
+            data.append(&mut keyframe.value.serialize());
+        }
+
         Packet {
             is_error: false,
             device_address: self.bcm_address,
@@ -270,6 +749,52 @@ mod tests {
         assert_eq!(event.to_packet(), packet);
     }
 
+    #[test]
+    fn change_brightness_hsv_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((BCM_CHANGE_BRIGHTNESS_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (BCM_CHANGE_BRIGHTNESS_EVENT_CODE & 0xff) as u8,        // event code
+            0x00,                                                   // transmitter address
+            0x00,                                                   // transmitter address
+            0x01,                                                   // index
+            0x08,                                                   // value
+            0x01,                                                   // value
+            0x0e,                                                   // value
+            0x45,                                                   // value
+            0x67,                                                   // value
+        ];
+
+        let event = BcmChangeBrightnessEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.value, BcmValue::Hsv(270, 0x45, 0x67));
+    }
+
+    #[test]
+    fn change_brightness_color_temperature_to_packet_test() {
+        let event = BcmChangeBrightnessEvent {
+            bcm_address: 0xabab,
+            transmitter_address: 0x0000,
+            index: 0x01,
+            value: BcmValue::ColorTemperature(2700, 0x45),
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((BCM_CHANGE_BRIGHTNESS_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (BCM_CHANGE_BRIGHTNESS_EVENT_CODE & 0xff) as u8,        // event code
+            0x00,                                                   // transmitter address
+            0x00,                                                   // transmitter address
+            0x01,                                                   // index
+            0x06,                                                   // value
+            0x0a,                                                   // value
+            0x8c,                                                   // value
+            0x45,                                                   // value
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
     #[test]
     fn animate_brightness_try_from_packet_test() {
         let mut packet = EVENT_PACKET;
@@ -296,6 +821,7 @@ mod tests {
         assert_eq!(event.index, 0x01);
         assert_eq!(event.duration, 0xabab_abab);
         assert_eq!(event.target_value, BcmValue::Rgb(0x23, 0x45, 0x67));
+        assert_eq!(event.easing, BcmEasing::Linear);
     }
 
     #[test]
@@ -306,6 +832,7 @@ mod tests {
             index: 0x01,
             duration: 0xabab_abab,
             target_value: BcmValue::Rgb(0x23, 0x45, 0x67),
+            easing: BcmEasing::Linear,
         };
 
         let mut packet = EVENT_PACKET;
@@ -327,4 +854,351 @@ mod tests {
 
         assert_eq!(event.to_packet(), packet);
     }
+
+    #[test]
+    fn animate_brightness_easing_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((BCM_ANIMATE_BRIGHTNESS_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (BCM_ANIMATE_BRIGHTNESS_EVENT_CODE & 0xff) as u8,        // event code
+            0x00,                                                    // transmitter address
+            0x00,                                                    // transmitter address
+            0x01,                                                    // index
+            0xab,                                                    // duration
+            0xab,                                                    // duration
+            0xab,                                                    // duration
+            0xab,                                                    // duration
+            0x01,                                                    // target value
+            0x23,                                                    // target value
+            0x04,                                                    // easing
+        ];
+
+        let event = BcmAnimateBrightnessEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.target_value, BcmValue::Single(0x23));
+        assert_eq!(event.easing, BcmEasing::Gamma);
+
+        packet.data.push(0x00);
+
+        assert_eq!(
+            BcmAnimateBrightnessEvent::try_from_packet(&packet),
+            Err(ConvertPacketError::WrongSize)
+        );
+    }
+
+    #[test]
+    fn animate_brightness_easing_to_packet_test() {
+        let event = BcmAnimateBrightnessEvent {
+            bcm_address: 0xabab,
+            transmitter_address: 0x0000,
+            index: 0x01,
+            duration: 0xabab_abab,
+            target_value: BcmValue::Single(0x23),
+            easing: BcmEasing::EaseInOut,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((BCM_ANIMATE_BRIGHTNESS_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (BCM_ANIMATE_BRIGHTNESS_EVENT_CODE & 0xff) as u8,        // event code
+            0x00,                                                    // transmitter address
+            0x00,                                                    // transmitter address
+            0x01,                                                    // index
+            0xab,                                                    // duration
+            0xab,                                                    // duration
+            0xab,                                                    // duration
+            0xab,                                                    // duration
+            0x01,                                                    // target value
+            0x23,                                                    // target value
+            0x03,                                                    // easing
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn animate_sequence_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((BCM_ANIMATE_SEQUENCE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (BCM_ANIMATE_SEQUENCE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                  // transmitter address
+            0x23,                                                  // transmitter address
+            0x01,                                                  // index
+            0x00,                                                  // loop count
+            0x02,                                                  // keyframe count
+            0x00,                                                  // duration
+            0x00,                                                  // duration
+            0x07,                                                  // duration
+            0xd0,                                                  // duration
+            0x03,                                                  // easing
+            0x01,                                                  // value
+            0xff,                                                  // value
+            0x00,                                                  // duration
+            0x00,                                                  // duration
+            0x07,                                                  // duration
+            0xd0,                                                  // duration
+            0x03,                                                  // easing
+            0x01,                                                  // value
+            0x00,                                                  // value
+        ];
+
+        let event = BcmAnimateSequenceEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.bcm_address, 0xabab);
+        assert_eq!(event.transmitter_address, 0x0123);
+        assert_eq!(event.index, 0x01);
+        assert_eq!(event.loop_count, 0x00);
+        assert_eq!(
+            event.keyframes,
+            vec![
+                BcmKeyframe {
+                    duration: 2000,
+                    easing: BcmEasing::EaseInOut,
+                    value: BcmValue::Single(0xff),
+                },
+                BcmKeyframe {
+                    duration: 2000,
+                    easing: BcmEasing::EaseInOut,
+                    value: BcmValue::Single(0x00),
+                },
+            ]
+        );
+
+        packet.data.pop();
+
+        assert_eq!(
+            BcmAnimateSequenceEvent::try_from_packet(&packet),
+            Err(ConvertPacketError::WrongSize)
+        );
+    }
+
+    #[test]
+    fn animate_sequence_to_packet_test() {
+        let event = BcmAnimateSequenceEvent {
+            bcm_address: 0xabab,
+            transmitter_address: 0x0123,
+            index: 0x01,
+            loop_count: 0x03,
+            keyframes: vec![BcmKeyframe {
+                duration: 2000,
+                easing: BcmEasing::Linear,
+                value: BcmValue::Rgb(0x23, 0x45, 0x67),
+            }],
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((BCM_ANIMATE_SEQUENCE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (BCM_ANIMATE_SEQUENCE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                  // transmitter address
+            0x23,                                                  // transmitter address
+            0x01,                                                  // index
+            0x03,                                                  // loop count
+            0x01,                                                  // keyframe count
+            0x00,                                                  // duration
+            0x00,                                                  // duration
+            0x07,                                                  // duration
+            0xd0,                                                  // duration
+            0x00,                                                  // easing
+            0x02,                                                  // value
+            0x23,                                                  // value
+            0x45,                                                  // value
+            0x67,                                                  // value
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn animate_sequence_too_many_keyframes_test() {
+        let keyframe = BcmKeyframe {
+            duration: 100,
+            easing: BcmEasing::Linear,
+            value: BcmValue::Single(0xff),
+        };
+
+        let mut event = BcmAnimateSequenceEvent {
+            bcm_address: 0xabab,
+            transmitter_address: 0x0123,
+            index: 0x01,
+            loop_count: 0x00,
+            keyframes: vec![keyframe; MAX_BCM_KEYFRAMES + 1],
+        };
+
+        let packet = event.to_packet();
+
+        event.keyframes.pop();
+
+        assert_eq!(packet.data[6], 0xff);
+        assert_eq!(BcmAnimateSequenceEvent::try_from_packet(&packet), Ok(event));
+    }
+
+    #[test]
+    fn get_value_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((BCM_GET_VALUE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (BCM_GET_VALUE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                           // transmitter address
+            0x23,                                           // transmitter address
+            0x01,                                           // index
+        ];
+
+        let event = BcmGetValueEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.bcm_address, 0xabab);
+        assert_eq!(event.transmitter_address, 0x0123);
+        assert_eq!(event.index, 0x01);
+    }
+
+    #[test]
+    fn get_value_to_packet_test() {
+        let event = BcmGetValueEvent {
+            bcm_address: 0xabab,
+            transmitter_address: 0x0123,
+            index: 0x01,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((BCM_GET_VALUE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (BCM_GET_VALUE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                           // transmitter address
+            0x23,                                           // transmitter address
+            0x01,                                           // index
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn report_value_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((BCM_REPORT_VALUE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (BCM_REPORT_VALUE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                              // bcm address
+            0x23,                                              // bcm address
+            0x01,                                              // index
+            0x01,                                              // value
+            0x45,                                              // value
+        ];
+
+        let event = BcmReportValueEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.receiver_address, 0xabab);
+        assert_eq!(event.bcm_address, 0x0123);
+        assert_eq!(event.index, 0x01);
+        assert_eq!(event.value, BcmValue::Single(0x45));
+    }
+
+    #[test]
+    fn report_value_to_packet_test() {
+        let event = BcmReportValueEvent {
+            receiver_address: 0xabab,
+            bcm_address: 0x0123,
+            index: 0x01,
+            value: BcmValue::Single(0x45),
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((BCM_REPORT_VALUE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (BCM_REPORT_VALUE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                              // bcm address
+            0x23,                                              // bcm address
+            0x01,                                              // index
+            0x01,                                              // value
+            0x45,                                              // value
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn store_scene_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((BCM_STORE_SCENE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (BCM_STORE_SCENE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                             // transmitter address
+            0x23,                                             // transmitter address
+            0x01,                                             // scene
+        ];
+
+        let event = BcmStoreSceneEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.bcm_address, 0xabab);
+        assert_eq!(event.transmitter_address, 0x0123);
+        assert_eq!(event.scene, 0x01);
+    }
+
+    #[test]
+    fn store_scene_to_packet_test() {
+        let event = BcmStoreSceneEvent {
+            bcm_address: 0xabab,
+            transmitter_address: 0x0123,
+            scene: 0x01,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((BCM_STORE_SCENE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (BCM_STORE_SCENE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                             // transmitter address
+            0x23,                                             // transmitter address
+            0x01,                                             // scene
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn recall_scene_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((BCM_RECALL_SCENE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (BCM_RECALL_SCENE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                              // transmitter address
+            0x23,                                              // transmitter address
+            0x01,                                              // scene
+            0xab,                                              // duration
+            0xab,                                              // duration
+            0xab,                                              // duration
+            0xab,                                              // duration
+        ];
+
+        let event = BcmRecallSceneEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.bcm_address, 0xabab);
+        assert_eq!(event.transmitter_address, 0x0123);
+        assert_eq!(event.scene, 0x01);
+        assert_eq!(event.duration, 0xabababab);
+    }
+
+    #[test]
+    fn recall_scene_to_packet_test() {
+        let event = BcmRecallSceneEvent {
+            bcm_address: 0xabab,
+            transmitter_address: 0x0123,
+            scene: 0x01,
+            duration: 0xabababab,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((BCM_RECALL_SCENE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (BCM_RECALL_SCENE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                              // transmitter address
+            0x23,                                              // transmitter address
+            0x01,                                              // scene
+            0xab,                                              // duration
+            0xab,                                              // duration
+            0xab,                                              // duration
+            0xab,                                              // duration
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
 }
@@ -113,6 +113,233 @@ impl ConvertPacket<ButtonReleasedEvent> for ButtonReleasedEvent {
     }
 }
 
+/// Sent when a button is pressed and released once
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ButtonClickedEvent {
+    pub receiver_address: u16,
+    pub button_address: u16,
+    pub index: u8,
+}
+
+impl ConvertPacket<ButtonClickedEvent> for ButtonClickedEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 5 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != BUTTON_CLICKED_EVENT_CODE {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let receiver_address = packet.device_address;
+        let button_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = packet.data[4];
+
+        Ok(Self {
+            receiver_address,
+            button_address,
+            index,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(BUTTON_CLICKED_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.button_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.index);
+
+        Packet {
+            is_error: false,
+            device_address: self.receiver_address,
+            data,
+        }
+    }
+}
+
+/// Sent when a button is clicked twice in quick succession
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ButtonDoubleClickedEvent {
+    pub receiver_address: u16,
+    pub button_address: u16,
+    pub index: u8,
+}
+
+impl ConvertPacket<ButtonDoubleClickedEvent> for ButtonDoubleClickedEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 5 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap())
+            != BUTTON_DOUBLE_CLICKED_EVENT_CODE
+        {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let receiver_address = packet.device_address;
+        let button_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = packet.data[4];
+
+        Ok(Self {
+            receiver_address,
+            button_address,
+            index,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(BUTTON_DOUBLE_CLICKED_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.button_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.index);
+
+        Packet {
+            is_error: false,
+            device_address: self.receiver_address,
+            data,
+        }
+    }
+}
+
+/// Sent once a button has been held down for the long press time
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ButtonLongPressedEvent {
+    pub receiver_address: u16,
+    pub button_address: u16,
+    pub index: u8,
+}
+
+impl ConvertPacket<ButtonLongPressedEvent> for ButtonLongPressedEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 5 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap())
+            != BUTTON_LONG_PRESSED_EVENT_CODE
+        {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let receiver_address = packet.device_address;
+        let button_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = packet.data[4];
+
+        Ok(Self {
+            receiver_address,
+            button_address,
+            index,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(BUTTON_LONG_PRESSED_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.button_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.index);
+
+        Packet {
+            is_error: false,
+            device_address: self.receiver_address,
+            data,
+        }
+    }
+}
+
+/// Sent repeatedly while a button is held down after a long press, `repeat` counts up from 1
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ButtonHeldEvent {
+    pub receiver_address: u16,
+    pub button_address: u16,
+    pub index: u8,
+    pub repeat: u16,
+}
+
+impl ConvertPacket<ButtonHeldEvent> for ButtonHeldEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 7 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != BUTTON_HELD_EVENT_CODE {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let receiver_address = packet.device_address;
+        let button_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = packet.data[4];
+        let repeat = u16::from_be_bytes(packet.data[5..=6].try_into().unwrap());
+
+        Ok(Self {
+            receiver_address,
+            button_address,
+            index,
+            repeat,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(BUTTON_HELD_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.button_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.index);
+
+        for byte in u16::to_be_bytes(self.repeat).iter() {
+            data.push(*byte);
+        }
+
+        Packet {
+            is_error: false,
+            device_address: self.receiver_address,
+            data,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -198,4 +425,162 @@ mod tests {
 
         assert_eq!(event.to_packet(), packet);
     }
+
+    #[test]
+    fn clicked_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((BUTTON_CLICKED_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (BUTTON_CLICKED_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                            // button address
+            0x23,                                            // button address
+            0x45,                                            // index
+        ];
+
+        let event = ButtonClickedEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.receiver_address, 0xabab);
+        assert_eq!(event.button_address, 0x0123);
+        assert_eq!(event.index, 0x45);
+    }
+
+    #[test]
+    fn clicked_to_packet_test() {
+        let event = ButtonClickedEvent {
+            receiver_address: 0xabab,
+            button_address: 0x0123,
+            index: 0x45,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((BUTTON_CLICKED_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (BUTTON_CLICKED_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                            // button address
+            0x23,                                            // button address
+            0x45,                                            // index
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn double_clicked_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((BUTTON_DOUBLE_CLICKED_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (BUTTON_DOUBLE_CLICKED_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                   // button address
+            0x23,                                                   // button address
+            0x45,                                                   // index
+        ];
+
+        let event = ButtonDoubleClickedEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.receiver_address, 0xabab);
+        assert_eq!(event.button_address, 0x0123);
+        assert_eq!(event.index, 0x45);
+    }
+
+    #[test]
+    fn double_clicked_to_packet_test() {
+        let event = ButtonDoubleClickedEvent {
+            receiver_address: 0xabab,
+            button_address: 0x0123,
+            index: 0x45,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((BUTTON_DOUBLE_CLICKED_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (BUTTON_DOUBLE_CLICKED_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                   // button address
+            0x23,                                                   // button address
+            0x45,                                                   // index
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn long_pressed_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((BUTTON_LONG_PRESSED_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (BUTTON_LONG_PRESSED_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                 // button address
+            0x23,                                                 // button address
+            0x45,                                                 // index
+        ];
+
+        let event = ButtonLongPressedEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.receiver_address, 0xabab);
+        assert_eq!(event.button_address, 0x0123);
+        assert_eq!(event.index, 0x45);
+    }
+
+    #[test]
+    fn long_pressed_to_packet_test() {
+        let event = ButtonLongPressedEvent {
+            receiver_address: 0xabab,
+            button_address: 0x0123,
+            index: 0x45,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((BUTTON_LONG_PRESSED_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (BUTTON_LONG_PRESSED_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                 // button address
+            0x23,                                                 // button address
+            0x45,                                                 // index
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn held_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((BUTTON_HELD_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (BUTTON_HELD_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                         // button address
+            0x23,                                         // button address
+            0x45,                                         // index
+            0x45,                                         // repeat
+            0x67,                                         // repeat
+        ];
+
+        let event = ButtonHeldEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.receiver_address, 0xabab);
+        assert_eq!(event.button_address, 0x0123);
+        assert_eq!(event.index, 0x45);
+        assert_eq!(event.repeat, 0x4567);
+    }
+
+    #[test]
+    fn held_to_packet_test() {
+        let event = ButtonHeldEvent {
+            receiver_address: 0xabab,
+            button_address: 0x0123,
+            index: 0x45,
+            repeat: 0x4567,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((BUTTON_HELD_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (BUTTON_HELD_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                         // button address
+            0x23,                                         // button address
+            0x45,                                         // index
+            0x45,                                         // repeat
+            0x67,                                         // repeat
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
 }
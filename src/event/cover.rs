@@ -0,0 +1,602 @@
+use alloc::vec;
+use core::convert::TryInto;
+
+use crate::convert_packet::{ConvertPacket, ConvertPacketError};
+use crate::event::event_code::*;
+use crate::event::EventError;
+use crate::packet::Packet;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum CoverState {
+    Stopped,
+    Opening,
+    Closing,
+}
+
+impl CoverState {
+    fn serialize(self) -> u8 {
+        match self {
+            Self::Stopped => 0x00,
+            Self::Opening => 0x01,
+            Self::Closing => 0x02,
+        }
+    }
+
+    fn deserialize(data: u8) -> Result<Self, ConvertPacketError> {
+        match data {
+            0x00 => Ok(Self::Stopped),
+            0x01 => Ok(Self::Opening),
+            0x02 => Ok(Self::Closing),
+            _ => Err(ConvertPacketError::UnknownEnumVariant),
+        }
+    }
+}
+
+/// Moves a cover to a position in percent, where 0 is fully closed and 100 is fully open
+///
+/// Covers are driven by two double exclusive relay channels, the first one opening and the second
+/// one closing the cover. The position is estimated from the calibrated travel times.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct CoverSetPositionEvent {
+    pub cover_address: u16,
+    pub transmitter_address: u16,
+    pub index: u8,
+    pub position: u8,
+}
+
+impl ConvertPacket<CoverSetPositionEvent> for CoverSetPositionEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 6 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap())
+            != COVER_SET_POSITION_EVENT_CODE
+        {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let cover_address = packet.device_address;
+        let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = packet.data[4];
+        let position = packet.data[5];
+
+        Ok(Self {
+            cover_address,
+            transmitter_address,
+            index,
+            position,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(COVER_SET_POSITION_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.transmitter_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.index);
+        data.push(self.position);
+
+        Packet {
+            is_error: false,
+            device_address: self.cover_address,
+            data,
+        }
+    }
+}
+
+/// Tilts the slats of a cover to an angle in percent, where 0 is fully closed and 100 is fully open
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct CoverSetTiltEvent {
+    pub cover_address: u16,
+    pub transmitter_address: u16,
+    pub index: u8,
+    pub tilt: u8,
+}
+
+impl ConvertPacket<CoverSetTiltEvent> for CoverSetTiltEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 6 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != COVER_SET_TILT_EVENT_CODE {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let cover_address = packet.device_address;
+        let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = packet.data[4];
+        let tilt = packet.data[5];
+
+        Ok(Self {
+            cover_address,
+            transmitter_address,
+            index,
+            tilt,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(COVER_SET_TILT_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.transmitter_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.index);
+        data.push(self.tilt);
+
+        Packet {
+            is_error: false,
+            device_address: self.cover_address,
+            data,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct CoverStopEvent {
+    pub cover_address: u16,
+    pub transmitter_address: u16,
+    pub index: u8,
+}
+
+impl ConvertPacket<CoverStopEvent> for CoverStopEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 5 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != COVER_STOP_EVENT_CODE {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let cover_address = packet.device_address;
+        let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = packet.data[4];
+
+        Ok(Self {
+            cover_address,
+            transmitter_address,
+            index,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(COVER_STOP_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.transmitter_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.index);
+
+        Packet {
+            is_error: false,
+            device_address: self.cover_address,
+            data,
+        }
+    }
+}
+
+/// Sets the time in milliseconds a cover takes to fully open, fully close and fully tilt
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct CoverCalibrateEvent {
+    pub cover_address: u16,
+    pub transmitter_address: u16,
+    pub index: u8,
+    pub opening_duration: u32,
+    pub closing_duration: u32,
+    pub tilt_duration: u32,
+}
+
+impl ConvertPacket<CoverCalibrateEvent> for CoverCalibrateEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 17 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != COVER_CALIBRATE_EVENT_CODE
+        {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let cover_address = packet.device_address;
+        let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = packet.data[4];
+        let opening_duration = u32::from_be_bytes(packet.data[5..=8].try_into().unwrap());
+        let closing_duration = u32::from_be_bytes(packet.data[9..=12].try_into().unwrap());
+        let tilt_duration = u32::from_be_bytes(packet.data[13..=16].try_into().unwrap());
+
+        Ok(Self {
+            cover_address,
+            transmitter_address,
+            index,
+            opening_duration,
+            closing_duration,
+            tilt_duration,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(COVER_CALIBRATE_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.transmitter_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.index);
+
+        for byte in u32::to_be_bytes(self.opening_duration).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u32::to_be_bytes(self.closing_duration).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u32::to_be_bytes(self.tilt_duration).iter() {
+            data.push(*byte);
+        }
+
+        Packet {
+            is_error: false,
+            device_address: self.cover_address,
+            data,
+        }
+    }
+}
+
+/// Sent by a cover whenever it starts moving, stops or reaches a position
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct CoverReportPositionEvent {
+    pub receiver_address: u16,
+    pub cover_address: u16,
+    pub index: u8,
+    pub position: u8,
+    pub tilt: u8,
+    pub state: CoverState,
+}
+
+impl ConvertPacket<CoverReportPositionEvent> for CoverReportPositionEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 8 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap())
+            != COVER_REPORT_POSITION_EVENT_CODE
+        {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let receiver_address = packet.device_address;
+        let cover_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = packet.data[4];
+        let position = packet.data[5];
+        let tilt = packet.data[6];
+        let state = CoverState::deserialize(packet.data[7])?;
+
+        Ok(Self {
+            receiver_address,
+            cover_address,
+            index,
+            position,
+            tilt,
+            state,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(COVER_REPORT_POSITION_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.cover_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.index);
+        data.push(self.position);
+        data.push(self.tilt);
+        data.push(self.state.serialize());
+
+        Packet {
+            is_error: false,
+            device_address: self.receiver_address,
+            data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EVENT_PACKET: Packet = Packet {
+        is_error: false,
+        device_address: 0xabab,
+        data: vec![],
+    };
+
+    #[test]
+    fn set_position_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((COVER_SET_POSITION_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (COVER_SET_POSITION_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                // transmitter address
+            0x23,                                                // transmitter address
+            0x45,                                                // index
+            0x32,                                                // position
+        ];
+
+        let event = CoverSetPositionEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.cover_address, 0xabab);
+        assert_eq!(event.transmitter_address, 0x0123);
+        assert_eq!(event.index, 0x45);
+        assert_eq!(event.position, 0x32);
+    }
+
+    #[test]
+    fn set_position_to_packet_test() {
+        let event = CoverSetPositionEvent {
+            cover_address: 0xabab,
+            transmitter_address: 0x0123,
+            index: 0x45,
+            position: 0x32,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((COVER_SET_POSITION_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (COVER_SET_POSITION_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                // transmitter address
+            0x23,                                                // transmitter address
+            0x45,                                                // index
+            0x32,                                                // position
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn set_tilt_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((COVER_SET_TILT_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (COVER_SET_TILT_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                            // transmitter address
+            0x23,                                            // transmitter address
+            0x45,                                            // index
+            0x32,                                            // tilt
+        ];
+
+        let event = CoverSetTiltEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.cover_address, 0xabab);
+        assert_eq!(event.transmitter_address, 0x0123);
+        assert_eq!(event.index, 0x45);
+        assert_eq!(event.tilt, 0x32);
+    }
+
+    #[test]
+    fn set_tilt_to_packet_test() {
+        let event = CoverSetTiltEvent {
+            cover_address: 0xabab,
+            transmitter_address: 0x0123,
+            index: 0x45,
+            tilt: 0x32,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((COVER_SET_TILT_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (COVER_SET_TILT_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                            // transmitter address
+            0x23,                                            // transmitter address
+            0x45,                                            // index
+            0x32,                                            // tilt
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn stop_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((COVER_STOP_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (COVER_STOP_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                        // transmitter address
+            0x23,                                        // transmitter address
+            0x45,                                        // index
+        ];
+
+        let event = CoverStopEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.cover_address, 0xabab);
+        assert_eq!(event.transmitter_address, 0x0123);
+        assert_eq!(event.index, 0x45);
+    }
+
+    #[test]
+    fn stop_to_packet_test() {
+        let event = CoverStopEvent {
+            cover_address: 0xabab,
+            transmitter_address: 0x0123,
+            index: 0x45,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((COVER_STOP_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (COVER_STOP_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                        // transmitter address
+            0x23,                                        // transmitter address
+            0x45,                                        // index
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn calibrate_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((COVER_CALIBRATE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (COVER_CALIBRATE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                             // transmitter address
+            0x23,                                             // transmitter address
+            0x45,                                             // index
+            0x00,                                             // opening duration
+            0x00,                                             // opening duration
+            0x4e,                                             // opening duration
+            0x20,                                             // opening duration
+            0x00,                                             // closing duration
+            0x00,                                             // closing duration
+            0x4a,                                             // closing duration
+            0x38,                                             // closing duration
+            0x00,                                             // tilt duration
+            0x00,                                             // tilt duration
+            0x05,                                             // tilt duration
+            0xdc,                                             // tilt duration
+        ];
+
+        let event = CoverCalibrateEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.cover_address, 0xabab);
+        assert_eq!(event.transmitter_address, 0x0123);
+        assert_eq!(event.index, 0x45);
+        assert_eq!(event.opening_duration, 0x00004e20);
+        assert_eq!(event.closing_duration, 0x00004a38);
+        assert_eq!(event.tilt_duration, 0x000005dc);
+    }
+
+    #[test]
+    fn calibrate_to_packet_test() {
+        let event = CoverCalibrateEvent {
+            cover_address: 0xabab,
+            transmitter_address: 0x0123,
+            index: 0x45,
+            opening_duration: 0x00004e20,
+            closing_duration: 0x00004a38,
+            tilt_duration: 0x000005dc,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((COVER_CALIBRATE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (COVER_CALIBRATE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                             // transmitter address
+            0x23,                                             // transmitter address
+            0x45,                                             // index
+            0x00,                                             // opening duration
+            0x00,                                             // opening duration
+            0x4e,                                             // opening duration
+            0x20,                                             // opening duration
+            0x00,                                             // closing duration
+            0x00,                                             // closing duration
+            0x4a,                                             // closing duration
+            0x38,                                             // closing duration
+            0x00,                                             // tilt duration
+            0x00,                                             // tilt duration
+            0x05,                                             // tilt duration
+            0xdc,                                             // tilt duration
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn report_position_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((COVER_REPORT_POSITION_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (COVER_REPORT_POSITION_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                   // cover address
+            0x23,                                                   // cover address
+            0x45,                                                   // index
+            0x32,                                                   // position
+            0x64,                                                   // tilt
+            0x02,                                                   // state
+        ];
+
+        let event = CoverReportPositionEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.receiver_address, 0xabab);
+        assert_eq!(event.cover_address, 0x0123);
+        assert_eq!(event.index, 0x45);
+        assert_eq!(event.position, 0x32);
+        assert_eq!(event.tilt, 0x64);
+        assert_eq!(event.state, CoverState::Closing);
+    }
+
+    #[test]
+    fn report_position_to_packet_test() {
+        let event = CoverReportPositionEvent {
+            receiver_address: 0xabab,
+            cover_address: 0x0123,
+            index: 0x45,
+            position: 0x32,
+            tilt: 0x64,
+            state: CoverState::Closing,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((COVER_REPORT_POSITION_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (COVER_REPORT_POSITION_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                   // cover address
+            0x23,                                                   // cover address
+            0x45,                                                   // index
+            0x32,                                                   // position
+            0x64,                                                   // tilt
+            0x02,                                                   // state
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+}
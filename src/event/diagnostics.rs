@@ -0,0 +1,289 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+use crate::convert_packet::{ConvertPacket, ConvertPacketError};
+use crate::event::event_code::*;
+use crate::event::EventError;
+use crate::interface::InterfaceStatistics;
+use crate::packet::Packet;
+use crate::protocol::ProtocolStatistics;
+
+/// Asks a device for its bus statistics, answered with a `DiagnosticsReportEvent`
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct DiagnosticsGetEvent {
+    pub device_address: u16,
+    pub transmitter_address: u16,
+}
+
+impl ConvertPacket<DiagnosticsGetEvent> for DiagnosticsGetEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 4 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != DIAGNOSTICS_GET_EVENT_CODE
+        {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let device_address = packet.device_address;
+        let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+
+        Ok(Self {
+            device_address,
+            transmitter_address,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(DIAGNOSTICS_GET_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.transmitter_address).iter() {
+            data.push(*byte);
+        }
+
+        Packet {
+            is_error: false,
+            device_address: self.device_address,
+            data,
+        }
+    }
+}
+
+/// Reply to a `DiagnosticsGetEvent` carrying the counters of a device's interface and protocol
+///
+/// Every counter is sent as a `u32`, the interface counters first.
+#[derive(Debug, PartialEq)]
+pub struct DiagnosticsReportEvent {
+    pub receiver_address: u16,
+    pub device_address: u16,
+    pub interface_statistics: InterfaceStatistics,
+    pub protocol_statistics: ProtocolStatistics,
+}
+
+const INTERFACE_COUNTERS: usize = 12;
+const PROTOCOL_COUNTERS: usize = 5;
+
+impl ConvertPacket<DiagnosticsReportEvent> for DiagnosticsReportEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 4 + (INTERFACE_COUNTERS + PROTOCOL_COUNTERS) * 4 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap())
+            != DIAGNOSTICS_REPORT_EVENT_CODE
+        {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let receiver_address = packet.device_address;
+        let device_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+
+        let counters: Vec<u32> = packet.data[4..]
+            .chunks(4)
+            .map(|chunk| u32::from_be_bytes(chunk.try_into().unwrap()))
+            .collect();
+
+        let interface_statistics = InterfaceStatistics {
+            frames_received: counters[0],
+            frames_sent: counters[1],
+            packets_received: counters[2],
+            packets_sent: counters[3],
+            cobs_errors: counters[4],
+            frame_errors: counters[5],
+            out_of_order_frames: counters[6],
+            builder_resets: counters[7],
+            timeouts: counters[8],
+            mailbox_full: counters[9],
+            overruns: counters[10],
+            io_errors: counters[11],
+        };
+
+        let protocol_statistics = ProtocolStatistics {
+            packets_received: counters[12],
+            packets_handled: counters[13],
+            packets_sent: counters[14],
+            handler_invocations: counters[15],
+            interface_errors: counters[16],
+        };
+
+        Ok(Self {
+            receiver_address,
+            device_address,
+            interface_statistics,
+            protocol_statistics,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(DIAGNOSTICS_REPORT_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.device_address).iter() {
+            data.push(*byte);
+        }
+
+        let interface = &self.interface_statistics;
+        let protocol = &self.protocol_statistics;
+
+        let counters: [u32; INTERFACE_COUNTERS + PROTOCOL_COUNTERS] = [
+            interface.frames_received,
+            interface.frames_sent,
+            interface.packets_received,
+            interface.packets_sent,
+            interface.cobs_errors,
+            interface.frame_errors,
+            interface.out_of_order_frames,
+            interface.builder_resets,
+            interface.timeouts,
+            interface.mailbox_full,
+            interface.overruns,
+            interface.io_errors,
+            protocol.packets_received,
+            protocol.packets_handled,
+            protocol.packets_sent,
+            protocol.handler_invocations,
+            protocol.interface_errors,
+        ];
+
+        for counter in counters.iter() {
+            data.extend_from_slice(&counter.to_be_bytes());
+        }
+
+        Packet {
+            is_error: false,
+            device_address: self.receiver_address,
+            data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EVENT_PACKET: Packet = Packet {
+        is_error: false,
+        device_address: 0xabab,
+        data: vec![],
+    };
+
+    fn report_event() -> DiagnosticsReportEvent {
+        DiagnosticsReportEvent {
+            receiver_address: 0xabab,
+            device_address: 0x0123,
+            interface_statistics: InterfaceStatistics {
+                frames_received: 0x0000_0001,
+                frames_sent: 0x0000_0002,
+                packets_received: 0x0000_0003,
+                packets_sent: 0x0000_0004,
+                cobs_errors: 0x0000_0005,
+                frame_errors: 0x0000_0006,
+                out_of_order_frames: 0x0000_0007,
+                builder_resets: 0x0000_0008,
+                timeouts: 0x0000_0009,
+                mailbox_full: 0x0000_000a,
+                overruns: 0x0000_000b,
+                io_errors: 0x0000_000c,
+            },
+            protocol_statistics: ProtocolStatistics {
+                packets_received: 0x0000_000d,
+                packets_handled: 0x0000_000e,
+                packets_sent: 0x0000_000f,
+                handler_invocations: 0x0000_0010,
+                interface_errors: 0x0000_0011,
+            },
+        }
+    }
+
+    fn report_data() -> Vec<u8> {
+        let mut data = vec![
+            ((DIAGNOSTICS_REPORT_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (DIAGNOSTICS_REPORT_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                // device address
+            0x23,                                                // device address
+        ];
+
+        for counter in 0x01u32..=0x11 {
+            data.extend_from_slice(&counter.to_be_bytes());
+        }
+
+        data
+    }
+
+    #[test]
+    fn get_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((DIAGNOSTICS_GET_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (DIAGNOSTICS_GET_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                             // transmitter address
+            0x23,                                             // transmitter address
+        ];
+
+        let event = DiagnosticsGetEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.device_address, 0xabab);
+        assert_eq!(event.transmitter_address, 0x0123);
+    }
+
+    #[test]
+    fn get_to_packet_test() {
+        let event = DiagnosticsGetEvent {
+            device_address: 0xabab,
+            transmitter_address: 0x0123,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((DIAGNOSTICS_GET_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (DIAGNOSTICS_GET_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                             // transmitter address
+            0x23,                                             // transmitter address
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn report_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = report_data();
+
+        assert_eq!(
+            DiagnosticsReportEvent::try_from_packet(&packet).unwrap(),
+            report_event()
+        );
+
+        packet.data.pop();
+
+        assert!(matches!(
+            DiagnosticsReportEvent::try_from_packet(&packet),
+            Err(ConvertPacketError::WrongSize)
+        ));
+    }
+
+    #[test]
+    fn report_to_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = report_data();
+
+        assert_eq!(report_event().to_packet(), packet);
+    }
+}
@@ -0,0 +1,124 @@
+use alloc::vec;
+use core::convert::TryInto;
+
+use crate::convert_packet::{ConvertPacket, ConvertPacketError};
+use crate::event::event_code::*;
+use crate::event::EventError;
+use crate::packet::Packet;
+
+/// Sent when a rotary encoder has been turned by `delta` steps, positive values being clockwise
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct EncoderRotatedEvent {
+    pub receiver_address: u16,
+    pub encoder_address: u16,
+    pub index: u8,
+    pub delta: i16,
+}
+
+impl ConvertPacket<EncoderRotatedEvent> for EncoderRotatedEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 7 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != ENCODER_ROTATED_EVENT_CODE
+        {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let receiver_address = packet.device_address;
+        let encoder_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = packet.data[4];
+        let delta = i16::from_be_bytes(packet.data[5..=6].try_into().unwrap());
+
+        Ok(Self {
+            receiver_address,
+            encoder_address,
+            index,
+            delta,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(ENCODER_ROTATED_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.encoder_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.index);
+
+        for byte in i16::to_be_bytes(self.delta).iter() {
+            data.push(*byte);
+        }
+
+        Packet {
+            is_error: false,
+            device_address: self.receiver_address,
+            data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EVENT_PACKET: Packet = Packet {
+        is_error: false,
+        device_address: 0xabab,
+        data: vec![],
+    };
+
+    #[test]
+    fn rotated_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((ENCODER_ROTATED_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (ENCODER_ROTATED_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                             // encoder address
+            0x23,                                             // encoder address
+            0x45,                                             // index
+            0xff,                                             // delta
+            0xfd,                                             // delta
+        ];
+
+        let event = EncoderRotatedEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.receiver_address, 0xabab);
+        assert_eq!(event.encoder_address, 0x0123);
+        assert_eq!(event.index, 0x45);
+        assert_eq!(event.delta, -3);
+    }
+
+    #[test]
+    fn rotated_to_packet_test() {
+        let event = EncoderRotatedEvent {
+            receiver_address: 0xabab,
+            encoder_address: 0x0123,
+            index: 0x45,
+            delta: -3,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((ENCODER_ROTATED_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (ENCODER_ROTATED_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                             // encoder address
+            0x23,                                             // encoder address
+            0x45,                                             // index
+            0xff,                                             // delta
+            0xfd,                                             // delta
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+}
@@ -0,0 +1,257 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+use crate::convert_packet::{ConvertPacket, ConvertPacketError};
+use crate::packet::Packet;
+
+/// Reason a device rejected an event
+///
+/// Codes from `0x80` upwards are free for application specific errors and are decoded as `Custom`,
+/// codes between the named ones and `0x80` are reserved and rejected when decoding.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum ErrorCode {
+    /// The device does not handle events with this event code
+    UnsupportedEvent,
+    /// The event could not be decoded
+    MalformedEvent,
+    /// One of the event values is out of range
+    InvalidValue,
+    /// The device can not handle the event right now, it may be retried later
+    Busy,
+    /// The event is not allowed in the current device state
+    InvalidState,
+    /// The device failed to read or write its storage
+    StorageError,
+    /// Any other failure on the device side
+    InternalError,
+    Custom(CustomErrorCode),
+}
+
+/// Application specific error code, always `0x80` or above
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct CustomErrorCode(u8);
+
+impl CustomErrorCode {
+    /// Returns `None` for codes below `0x80`, which are reserved for the protocol
+    pub const fn new(code: u8) -> Option<Self> {
+        if code >= 0x80 {
+            Some(Self(code))
+        } else {
+            None
+        }
+    }
+
+    pub const fn code(self) -> u8 {
+        self.0
+    }
+}
+
+impl ErrorCode {
+    fn serialize(self) -> u8 {
+        match self {
+            Self::UnsupportedEvent => 0x00,
+            Self::MalformedEvent => 0x01,
+            Self::InvalidValue => 0x02,
+            Self::Busy => 0x03,
+            Self::InvalidState => 0x04,
+            Self::StorageError => 0x05,
+            Self::InternalError => 0x06,
+            Self::Custom(code) => code.code(),
+        }
+    }
+
+    fn deserialize(data: u8) -> Result<Self, ConvertPacketError> {
+        match data {
+            0x00 => Ok(Self::UnsupportedEvent),
+            0x01 => Ok(Self::MalformedEvent),
+            0x02 => Ok(Self::InvalidValue),
+            0x03 => Ok(Self::Busy),
+            0x04 => Ok(Self::InvalidState),
+            0x05 => Ok(Self::StorageError),
+            0x06 => Ok(Self::InternalError),
+            code => CustomErrorCode::new(code)
+                .map(Self::Custom)
+                .ok_or(ConvertPacketError::UnknownEnumVariant),
+        }
+    }
+}
+
+/// Error reply to a failed event
+///
+/// Error packets have `is_error` set and carry the event code of the event that failed in place of
+/// their own event code, followed by the transmitter address, the error code and any detail bytes.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ErrorEvent {
+    pub receiver_address: u16,
+    pub transmitter_address: u16,
+    pub event_code: u16,
+    pub error_code: ErrorCode,
+    pub detail: Vec<u8>,
+}
+
+impl ConvertPacket<ErrorEvent> for ErrorEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() < 5 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if !packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        let receiver_address = packet.device_address;
+        let event_code = u16::from_be_bytes(packet.data[0..=1].try_into().unwrap());
+        let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let error_code = ErrorCode::deserialize(packet.data[4])?;
+        let detail = packet.data[5..].to_vec();
+
+        Ok(ErrorEvent {
+            receiver_address,
+            transmitter_address,
+            event_code,
+            error_code,
+            detail,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(self.event_code).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.transmitter_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.error_code.serialize());
+
+        for byte in self.detail.iter() {
+            data.push(*byte);
+        }
+
+        Packet {
+            is_error: true,
+            device_address: self.receiver_address,
+            data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::event::event_code::RELAY_SET_VALUE_EVENT_CODE;
+
+    const EVENT_PACKET: Packet = Packet {
+        is_error: true,
+        device_address: 0xabab,
+        data: vec![],
+    };
+
+    #[test]
+    fn try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((RELAY_SET_VALUE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (RELAY_SET_VALUE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                             // transmitter address
+            0x23,                                             // transmitter address
+            0x02,                                             // error code
+            0x45,                                             // detail
+            0x67,                                             // detail
+        ];
+
+        let event = ErrorEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.receiver_address, 0xabab);
+        assert_eq!(event.transmitter_address, 0x0123);
+        assert_eq!(event.event_code, RELAY_SET_VALUE_EVENT_CODE);
+        assert_eq!(event.error_code, ErrorCode::InvalidValue);
+        assert_eq!(event.detail, vec![0x45, 0x67]);
+    }
+
+    #[test]
+    fn try_from_packet_not_error_test() {
+        let mut packet = EVENT_PACKET;
+        packet.is_error = false;
+        packet.data = vec![
+            ((RELAY_SET_VALUE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (RELAY_SET_VALUE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                             // transmitter address
+            0x23,                                             // transmitter address
+            0x02,                                             // error code
+        ];
+
+        assert_eq!(
+            ErrorEvent::try_from_packet(&packet),
+            Err(ConvertPacketError::WrongType)
+        );
+    }
+
+    #[test]
+    fn try_from_packet_reserved_code_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((RELAY_SET_VALUE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (RELAY_SET_VALUE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                             // transmitter address
+            0x23,                                             // transmitter address
+            0x07,                                             // error code
+        ];
+
+        assert_eq!(
+            ErrorEvent::try_from_packet(&packet),
+            Err(ConvertPacketError::UnknownEnumVariant)
+        );
+
+        packet.data[4] = 0x7f;
+
+        assert_eq!(
+            ErrorEvent::try_from_packet(&packet),
+            Err(ConvertPacketError::UnknownEnumVariant)
+        );
+
+        packet.data[4] = 0x80;
+
+        assert_eq!(
+            ErrorEvent::try_from_packet(&packet).unwrap().error_code,
+            ErrorCode::Custom(CustomErrorCode::new(0x80).unwrap())
+        );
+    }
+
+    #[test]
+    fn custom_error_code_test() {
+        assert_eq!(CustomErrorCode::new(0x06), None);
+        assert_eq!(CustomErrorCode::new(0x7f), None);
+        assert_eq!(
+            CustomErrorCode::new(0xff).map(CustomErrorCode::code),
+            Some(0xff)
+        );
+    }
+
+    #[test]
+    fn to_packet_test() {
+        let event = ErrorEvent {
+            receiver_address: 0xabab,
+            transmitter_address: 0x0123,
+            event_code: RELAY_SET_VALUE_EVENT_CODE,
+            error_code: ErrorCode::Custom(CustomErrorCode::new(0x89).unwrap()),
+            detail: vec![],
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((RELAY_SET_VALUE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (RELAY_SET_VALUE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                             // transmitter address
+            0x23,                                             // transmitter address
+            0x89,                                             // error code
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+}
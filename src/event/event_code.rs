@@ -25,3 +25,61 @@ pub const BCM_ANIMATE_BRIGHTNESS_EVENT_CODE: u16 = 0x000d;
 pub const RELAY_SET_VALUE_EVENT_CODE: u16 = 0x000e;
 
 pub const GATEWAY_DISCOVER_EVENT_CODE: u16 = 0x000f;
+
+pub const SEQUENCED_EVENT_CODE: u16 = 0x0010;
+
+pub const RELAY_GET_VALUE_EVENT_CODE: u16 = 0x0011;
+pub const RELAY_REPORT_VALUE_EVENT_CODE: u16 = 0x0012;
+pub const RELAY_VALUE_CHANGED_EVENT_CODE: u16 = 0x0013;
+
+pub const COVER_SET_POSITION_EVENT_CODE: u16 = 0x0014;
+pub const COVER_SET_TILT_EVENT_CODE: u16 = 0x0015;
+pub const COVER_STOP_EVENT_CODE: u16 = 0x0016;
+pub const COVER_CALIBRATE_EVENT_CODE: u16 = 0x0017;
+pub const COVER_REPORT_POSITION_EVENT_CODE: u16 = 0x0018;
+
+pub const BCM_GET_VALUE_EVENT_CODE: u16 = 0x0019;
+pub const BCM_REPORT_VALUE_EVENT_CODE: u16 = 0x001a;
+pub const BCM_STORE_SCENE_EVENT_CODE: u16 = 0x001b;
+pub const BCM_RECALL_SCENE_EVENT_CODE: u16 = 0x001c;
+
+pub const BCM_ANIMATE_SEQUENCE_EVENT_CODE: u16 = 0x001d;
+
+pub const BUTTON_CLICKED_EVENT_CODE: u16 = 0x001e;
+pub const BUTTON_DOUBLE_CLICKED_EVENT_CODE: u16 = 0x001f;
+pub const BUTTON_LONG_PRESSED_EVENT_CODE: u16 = 0x0020;
+pub const BUTTON_HELD_EVENT_CODE: u16 = 0x0021;
+
+pub const ENCODER_ROTATED_EVENT_CODE: u16 = 0x0022;
+
+pub const ANALOG_VALUE_EVENT_CODE: u16 = 0x0023;
+
+pub const SENSOR_GET_READING_EVENT_CODE: u16 = 0x0024;
+pub const SENSOR_READING_EVENT_CODE: u16 = 0x0025;
+
+pub const RELAY_REPORT_POWER_EVENT_CODE: u16 = 0x0026;
+pub const RELAY_REPORT_ENERGY_EVENT_CODE: u16 = 0x0027;
+pub const RELAY_OVER_CURRENT_EVENT_CODE: u16 = 0x0028;
+pub const RELAY_RESET_ENERGY_EVENT_CODE: u16 = 0x0029;
+
+pub const TIME_GET_EVENT_CODE: u16 = 0x002a;
+pub const TIME_SYNC_EVENT_CODE: u16 = 0x002b;
+
+pub const SCHEDULE_ADD_EVENT_CODE: u16 = 0x002c;
+pub const SCHEDULE_REMOVE_EVENT_CODE: u16 = 0x002d;
+pub const SCHEDULE_LIST_EVENT_CODE: u16 = 0x002e;
+pub const SCHEDULE_REPORT_EVENT_CODE: u16 = 0x002f;
+
+pub const GROUP_ADD_EVENT_CODE: u16 = 0x0030;
+pub const GROUP_REMOVE_EVENT_CODE: u16 = 0x0031;
+pub const GROUP_GET_EVENT_CODE: u16 = 0x0032;
+pub const GROUP_REPORT_EVENT_CODE: u16 = 0x0033;
+
+pub const PARAMETER_READ_EVENT_CODE: u16 = 0x0034;
+pub const PARAMETER_WRITE_EVENT_CODE: u16 = 0x0035;
+pub const PARAMETER_REPORT_EVENT_CODE: u16 = 0x0036;
+pub const PARAMETER_DESCRIBE_EVENT_CODE: u16 = 0x0037;
+pub const PARAMETER_DESCRIPTION_EVENT_CODE: u16 = 0x0038;
+
+pub const DIAGNOSTICS_GET_EVENT_CODE: u16 = 0x0039;
+pub const DIAGNOSTICS_REPORT_EVENT_CODE: u16 = 0x003a;
@@ -55,6 +55,132 @@ impl ConvertPacket<AckEvent> for AckEvent {
     }
 }
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum AckStatus {
+    Ok,
+    /// The event was received, but the device can not handle it right now
+    Busy,
+    /// The event was received, but its values are invalid
+    Invalid,
+    /// The event was received, but the device does not handle it
+    Unsupported,
+}
+
+impl AckStatus {
+    fn serialize(self) -> u8 {
+        match self {
+            Self::Ok => 0x00,
+            Self::Busy => 0x01,
+            Self::Invalid => 0x02,
+            Self::Unsupported => 0x03,
+        }
+    }
+
+    fn deserialize(data: u8) -> Result<Self, ConvertPacketError> {
+        match data {
+            0x00 => Ok(Self::Ok),
+            0x01 => Ok(Self::Busy),
+            0x02 => Ok(Self::Invalid),
+            0x03 => Ok(Self::Unsupported),
+            _ => Err(ConvertPacketError::UnknownEnumVariant),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct AckDetails {
+    /// Event code of the acknowledged event
+    pub event_code: u16,
+    pub sequence: u16,
+    pub status: AckStatus,
+}
+
+/// Acknowledgement that may say which event is acknowledged and whether it was accepted
+///
+/// Shares the event code with `AckEvent`. Plain 4-byte acknowledgements are decoded with `details`
+/// set to `None`, and an event without `details` is encoded exactly like an `AckEvent`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ExtendedAckEvent {
+    pub receiver_address: u16,
+    pub transmitter_address: u16,
+    pub details: Option<AckDetails>,
+}
+
+impl ExtendedAckEvent {
+    /// Returns `true` if the event was acknowledged without details or with `AckStatus::Ok`
+    pub fn is_ok(&self) -> bool {
+        match self.details {
+            Some(details) => details.status == AckStatus::Ok,
+            None => true,
+        }
+    }
+}
+
+impl ConvertPacket<ExtendedAckEvent> for ExtendedAckEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 4 && packet.data.len() != 9 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != ACK_EVENT_CODE {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let receiver_address = packet.device_address;
+        let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+
+        let details = if packet.data.len() == 9 {
+            Some(AckDetails {
+                event_code: u16::from_be_bytes(packet.data[4..=5].try_into().unwrap()),
+                sequence: u16::from_be_bytes(packet.data[6..=7].try_into().unwrap()),
+                status: AckStatus::deserialize(packet.data[8])?,
+            })
+        } else {
+            None
+        };
+
+        Ok(ExtendedAckEvent {
+            receiver_address,
+            transmitter_address,
+            details,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(ACK_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.transmitter_address).iter() {
+            data.push(*byte);
+        }
+
+        if let Some(details) = self.details {
+            for byte in u16::to_be_bytes(details.event_code).iter() {
+                data.push(*byte);
+            }
+
+            for byte in u16::to_be_bytes(details.sequence).iter() {
+                data.push(*byte);
+            }
+
+            data.push(details.status.serialize());
+        }
+
+        Packet {
+            is_error: false,
+            device_address: self.receiver_address,
+            data,
+        }
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub struct DataEvent {
     pub receiver_address: u16,
@@ -65,6 +191,10 @@ pub struct DataEvent {
 
 impl ConvertPacket<DataEvent> for DataEvent {
     fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() < 6 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
         if packet.is_error {
             return Err(ConvertPacketError::WrongType);
         }
@@ -122,6 +252,78 @@ impl ConvertPacket<DataEvent> for DataEvent {
     }
 }
 
+/// Envelope carrying another event together with a sequence number
+///
+/// `session` changes whenever the transmitter restarts, so its sequence numbers starting over are
+/// not mistaken for duplicates. `data` holds the data of the wrapped packet, starting with its
+/// event code.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct SequencedEvent {
+    pub receiver_address: u16,
+    pub transmitter_address: u16,
+    pub session: u8,
+    pub sequence: u16,
+    pub data: Vec<u8>,
+}
+
+impl ConvertPacket<SequencedEvent> for SequencedEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() < 7 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != SEQUENCED_EVENT_CODE {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let receiver_address = packet.device_address;
+        let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let session = packet.data[4];
+        let sequence = u16::from_be_bytes(packet.data[5..=6].try_into().unwrap());
+        let data = packet.data[7..].to_vec();
+
+        Ok(SequencedEvent {
+            receiver_address,
+            transmitter_address,
+            session,
+            sequence,
+            data,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(SEQUENCED_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.transmitter_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.session);
+
+        for byte in u16::to_be_bytes(self.sequence).iter() {
+            data.push(*byte);
+        }
+
+        for byte in self.data.iter() {
+            data.push(*byte);
+        }
+
+        Packet {
+            is_error: false,
+            device_address: self.receiver_address,
+            data,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -166,6 +368,80 @@ mod tests {
         assert_eq!(event.to_packet(), packet);
     }
 
+    #[test]
+    fn extended_ack_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((ACK_EVENT_CODE >> 8) & 0xff) as u8,  // event code
+            (ACK_EVENT_CODE & 0xff) as u8,         // event code
+            0x01,                                  // transmitter address
+            0x23,                                  // transmitter address
+            ((DATA_EVENT_CODE >> 8) & 0xff) as u8, // acknowledged event code
+            (DATA_EVENT_CODE & 0xff) as u8,        // acknowledged event code
+            0x45,                                  // sequence
+            0x67,                                  // sequence
+            0x01,                                  // status
+        ];
+
+        let event = ExtendedAckEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.receiver_address, 0xabab);
+        assert_eq!(event.transmitter_address, 0x0123);
+        assert_eq!(
+            event.details,
+            Some(AckDetails {
+                event_code: DATA_EVENT_CODE,
+                sequence: 0x4567,
+                status: AckStatus::Busy,
+            })
+        );
+        assert!(!event.is_ok());
+    }
+
+    #[test]
+    fn extended_ack_try_from_legacy_packet_test() {
+        let packet = AckEvent {
+            receiver_address: 0xabab,
+            transmitter_address: 0x0123,
+        }
+        .to_packet();
+
+        let event = ExtendedAckEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.receiver_address, 0xabab);
+        assert_eq!(event.transmitter_address, 0x0123);
+        assert_eq!(event.details, None);
+        assert!(event.is_ok());
+    }
+
+    #[test]
+    fn extended_ack_to_packet_test() {
+        let event = ExtendedAckEvent {
+            receiver_address: 0xabab,
+            transmitter_address: 0x0123,
+            details: Some(AckDetails {
+                event_code: DATA_EVENT_CODE,
+                sequence: 0x4567,
+                status: AckStatus::Unsupported,
+            }),
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((ACK_EVENT_CODE >> 8) & 0xff) as u8,  // event code
+            (ACK_EVENT_CODE & 0xff) as u8,         // event code
+            0x01,                                  // transmitter address
+            0x23,                                  // transmitter address
+            ((DATA_EVENT_CODE >> 8) & 0xff) as u8, // acknowledged event code
+            (DATA_EVENT_CODE & 0xff) as u8,        // acknowledged event code
+            0x45,                                  // sequence
+            0x67,                                  // sequence
+            0x03,                                  // status
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
     #[test]
     fn data_try_from_packet_test() {
         let mut packet = EVENT_PACKET;
@@ -217,4 +493,76 @@ mod tests {
 
         assert_eq!(event.to_packet(), packet);
     }
+
+    #[test]
+    fn data_try_from_packet_too_short_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((DATA_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (DATA_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                  // transmitter address
+        ];
+
+        assert_eq!(
+            DataEvent::try_from_packet(&packet),
+            Err(ConvertPacketError::WrongSize)
+        );
+
+        packet.data = vec![];
+
+        assert_eq!(
+            DataEvent::try_from_packet(&packet),
+            Err(ConvertPacketError::WrongSize)
+        );
+    }
+
+    #[test]
+    fn sequenced_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((SEQUENCED_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (SEQUENCED_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                       // transmitter address
+            0x23,                                       // transmitter address
+            0x89,                                       // session
+            0x45,                                       // sequence
+            0x67,                                       // sequence
+            ((ACK_EVENT_CODE >> 8) & 0xff) as u8,       // data
+            (ACK_EVENT_CODE & 0xff) as u8,              // data
+        ];
+
+        let event = SequencedEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.receiver_address, 0xabab);
+        assert_eq!(event.transmitter_address, 0x0123);
+        assert_eq!(event.session, 0x89);
+        assert_eq!(event.sequence, 0x4567);
+        assert_eq!(event.data, vec![0x00, 0x03]);
+    }
+
+    #[test]
+    fn sequenced_to_packet_test() {
+        let event = SequencedEvent {
+            receiver_address: 0xabab,
+            transmitter_address: 0x0123,
+            session: 0x89,
+            sequence: 0x4567,
+            data: vec![0x00, 0x03],
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((SEQUENCED_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (SEQUENCED_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                       // transmitter address
+            0x23,                                       // transmitter address
+            0x89,                                       // session
+            0x45,                                       // sequence
+            0x67,                                       // sequence
+            0x00,                                       // data
+            0x03,                                       // data
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
 }
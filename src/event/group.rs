@@ -0,0 +1,416 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+use crate::convert_packet::{ConvertPacket, ConvertPacketError};
+use crate::event::event_code::*;
+use crate::event::EventError;
+use crate::packet::Packet;
+
+/// Makes a device a member of a group, so it receives packets addressed to the group
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct GroupAddEvent {
+    pub device_address: u16,
+    pub transmitter_address: u16,
+    pub group_address: u16,
+}
+
+impl ConvertPacket<GroupAddEvent> for GroupAddEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 6 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != GROUP_ADD_EVENT_CODE {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let device_address = packet.device_address;
+        let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let group_address = u16::from_be_bytes(packet.data[4..=5].try_into().unwrap());
+
+        Ok(Self {
+            device_address,
+            transmitter_address,
+            group_address,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(GROUP_ADD_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.transmitter_address).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.group_address).iter() {
+            data.push(*byte);
+        }
+
+        Packet {
+            is_error: false,
+            device_address: self.device_address,
+            data,
+        }
+    }
+}
+
+/// Removes a device from a group
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct GroupRemoveEvent {
+    pub device_address: u16,
+    pub transmitter_address: u16,
+    pub group_address: u16,
+}
+
+impl ConvertPacket<GroupRemoveEvent> for GroupRemoveEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 6 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != GROUP_REMOVE_EVENT_CODE {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let device_address = packet.device_address;
+        let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let group_address = u16::from_be_bytes(packet.data[4..=5].try_into().unwrap());
+
+        Ok(Self {
+            device_address,
+            transmitter_address,
+            group_address,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(GROUP_REMOVE_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.transmitter_address).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.group_address).iter() {
+            data.push(*byte);
+        }
+
+        Packet {
+            is_error: false,
+            device_address: self.device_address,
+            data,
+        }
+    }
+}
+
+/// Asks a device for the groups it is a member of, answered with a `GroupReportEvent`
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct GroupGetEvent {
+    pub device_address: u16,
+    pub transmitter_address: u16,
+}
+
+impl ConvertPacket<GroupGetEvent> for GroupGetEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 4 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != GROUP_GET_EVENT_CODE {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let device_address = packet.device_address;
+        let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+
+        Ok(Self {
+            device_address,
+            transmitter_address,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(GROUP_GET_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.transmitter_address).iter() {
+            data.push(*byte);
+        }
+
+        Packet {
+            is_error: false,
+            device_address: self.device_address,
+            data,
+        }
+    }
+}
+
+/// Reply to a `GroupGetEvent` listing the groups a device is a member of
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct GroupReportEvent {
+    pub receiver_address: u16,
+    pub device_address: u16,
+    pub group_addresses: Vec<u16>,
+}
+
+impl ConvertPacket<GroupReportEvent> for GroupReportEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() < 4 || packet.data.len() % 2 == 1 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != GROUP_REPORT_EVENT_CODE {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let receiver_address = packet.device_address;
+        let device_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let group_addresses = packet.data[4..]
+            .chunks(2)
+            .map(|chunk| u16::from_be_bytes(chunk.try_into().unwrap()))
+            .collect();
+
+        Ok(Self {
+            receiver_address,
+            device_address,
+            group_addresses,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(GROUP_REPORT_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.device_address).iter() {
+            data.push(*byte);
+        }
+
+        for group_address in self.group_addresses.iter() {
+            for byte in u16::to_be_bytes(*group_address).iter() {
+                data.push(*byte);
+            }
+        }
+
+        Packet {
+            is_error: false,
+            device_address: self.receiver_address,
+            data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EVENT_PACKET: Packet = Packet {
+        is_error: false,
+        device_address: 0xabab,
+        data: vec![],
+    };
+
+    #[test]
+    fn add_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((GROUP_ADD_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (GROUP_ADD_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                       // transmitter address
+            0x23,                                       // transmitter address
+            0xff,                                       // group address
+            0x12,                                       // group address
+        ];
+
+        let event = GroupAddEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.device_address, 0xabab);
+        assert_eq!(event.transmitter_address, 0x0123);
+        assert_eq!(event.group_address, 0xff12);
+    }
+
+    #[test]
+    fn add_to_packet_test() {
+        let event = GroupAddEvent {
+            device_address: 0xabab,
+            transmitter_address: 0x0123,
+            group_address: 0xff12,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((GROUP_ADD_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (GROUP_ADD_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                       // transmitter address
+            0x23,                                       // transmitter address
+            0xff,                                       // group address
+            0x12,                                       // group address
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn remove_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((GROUP_REMOVE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (GROUP_REMOVE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                          // transmitter address
+            0x23,                                          // transmitter address
+            0xff,                                          // group address
+            0x12,                                          // group address
+        ];
+
+        let event = GroupRemoveEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.device_address, 0xabab);
+        assert_eq!(event.transmitter_address, 0x0123);
+        assert_eq!(event.group_address, 0xff12);
+    }
+
+    #[test]
+    fn remove_to_packet_test() {
+        let event = GroupRemoveEvent {
+            device_address: 0xabab,
+            transmitter_address: 0x0123,
+            group_address: 0xff12,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((GROUP_REMOVE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (GROUP_REMOVE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                          // transmitter address
+            0x23,                                          // transmitter address
+            0xff,                                          // group address
+            0x12,                                          // group address
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn get_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((GROUP_GET_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (GROUP_GET_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                       // transmitter address
+            0x23,                                       // transmitter address
+        ];
+
+        let event = GroupGetEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.device_address, 0xabab);
+        assert_eq!(event.transmitter_address, 0x0123);
+    }
+
+    #[test]
+    fn get_to_packet_test() {
+        let event = GroupGetEvent {
+            device_address: 0xabab,
+            transmitter_address: 0x0123,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((GROUP_GET_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (GROUP_GET_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                       // transmitter address
+            0x23,                                       // transmitter address
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn report_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((GROUP_REPORT_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (GROUP_REPORT_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                          // device address
+            0x23,                                          // device address
+            0xff,                                          // group address
+            0x12,                                          // group address
+            0xff,                                          // group address
+            0x34,                                          // group address
+        ];
+
+        let event = GroupReportEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.receiver_address, 0xabab);
+        assert_eq!(event.device_address, 0x0123);
+        assert_eq!(event.group_addresses, vec![0xff12, 0xff34]);
+    }
+
+    #[test]
+    fn report_try_from_packet_odd_length_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((GROUP_REPORT_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (GROUP_REPORT_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                          // device address
+            0x23,                                          // device address
+            0xff,                                          // group address
+        ];
+
+        assert_eq!(
+            GroupReportEvent::try_from_packet(&packet),
+            Err(ConvertPacketError::WrongSize)
+        );
+    }
+
+    #[test]
+    fn report_to_packet_test() {
+        let event = GroupReportEvent {
+            receiver_address: 0xabab,
+            device_address: 0x0123,
+            group_addresses: vec![0xff12, 0xff34],
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((GROUP_REPORT_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (GROUP_REPORT_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                          // device address
+            0x23,                                          // device address
+            0xff,                                          // group address
+            0x12,                                          // group address
+            0xff,                                          // group address
+            0x34,                                          // group address
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+}
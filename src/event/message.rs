@@ -1,13 +1,18 @@
 use alloc::vec;
+use alloc::vec::Vec;
 use core::convert::TryInto;
-use core::mem::{size_of, transmute_copy};
 
 use crate::convert_packet::{ConvertPacket, ConvertPacketError};
 use crate::event::event_code::*;
 use crate::event::EventError;
 use crate::packet::Packet;
 
-#[repr(C)]
+/// Size of a serialized `MessageValue`
+///
+/// Values keep the layout of a `repr(C)` enum on little-endian targets: a 4 byte little-endian tag
+/// followed by the little-endian value, padded with zeroes.
+const MESSAGE_VALUE_SIZE: usize = 8;
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub enum MessageValue {
     U8(u8),
@@ -16,6 +21,51 @@ pub enum MessageValue {
     Bool(bool),
 }
 
+impl MessageValue {
+    fn serialize(self) -> Vec<u8> {
+        let mut data = vec![0x00; MESSAGE_VALUE_SIZE];
+
+        match self {
+            Self::U8(value) => {
+                data[0] = 0x00;
+                data[4] = value;
+            }
+            Self::U16(value) => {
+                data[0] = 0x01;
+                data[4..=5].copy_from_slice(&value.to_le_bytes());
+            }
+            Self::U32(value) => {
+                data[0] = 0x02;
+                data[4..=7].copy_from_slice(&value.to_le_bytes());
+            }
+            Self::Bool(value) => {
+                data[0] = 0x03;
+                data[4] = value as u8;
+            }
+        }
+
+        data
+    }
+
+    fn deserialize(data: &[u8]) -> Result<Self, ConvertPacketError> {
+        if data.len() != MESSAGE_VALUE_SIZE {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        match u32::from_le_bytes(data[0..=3].try_into().unwrap()) {
+            0x00 => Ok(Self::U8(data[4])),
+            0x01 => Ok(Self::U16(u16::from_le_bytes(
+                data[4..=5].try_into().unwrap(),
+            ))),
+            0x02 => Ok(Self::U32(u32::from_le_bytes(
+                data[4..=7].try_into().unwrap(),
+            ))),
+            0x03 => Ok(Self::Bool(data[4] != 0x00)),
+            _ => Err(ConvertPacketError::UnknownEnumVariant),
+        }
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub struct MessageEvent {
     pub receiver_address: u16,
@@ -26,7 +76,7 @@ pub struct MessageEvent {
 
 impl ConvertPacket<MessageEvent> for MessageEvent {
     fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
-        if packet.data.len() != 6 + size_of::<MessageValue>() {
+        if packet.data.len() != 6 + MESSAGE_VALUE_SIZE {
             return Err(ConvertPacketError::WrongSize);
         }
 
@@ -41,13 +91,7 @@ impl ConvertPacket<MessageEvent> for MessageEvent {
         let receiver_address = packet.device_address;
         let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
         let code = u16::from_be_bytes(packet.data[4..=5].try_into().unwrap());
-        let value = unsafe {
-            transmute_copy::<[u8; size_of::<MessageValue>()], MessageValue>(
-                &packet.data[6..6 + size_of::<MessageValue>()]
-                    .try_into()
-                    .unwrap(),
-            )
-        };
+        let value = MessageValue::deserialize(&packet.data[6..])?;
 
         Ok(Self {
             receiver_address,
@@ -72,13 +116,7 @@ impl ConvertPacket<MessageEvent> for MessageEvent {
             data.push(*byte);
         }
 
-        unsafe {
-            for byte in
-                transmute_copy::<MessageValue, [u8; size_of::<MessageValue>()]>(&self.value).iter()
-            {
-                data.push(*byte);
-            }
-        }
+        data.append(&mut self.value.serialize());
 
         Packet {
             is_error: false,
@@ -154,4 +192,61 @@ mod tests {
 
         assert_eq!(event.to_packet(), packet);
     }
+
+    #[test]
+    fn try_from_packet_unknown_value_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((MESSAGE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (MESSAGE_EVENT_CODE & 0xff) as u8,        // event code
+            0x00,                                     // transmitter address
+            0x00,                                     // transmitter address
+            0x01,                                     // code
+            0x23,                                     // code
+            0x04,                                     // value
+            0x00,                                     // value
+            0x00,                                     // value
+            0x00,                                     // value
+            0xff,                                     // value
+            0xff,                                     // value
+            0xff,                                     // value
+            0xff,                                     // value
+        ];
+
+        assert_eq!(
+            MessageEvent::try_from_packet(&packet),
+            Err(ConvertPacketError::UnknownEnumVariant)
+        );
+    }
+
+    #[test]
+    fn u16_value_test() {
+        let event = MessageEvent {
+            receiver_address: 0xabab,
+            transmitter_address: 0x0000,
+            code: 0x0123,
+            value: MessageValue::U16(0x4567),
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((MESSAGE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (MESSAGE_EVENT_CODE & 0xff) as u8,        // event code
+            0x00,                                     // transmitter address
+            0x00,                                     // transmitter address
+            0x01,                                     // code
+            0x23,                                     // code
+            0x01,                                     // value
+            0x00,                                     // value
+            0x00,                                     // value
+            0x00,                                     // value
+            0x67,                                     // value
+            0x45,                                     // value
+            0x00,                                     // value
+            0x00,                                     // value
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+        assert_eq!(MessageEvent::try_from_packet(&packet), Ok(event));
+    }
 }
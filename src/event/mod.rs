@@ -1,14 +1,24 @@
+pub mod analog;
 pub mod bcm;
 pub mod bootloader;
 pub mod button;
 pub mod configurator;
+pub mod cover;
+pub mod diagnostics;
+pub mod encoder;
+pub mod error;
 pub mod event_code;
 pub mod gateway;
 pub mod general;
+pub mod group;
 pub mod internal;
 pub mod message;
+pub mod parameter;
 pub mod programmer;
 pub mod relay;
+pub mod schedule;
+pub mod sensor;
+pub mod time;
 
 #[derive(Debug, PartialEq)]
 pub enum EventError {
@@ -0,0 +1,722 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+use crate::convert_packet::{ConvertPacket, ConvertPacketError};
+use crate::event::event_code::*;
+use crate::event::EventError;
+use crate::packet::Packet;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum ParameterType {
+    Bool,
+    U8,
+    U16,
+    U32,
+    I16,
+    I32,
+}
+
+/// Typed value of a parameter, serialized as a type tag followed by the big-endian value
+///
+/// Values of the same type compare by value, so they can be checked against a parameter's range.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum ParameterValue {
+    Bool(bool),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    I16(i16),
+    I32(i32),
+}
+
+impl ParameterValue {
+    pub fn parameter_type(&self) -> ParameterType {
+        match self {
+            Self::Bool(_) => ParameterType::Bool,
+            Self::U8(_) => ParameterType::U8,
+            Self::U16(_) => ParameterType::U16,
+            Self::U32(_) => ParameterType::U32,
+            Self::I16(_) => ParameterType::I16,
+            Self::I32(_) => ParameterType::I32,
+        }
+    }
+
+    pub(crate) fn serialize(&self) -> Vec<u8> {
+        match *self {
+            Self::Bool(value) => vec![0x00, value as u8],
+            Self::U8(value) => vec![0x01, value],
+            Self::U16(value) => {
+                let mut data = vec![0x02];
+                data.extend_from_slice(&value.to_be_bytes());
+                data
+            }
+            Self::U32(value) => {
+                let mut data = vec![0x03];
+                data.extend_from_slice(&value.to_be_bytes());
+                data
+            }
+            Self::I16(value) => {
+                let mut data = vec![0x04];
+                data.extend_from_slice(&value.to_be_bytes());
+                data
+            }
+            Self::I32(value) => {
+                let mut data = vec![0x05];
+                data.extend_from_slice(&value.to_be_bytes());
+                data
+            }
+        }
+    }
+
+    /// Returns the size of the serialized value at the start of `data`
+    pub(crate) fn serialized_len(data: &[u8]) -> Result<usize, ConvertPacketError> {
+        match data.first() {
+            None => Err(ConvertPacketError::WrongSize),
+            Some(0x00) | Some(0x01) => Ok(2),
+            Some(0x02) | Some(0x04) => Ok(3),
+            Some(0x03) | Some(0x05) => Ok(5),
+            Some(_) => Err(ConvertPacketError::UnknownEnumVariant),
+        }
+    }
+
+    pub(crate) fn deserialize(data: &[u8]) -> Result<Self, ConvertPacketError> {
+        if data.len() != Self::serialized_len(data)? {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        match data[0] {
+            0x00 => Ok(Self::Bool(data[1] != 0x00)),
+            0x01 => Ok(Self::U8(data[1])),
+            0x02 => Ok(Self::U16(u16::from_be_bytes(
+                data[1..=2].try_into().unwrap(),
+            ))),
+            0x03 => Ok(Self::U32(u32::from_be_bytes(
+                data[1..=4].try_into().unwrap(),
+            ))),
+            0x04 => Ok(Self::I16(i16::from_be_bytes(
+                data[1..=2].try_into().unwrap(),
+            ))),
+            _ => Ok(Self::I32(i32::from_be_bytes(
+                data[1..=4].try_into().unwrap(),
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum ParameterAccess {
+    ReadOnly,
+    ReadWrite,
+}
+
+impl ParameterAccess {
+    fn serialize(self) -> u8 {
+        match self {
+            Self::ReadOnly => 0x00,
+            Self::ReadWrite => 0x01,
+        }
+    }
+
+    fn deserialize(data: u8) -> Result<Self, ConvertPacketError> {
+        match data {
+            0x00 => Ok(Self::ReadOnly),
+            0x01 => Ok(Self::ReadWrite),
+            _ => Err(ConvertPacketError::UnknownEnumVariant),
+        }
+    }
+}
+
+/// Reads a parameter of a device, answered with a `ParameterReportEvent`
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ParameterReadEvent {
+    pub device_address: u16,
+    pub transmitter_address: u16,
+    pub index: u16,
+}
+
+impl ConvertPacket<ParameterReadEvent> for ParameterReadEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 6 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != PARAMETER_READ_EVENT_CODE {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let device_address = packet.device_address;
+        let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = u16::from_be_bytes(packet.data[4..=5].try_into().unwrap());
+
+        Ok(Self {
+            device_address,
+            transmitter_address,
+            index,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(PARAMETER_READ_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.transmitter_address).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.index).iter() {
+            data.push(*byte);
+        }
+
+        Packet {
+            is_error: false,
+            device_address: self.device_address,
+            data,
+        }
+    }
+}
+
+/// Writes a parameter of a device, answered with a `ParameterReportEvent` carrying the new value
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ParameterWriteEvent {
+    pub device_address: u16,
+    pub transmitter_address: u16,
+    pub index: u16,
+    pub value: ParameterValue,
+}
+
+impl ConvertPacket<ParameterWriteEvent> for ParameterWriteEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() < 7 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != PARAMETER_WRITE_EVENT_CODE
+        {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let device_address = packet.device_address;
+        let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = u16::from_be_bytes(packet.data[4..=5].try_into().unwrap());
+        let value = ParameterValue::deserialize(&packet.data[6..])?;
+
+        Ok(Self {
+            device_address,
+            transmitter_address,
+            index,
+            value,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(PARAMETER_WRITE_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.transmitter_address).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.index).iter() {
+            data.push(*byte);
+        }
+
+        data.append(&mut self.value.serialize());
+
+        Packet {
+            is_error: false,
+            device_address: self.device_address,
+            data,
+        }
+    }
+}
+
+/// Value of a parameter, sent in reply to a `ParameterReadEvent` or a `ParameterWriteEvent`
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ParameterReportEvent {
+    pub receiver_address: u16,
+    pub device_address: u16,
+    pub index: u16,
+    pub value: ParameterValue,
+}
+
+impl ConvertPacket<ParameterReportEvent> for ParameterReportEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() < 7 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != PARAMETER_REPORT_EVENT_CODE
+        {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let receiver_address = packet.device_address;
+        let device_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = u16::from_be_bytes(packet.data[4..=5].try_into().unwrap());
+        let value = ParameterValue::deserialize(&packet.data[6..])?;
+
+        Ok(Self {
+            receiver_address,
+            device_address,
+            index,
+            value,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(PARAMETER_REPORT_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.device_address).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.index).iter() {
+            data.push(*byte);
+        }
+
+        data.append(&mut self.value.serialize());
+
+        Packet {
+            is_error: false,
+            device_address: self.receiver_address,
+            data,
+        }
+    }
+}
+
+/// Asks a device for the type, access and range of a parameter, answered with a
+/// `ParameterDescriptionEvent`
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ParameterDescribeEvent {
+    pub device_address: u16,
+    pub transmitter_address: u16,
+    pub index: u16,
+}
+
+impl ConvertPacket<ParameterDescribeEvent> for ParameterDescribeEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 6 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap())
+            != PARAMETER_DESCRIBE_EVENT_CODE
+        {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let device_address = packet.device_address;
+        let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = u16::from_be_bytes(packet.data[4..=5].try_into().unwrap());
+
+        Ok(Self {
+            device_address,
+            transmitter_address,
+            index,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(PARAMETER_DESCRIBE_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.transmitter_address).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.index).iter() {
+            data.push(*byte);
+        }
+
+        Packet {
+            is_error: false,
+            device_address: self.device_address,
+            data,
+        }
+    }
+}
+
+/// Reply to a `ParameterDescribeEvent`
+///
+/// The type of the parameter is the type of `min` and `max`.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ParameterDescriptionEvent {
+    pub receiver_address: u16,
+    pub device_address: u16,
+    pub index: u16,
+    pub access: ParameterAccess,
+    pub min: ParameterValue,
+    pub max: ParameterValue,
+}
+
+impl ConvertPacket<ParameterDescriptionEvent> for ParameterDescriptionEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() < 11 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap())
+            != PARAMETER_DESCRIPTION_EVENT_CODE
+        {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let receiver_address = packet.device_address;
+        let device_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = u16::from_be_bytes(packet.data[4..=5].try_into().unwrap());
+        let access = ParameterAccess::deserialize(packet.data[6])?;
+
+        let min_len = ParameterValue::serialized_len(&packet.data[7..])?;
+
+        if packet.data.len() < 7 + min_len {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        let min = ParameterValue::deserialize(&packet.data[7..7 + min_len])?;
+        let max = ParameterValue::deserialize(&packet.data[7 + min_len..])?;
+
+        Ok(Self {
+            receiver_address,
+            device_address,
+            index,
+            access,
+            min,
+            max,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(PARAMETER_DESCRIPTION_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.device_address).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.index).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.access.serialize());
+        data.append(&mut self.min.serialize());
+        data.append(&mut self.max.serialize());
+
+        Packet {
+            is_error: false,
+            device_address: self.receiver_address,
+            data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EVENT_PACKET: Packet = Packet {
+        is_error: false,
+        device_address: 0xabab,
+        data: vec![],
+    };
+
+    #[test]
+    fn read_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((PARAMETER_READ_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (PARAMETER_READ_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                            // transmitter address
+            0x23,                                            // transmitter address
+            0x12,                                            // index
+            0x34,                                            // index
+        ];
+
+        let event = ParameterReadEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.device_address, 0xabab);
+        assert_eq!(event.transmitter_address, 0x0123);
+        assert_eq!(event.index, 0x1234);
+    }
+
+    #[test]
+    fn read_to_packet_test() {
+        let event = ParameterReadEvent {
+            device_address: 0xabab,
+            transmitter_address: 0x0123,
+            index: 0x1234,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((PARAMETER_READ_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (PARAMETER_READ_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                            // transmitter address
+            0x23,                                            // transmitter address
+            0x12,                                            // index
+            0x34,                                            // index
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn write_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((PARAMETER_WRITE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (PARAMETER_WRITE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                             // transmitter address
+            0x23,                                             // transmitter address
+            0x12,                                             // index
+            0x34,                                             // index
+            0x05,                                             // value
+            0xff,                                             // value
+            0xff,                                             // value
+            0xfc,                                             // value
+            0x18,                                             // value
+        ];
+
+        let event = ParameterWriteEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.device_address, 0xabab);
+        assert_eq!(event.transmitter_address, 0x0123);
+        assert_eq!(event.index, 0x1234);
+        assert_eq!(event.value, ParameterValue::I32(-1000));
+    }
+
+    #[test]
+    fn write_to_packet_test() {
+        let event = ParameterWriteEvent {
+            device_address: 0xabab,
+            transmitter_address: 0x0123,
+            index: 0x1234,
+            value: ParameterValue::I32(-1000),
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((PARAMETER_WRITE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (PARAMETER_WRITE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                             // transmitter address
+            0x23,                                             // transmitter address
+            0x12,                                             // index
+            0x34,                                             // index
+            0x05,                                             // value
+            0xff,                                             // value
+            0xff,                                             // value
+            0xfc,                                             // value
+            0x18,                                             // value
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn report_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((PARAMETER_REPORT_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (PARAMETER_REPORT_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                              // device address
+            0x23,                                              // device address
+            0x12,                                              // index
+            0x34,                                              // index
+            0x05,                                              // value
+            0xff,                                              // value
+            0xff,                                              // value
+            0xfc,                                              // value
+            0x18,                                              // value
+        ];
+
+        let event = ParameterReportEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.receiver_address, 0xabab);
+        assert_eq!(event.device_address, 0x0123);
+        assert_eq!(event.index, 0x1234);
+        assert_eq!(event.value, ParameterValue::I32(-1000));
+    }
+
+    #[test]
+    fn report_to_packet_test() {
+        let event = ParameterReportEvent {
+            receiver_address: 0xabab,
+            device_address: 0x0123,
+            index: 0x1234,
+            value: ParameterValue::I32(-1000),
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((PARAMETER_REPORT_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (PARAMETER_REPORT_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                              // device address
+            0x23,                                              // device address
+            0x12,                                              // index
+            0x34,                                              // index
+            0x05,                                              // value
+            0xff,                                              // value
+            0xff,                                              // value
+            0xfc,                                              // value
+            0x18,                                              // value
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn write_try_from_packet_wrong_size_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((PARAMETER_WRITE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (PARAMETER_WRITE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                             // transmitter address
+            0x23,                                             // transmitter address
+            0x12,                                             // index
+            0x34,                                             // index
+            0x02,                                             // value
+            0x01,                                             // value
+        ];
+
+        assert_eq!(
+            ParameterWriteEvent::try_from_packet(&packet),
+            Err(ConvertPacketError::WrongSize)
+        );
+    }
+
+    #[test]
+    fn describe_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((PARAMETER_DESCRIBE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (PARAMETER_DESCRIBE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                // transmitter address
+            0x23,                                                // transmitter address
+            0x12,                                                // index
+            0x34,                                                // index
+        ];
+
+        let event = ParameterDescribeEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.device_address, 0xabab);
+        assert_eq!(event.transmitter_address, 0x0123);
+        assert_eq!(event.index, 0x1234);
+    }
+
+    #[test]
+    fn describe_to_packet_test() {
+        let event = ParameterDescribeEvent {
+            device_address: 0xabab,
+            transmitter_address: 0x0123,
+            index: 0x1234,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((PARAMETER_DESCRIBE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (PARAMETER_DESCRIBE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                // transmitter address
+            0x23,                                                // transmitter address
+            0x12,                                                // index
+            0x34,                                                // index
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn description_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((PARAMETER_DESCRIPTION_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (PARAMETER_DESCRIPTION_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                   // device address
+            0x23,                                                   // device address
+            0x12,                                                   // index
+            0x34,                                                   // index
+            0x01,                                                   // access
+            0x02,                                                   // min
+            0x00,                                                   // min
+            0x0a,                                                   // min
+            0x02,                                                   // max
+            0x03,                                                   // max
+            0xe8,                                                   // max
+        ];
+
+        let event = ParameterDescriptionEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.receiver_address, 0xabab);
+        assert_eq!(event.device_address, 0x0123);
+        assert_eq!(event.index, 0x1234);
+        assert_eq!(event.access, ParameterAccess::ReadWrite);
+        assert_eq!(event.min, ParameterValue::U16(10));
+        assert_eq!(event.max, ParameterValue::U16(1000));
+    }
+
+    #[test]
+    fn description_to_packet_test() {
+        let event = ParameterDescriptionEvent {
+            receiver_address: 0xabab,
+            device_address: 0x0123,
+            index: 0x1234,
+            access: ParameterAccess::ReadWrite,
+            min: ParameterValue::U16(10),
+            max: ParameterValue::U16(1000),
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((PARAMETER_DESCRIPTION_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (PARAMETER_DESCRIPTION_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                   // device address
+            0x23,                                                   // device address
+            0x12,                                                   // index
+            0x34,                                                   // index
+            0x01,                                                   // access
+            0x02,                                                   // min
+            0x00,                                                   // min
+            0x0a,                                                   // min
+            0x02,                                                   // max
+            0x03,                                                   // max
+            0xe8,                                                   // max
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+}
@@ -11,15 +11,110 @@ use crate::packet::Packet;
 pub enum RelayValue {
     Single(bool),
     DoubleExclusive(RelayDoubleExclusiveValue),
+    /// Inverts the current state of a single relay
+    Toggle,
+    /// Inverts the current state of a single relay for the given amount of milliseconds
+    Pulse(u32),
+    /// Turns a single relay on and back off after the given amount of milliseconds
+    ///
+    /// Receiving it again while the relay is still on restarts the timer (like a staircase timer).
+    TimedOn(u32),
+    /// Switches a double exclusive relay to the given value and to `NoChannelOn` after the given
+    /// amount of milliseconds
+    DoubleExclusiveForDuration(RelayDoubleExclusiveValue, u32),
 }
 
 impl RelayValue {
     fn serialize(self) -> Vec<u8> {
         match self {
             Self::Single(value) => vec![if value { 0x00 } else { 0x01 }],
-            Self::DoubleExclusive(RelayDoubleExclusiveValue::FirstChannelOn) => vec![0x02],
-            Self::DoubleExclusive(RelayDoubleExclusiveValue::SecondChannelOn) => vec![0x03],
-            Self::DoubleExclusive(RelayDoubleExclusiveValue::NoChannelOn) => vec![0x04],
+            Self::DoubleExclusive(value) => vec![value.serialize()],
+            Self::Toggle => vec![0x05],
+            Self::Pulse(duration) => {
+                let mut data = vec![0x06];
+                data.extend_from_slice(&u32::to_be_bytes(duration));
+                data
+            }
+            Self::TimedOn(duration) => {
+                let mut data = vec![0x07];
+                data.extend_from_slice(&u32::to_be_bytes(duration));
+                data
+            }
+            Self::DoubleExclusiveForDuration(value, duration) => {
+                let mut data = vec![0x08, value.serialize()];
+                data.extend_from_slice(&u32::to_be_bytes(duration));
+                data
+            }
+        }
+    }
+
+    fn deserialize(data: &[u8]) -> Result<Self, ConvertPacketError> {
+        if data.is_empty() {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        match data[0] {
+            0x00..=0x05 => {
+                if data.len() != 1 {
+                    return Err(ConvertPacketError::WrongSize);
+                }
+
+                match data[0] {
+                    0x00 => Ok(Self::Single(true)),
+                    0x01 => Ok(Self::Single(false)),
+                    0x05 => Ok(Self::Toggle),
+                    value => Ok(Self::DoubleExclusive(
+                        RelayDoubleExclusiveValue::deserialize(value)?,
+                    )),
+                }
+            }
+            0x06 => {
+                if data.len() != 5 {
+                    return Err(ConvertPacketError::WrongSize);
+                }
+
+                Ok(Self::Pulse(u32::from_be_bytes(
+                    data[1..=4].try_into().unwrap(),
+                )))
+            }
+            0x07 => {
+                if data.len() != 5 {
+                    return Err(ConvertPacketError::WrongSize);
+                }
+
+                Ok(Self::TimedOn(u32::from_be_bytes(
+                    data[1..=4].try_into().unwrap(),
+                )))
+            }
+            0x08 => {
+                if data.len() != 6 {
+                    return Err(ConvertPacketError::WrongSize);
+                }
+
+                Ok(Self::DoubleExclusiveForDuration(
+                    RelayDoubleExclusiveValue::deserialize(data[1])?,
+                    u32::from_be_bytes(data[2..=5].try_into().unwrap()),
+                ))
+            }
+            _ => Err(ConvertPacketError::UnknownEnumVariant),
+        }
+    }
+}
+
+/// State of a relay output, as reported by `RelayReportValueEvent` and `RelayValueChangedEvent`
+///
+/// Unlike `RelayValue`, it can not hold any of the command variants.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum RelayState {
+    Single(bool),
+    DoubleExclusive(RelayDoubleExclusiveValue),
+}
+
+impl RelayState {
+    fn serialize(self) -> Vec<u8> {
+        match self {
+            Self::Single(value) => vec![if value { 0x00 } else { 0x01 }],
+            Self::DoubleExclusive(value) => vec![value.serialize()],
         }
     }
 
@@ -28,42 +123,480 @@ impl RelayValue {
             return Err(ConvertPacketError::WrongSize);
         }
 
-        match data[0] {
-            0x00 => Ok(Self::Single(true)),
-            0x01 => Ok(Self::Single(false)),
-            0x02 => Ok(Self::DoubleExclusive(
-                RelayDoubleExclusiveValue::FirstChannelOn,
-            )),
-            0x03 => Ok(Self::DoubleExclusive(
-                RelayDoubleExclusiveValue::SecondChannelOn,
-            )),
-            0x04 => Ok(Self::DoubleExclusive(
-                RelayDoubleExclusiveValue::NoChannelOn,
-            )),
-            _ => Err(ConvertPacketError::UnknownEnumVariant),
+        match data[0] {
+            0x00 => Ok(Self::Single(true)),
+            0x01 => Ok(Self::Single(false)),
+            value => Ok(Self::DoubleExclusive(
+                RelayDoubleExclusiveValue::deserialize(value)?,
+            )),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum RelayDoubleExclusiveValue {
+    FirstChannelOn,
+    SecondChannelOn,
+    NoChannelOn,
+}
+
+impl RelayDoubleExclusiveValue {
+    fn serialize(self) -> u8 {
+        match self {
+            Self::FirstChannelOn => 0x02,
+            Self::SecondChannelOn => 0x03,
+            Self::NoChannelOn => 0x04,
+        }
+    }
+
+    fn deserialize(data: u8) -> Result<Self, ConvertPacketError> {
+        match data {
+            0x02 => Ok(Self::FirstChannelOn),
+            0x03 => Ok(Self::SecondChannelOn),
+            0x04 => Ok(Self::NoChannelOn),
+            _ => Err(ConvertPacketError::UnknownEnumVariant),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct RelaySetValueEvent {
+    pub relay_address: u16,
+    pub transmitter_address: u16,
+    pub index: u8,
+    pub value: RelayValue,
+}
+
+impl ConvertPacket<RelaySetValueEvent> for RelaySetValueEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() < 6 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != RELAY_SET_VALUE_EVENT_CODE
+        {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let relay_address = packet.device_address;
+        let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = packet.data[4];
+        let value = RelayValue::deserialize(&packet.data[5..])?;
+
+        Ok(Self {
+            relay_address,
+            transmitter_address,
+            index,
+            value,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(RELAY_SET_VALUE_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.transmitter_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.index);
+        data.append(&mut self.value.serialize());
+
+        Packet {
+            is_error: false,
+            device_address: self.relay_address,
+            data,
+        }
+    }
+}
+
+/// Asks a relay for the current value of one of its outputs, answered with a
+/// `RelayReportValueEvent`
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct RelayGetValueEvent {
+    pub relay_address: u16,
+    pub transmitter_address: u16,
+    pub index: u8,
+}
+
+impl ConvertPacket<RelayGetValueEvent> for RelayGetValueEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 5 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != RELAY_GET_VALUE_EVENT_CODE
+        {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let relay_address = packet.device_address;
+        let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = packet.data[4];
+
+        Ok(Self {
+            relay_address,
+            transmitter_address,
+            index,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(RELAY_GET_VALUE_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.transmitter_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.index);
+
+        Packet {
+            is_error: false,
+            device_address: self.relay_address,
+            data,
+        }
+    }
+}
+
+/// Reply to a `RelayGetValueEvent`
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct RelayReportValueEvent {
+    pub receiver_address: u16,
+    pub relay_address: u16,
+    pub index: u8,
+    pub value: RelayState,
+}
+
+impl ConvertPacket<RelayReportValueEvent> for RelayReportValueEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() < 6 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap())
+            != RELAY_REPORT_VALUE_EVENT_CODE
+        {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let receiver_address = packet.device_address;
+        let relay_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = packet.data[4];
+        let value = RelayState::deserialize(&packet.data[5..])?;
+
+        Ok(Self {
+            receiver_address,
+            relay_address,
+            index,
+            value,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(RELAY_REPORT_VALUE_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.relay_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.index);
+        data.append(&mut self.value.serialize());
+
+        Packet {
+            is_error: false,
+            device_address: self.receiver_address,
+            data,
+        }
+    }
+}
+
+/// Sent by a relay whenever one of its outputs changes, no matter what caused the change
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct RelayValueChangedEvent {
+    pub receiver_address: u16,
+    pub relay_address: u16,
+    pub index: u8,
+    pub value: RelayState,
+}
+
+impl ConvertPacket<RelayValueChangedEvent> for RelayValueChangedEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() < 6 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap())
+            != RELAY_VALUE_CHANGED_EVENT_CODE
+        {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let receiver_address = packet.device_address;
+        let relay_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = packet.data[4];
+        let value = RelayState::deserialize(&packet.data[5..])?;
+
+        Ok(Self {
+            receiver_address,
+            relay_address,
+            index,
+            value,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(RELAY_VALUE_CHANGED_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.relay_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.index);
+        data.append(&mut self.value.serialize());
+
+        Packet {
+            is_error: false,
+            device_address: self.receiver_address,
+            data,
+        }
+    }
+}
+
+/// Reports the instantaneous power drawn through one of the outputs of a relay, in milliwatts
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct RelayReportPowerEvent {
+    pub receiver_address: u16,
+    pub relay_address: u16,
+    pub index: u8,
+    pub power: u32,
+}
+
+impl ConvertPacket<RelayReportPowerEvent> for RelayReportPowerEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 9 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap())
+            != RELAY_REPORT_POWER_EVENT_CODE
+        {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let receiver_address = packet.device_address;
+        let relay_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = packet.data[4];
+        let power = u32::from_be_bytes(packet.data[5..=8].try_into().unwrap());
+
+        Ok(Self {
+            receiver_address,
+            relay_address,
+            index,
+            power,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(RELAY_REPORT_POWER_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.relay_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.index);
+
+        for byte in u32::to_be_bytes(self.power).iter() {
+            data.push(*byte);
+        }
+
+        Packet {
+            is_error: false,
+            device_address: self.receiver_address,
+            data,
+        }
+    }
+}
+
+/// Reports the energy consumed through one of the outputs of a relay since its counter was last
+/// reset, in watt hours
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct RelayReportEnergyEvent {
+    pub receiver_address: u16,
+    pub relay_address: u16,
+    pub index: u8,
+    pub energy: u32,
+}
+
+impl ConvertPacket<RelayReportEnergyEvent> for RelayReportEnergyEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 9 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap())
+            != RELAY_REPORT_ENERGY_EVENT_CODE
+        {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let receiver_address = packet.device_address;
+        let relay_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = packet.data[4];
+        let energy = u32::from_be_bytes(packet.data[5..=8].try_into().unwrap());
+
+        Ok(Self {
+            receiver_address,
+            relay_address,
+            index,
+            energy,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(RELAY_REPORT_ENERGY_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.relay_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.index);
+
+        for byte in u32::to_be_bytes(self.energy).iter() {
+            data.push(*byte);
+        }
+
+        Packet {
+            is_error: false,
+            device_address: self.receiver_address,
+            data,
+        }
+    }
+}
+
+/// Sent by a relay when one of its outputs was switched off for drawing too much current
+///
+/// `current` is the current measured when the output tripped, in milliamperes.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct RelayOverCurrentEvent {
+    pub receiver_address: u16,
+    pub relay_address: u16,
+    pub index: u8,
+    pub current: u32,
+}
+
+impl ConvertPacket<RelayOverCurrentEvent> for RelayOverCurrentEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 9 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap())
+            != RELAY_OVER_CURRENT_EVENT_CODE
+        {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let receiver_address = packet.device_address;
+        let relay_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = packet.data[4];
+        let current = u32::from_be_bytes(packet.data[5..=8].try_into().unwrap());
+
+        Ok(Self {
+            receiver_address,
+            relay_address,
+            index,
+            current,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(RELAY_OVER_CURRENT_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.relay_address).iter() {
+            data.push(*byte);
         }
-    }
-}
 
-#[repr(C)]
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
-pub enum RelayDoubleExclusiveValue {
-    FirstChannelOn,
-    SecondChannelOn,
-    NoChannelOn,
+        data.push(self.index);
+
+        for byte in u32::to_be_bytes(self.current).iter() {
+            data.push(*byte);
+        }
+
+        Packet {
+            is_error: false,
+            device_address: self.receiver_address,
+            data,
+        }
+    }
 }
 
+/// Resets the energy counter of one of the outputs of a relay
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
-pub struct RelaySetValueEvent {
+pub struct RelayResetEnergyEvent {
     pub relay_address: u16,
     pub transmitter_address: u16,
     pub index: u8,
-    pub value: RelayValue,
 }
 
-impl ConvertPacket<RelaySetValueEvent> for RelaySetValueEvent {
+impl ConvertPacket<RelayResetEnergyEvent> for RelayResetEnergyEvent {
     fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
-        if packet.data.len() != 6 {
+        if packet.data.len() != 5 {
             return Err(ConvertPacketError::WrongSize);
         }
 
@@ -71,7 +604,8 @@ impl ConvertPacket<RelaySetValueEvent> for RelaySetValueEvent {
             return Err(ConvertPacketError::WrongType);
         }
 
-        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != RELAY_SET_VALUE_EVENT_CODE
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap())
+            != RELAY_RESET_ENERGY_EVENT_CODE
         {
             return Err(ConvertPacketError::Event(EventError::WrongEventType));
         }
@@ -79,20 +613,18 @@ impl ConvertPacket<RelaySetValueEvent> for RelaySetValueEvent {
         let relay_address = packet.device_address;
         let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
         let index = packet.data[4];
-        let value = RelayValue::deserialize(&packet.data[5..])?;
 
         Ok(Self {
             relay_address,
             transmitter_address,
             index,
-            value,
         })
     }
 
     fn to_packet(&self) -> Packet {
         let mut data = vec![];
 
-        for byte in u16::to_be_bytes(RELAY_SET_VALUE_EVENT_CODE).iter() {
+        for byte in u16::to_be_bytes(RELAY_RESET_ENERGY_EVENT_CODE).iter() {
             data.push(*byte);
         }
 
@@ -101,7 +633,6 @@ impl ConvertPacket<RelaySetValueEvent> for RelaySetValueEvent {
         }
 
         data.push(self.index);
-        data.append(&mut self.value.serialize());
 
         Packet {
             is_error: false,
@@ -165,4 +696,432 @@ mod tests {
 
         assert_eq!(event.to_packet(), packet);
     }
+
+    #[test]
+    fn set_pulse_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((RELAY_SET_VALUE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (RELAY_SET_VALUE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                             // transmitter address
+            0x23,                                             // transmitter address
+            0x45,                                             // index
+            0x06,                                             // value
+            0x00,                                             // duration
+            0x00,                                             // duration
+            0x01,                                             // duration
+            0xf4,                                             // duration
+        ];
+
+        let event = RelaySetValueEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.value, RelayValue::Pulse(500));
+    }
+
+    #[test]
+    fn set_double_exclusive_for_duration_to_packet_test() {
+        let event = RelaySetValueEvent {
+            relay_address: 0xabab,
+            transmitter_address: 0x0123,
+            index: 0x45,
+            value: RelayValue::DoubleExclusiveForDuration(
+                RelayDoubleExclusiveValue::FirstChannelOn,
+                0x0001_d4c0,
+            ),
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((RELAY_SET_VALUE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (RELAY_SET_VALUE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                             // transmitter address
+            0x23,                                             // transmitter address
+            0x45,                                             // index
+            0x08,                                             // value
+            0x02,                                             // channel
+            0x00,                                             // duration
+            0x01,                                             // duration
+            0xd4,                                             // duration
+            0xc0,                                             // duration
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn set_value_wrong_size_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((RELAY_SET_VALUE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (RELAY_SET_VALUE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                             // transmitter address
+            0x23,                                             // transmitter address
+            0x45,                                             // index
+            0x07,                                             // value
+            0x00,                                             // duration
+        ];
+
+        assert_eq!(
+            RelaySetValueEvent::try_from_packet(&packet),
+            Err(ConvertPacketError::WrongSize)
+        );
+
+        packet.data = vec![
+            ((RELAY_SET_VALUE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (RELAY_SET_VALUE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                             // transmitter address
+            0x23,                                             // transmitter address
+            0x45,                                             // index
+            0x05,                                             // value
+            0x00,                                             // extra byte
+        ];
+
+        assert_eq!(
+            RelaySetValueEvent::try_from_packet(&packet),
+            Err(ConvertPacketError::WrongSize)
+        );
+    }
+
+    #[test]
+    fn get_value_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((RELAY_GET_VALUE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (RELAY_GET_VALUE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                             // transmitter address
+            0x23,                                             // transmitter address
+            0x45,                                             // index
+        ];
+
+        let event = RelayGetValueEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.relay_address, 0xabab);
+        assert_eq!(event.transmitter_address, 0x0123);
+        assert_eq!(event.index, 0x45);
+    }
+
+    #[test]
+    fn get_value_to_packet_test() {
+        let event = RelayGetValueEvent {
+            relay_address: 0xabab,
+            transmitter_address: 0x0123,
+            index: 0x45,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((RELAY_GET_VALUE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (RELAY_GET_VALUE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                             // transmitter address
+            0x23,                                             // transmitter address
+            0x45,                                             // index
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn report_value_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((RELAY_REPORT_VALUE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (RELAY_REPORT_VALUE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                // relay address
+            0x23,                                                // relay address
+            0x45,                                                // index
+            0x00,                                                // value
+        ];
+
+        let event = RelayReportValueEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.receiver_address, 0xabab);
+        assert_eq!(event.relay_address, 0x0123);
+        assert_eq!(event.index, 0x45);
+        assert_eq!(event.value, RelayState::Single(true));
+    }
+
+    #[test]
+    fn report_value_to_packet_test() {
+        let event = RelayReportValueEvent {
+            receiver_address: 0xabab,
+            relay_address: 0x0123,
+            index: 0x45,
+            value: RelayState::Single(true),
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((RELAY_REPORT_VALUE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (RELAY_REPORT_VALUE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                // relay address
+            0x23,                                                // relay address
+            0x45,                                                // index
+            0x00,                                                // value
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn report_value_command_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((RELAY_REPORT_VALUE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (RELAY_REPORT_VALUE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                // relay address
+            0x23,                                                // relay address
+            0x45,                                                // index
+            0x05,                                                // value
+        ];
+
+        assert_eq!(
+            RelayReportValueEvent::try_from_packet(&packet),
+            Err(ConvertPacketError::UnknownEnumVariant)
+        );
+
+        packet.data = vec![
+            ((RELAY_REPORT_VALUE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (RELAY_REPORT_VALUE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                // relay address
+            0x23,                                                // relay address
+            0x45,                                                // index
+            0x07,                                                // value
+            0x00,                                                // duration
+            0x00,                                                // duration
+            0x01,                                                // duration
+            0xf4,                                                // duration
+        ];
+
+        assert_eq!(
+            RelayReportValueEvent::try_from_packet(&packet),
+            Err(ConvertPacketError::WrongSize)
+        );
+    }
+
+    #[test]
+    fn value_changed_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((RELAY_VALUE_CHANGED_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (RELAY_VALUE_CHANGED_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                 // relay address
+            0x23,                                                 // relay address
+            0x45,                                                 // index
+            0x04,                                                 // value
+        ];
+
+        let event = RelayValueChangedEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.receiver_address, 0xabab);
+        assert_eq!(event.relay_address, 0x0123);
+        assert_eq!(event.index, 0x45);
+        assert_eq!(
+            event.value,
+            RelayState::DoubleExclusive(RelayDoubleExclusiveValue::NoChannelOn)
+        );
+    }
+
+    #[test]
+    fn value_changed_to_packet_test() {
+        let event = RelayValueChangedEvent {
+            receiver_address: 0xabab,
+            relay_address: 0x0123,
+            index: 0x45,
+            value: RelayState::DoubleExclusive(RelayDoubleExclusiveValue::NoChannelOn),
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((RELAY_VALUE_CHANGED_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (RELAY_VALUE_CHANGED_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                 // relay address
+            0x23,                                                 // relay address
+            0x45,                                                 // index
+            0x04,                                                 // value
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn report_power_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((RELAY_REPORT_POWER_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (RELAY_REPORT_POWER_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                // relay address
+            0x23,                                                // relay address
+            0x01,                                                // index
+            0x00,                                                // power
+            0x01,                                                // power
+            0xe2,                                                // power
+            0x40,                                                // power
+        ];
+
+        let event = RelayReportPowerEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.receiver_address, 0xabab);
+        assert_eq!(event.relay_address, 0x0123);
+        assert_eq!(event.index, 0x01);
+        assert_eq!(event.power, 0x0001e240);
+    }
+
+    #[test]
+    fn report_power_to_packet_test() {
+        let event = RelayReportPowerEvent {
+            receiver_address: 0xabab,
+            relay_address: 0x0123,
+            index: 0x01,
+            power: 0x0001e240,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((RELAY_REPORT_POWER_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (RELAY_REPORT_POWER_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                // relay address
+            0x23,                                                // relay address
+            0x01,                                                // index
+            0x00,                                                // power
+            0x01,                                                // power
+            0xe2,                                                // power
+            0x40,                                                // power
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn report_energy_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((RELAY_REPORT_ENERGY_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (RELAY_REPORT_ENERGY_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                 // relay address
+            0x23,                                                 // relay address
+            0x01,                                                 // index
+            0x00,                                                 // energy
+            0x01,                                                 // energy
+            0x23,                                                 // energy
+            0x45,                                                 // energy
+        ];
+
+        let event = RelayReportEnergyEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.receiver_address, 0xabab);
+        assert_eq!(event.relay_address, 0x0123);
+        assert_eq!(event.index, 0x01);
+        assert_eq!(event.energy, 0x00012345);
+    }
+
+    #[test]
+    fn report_energy_to_packet_test() {
+        let event = RelayReportEnergyEvent {
+            receiver_address: 0xabab,
+            relay_address: 0x0123,
+            index: 0x01,
+            energy: 0x00012345,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((RELAY_REPORT_ENERGY_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (RELAY_REPORT_ENERGY_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                 // relay address
+            0x23,                                                 // relay address
+            0x01,                                                 // index
+            0x00,                                                 // energy
+            0x01,                                                 // energy
+            0x23,                                                 // energy
+            0x45,                                                 // energy
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn over_current_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((RELAY_OVER_CURRENT_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (RELAY_OVER_CURRENT_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                // relay address
+            0x23,                                                // relay address
+            0x01,                                                // index
+            0x00,                                                // current
+            0x00,                                                // current
+            0x4e,                                                // current
+            0x20,                                                // current
+        ];
+
+        let event = RelayOverCurrentEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.receiver_address, 0xabab);
+        assert_eq!(event.relay_address, 0x0123);
+        assert_eq!(event.index, 0x01);
+        assert_eq!(event.current, 0x00004e20);
+    }
+
+    #[test]
+    fn over_current_to_packet_test() {
+        let event = RelayOverCurrentEvent {
+            receiver_address: 0xabab,
+            relay_address: 0x0123,
+            index: 0x01,
+            current: 0x00004e20,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((RELAY_OVER_CURRENT_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (RELAY_OVER_CURRENT_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                // relay address
+            0x23,                                                // relay address
+            0x01,                                                // index
+            0x00,                                                // current
+            0x00,                                                // current
+            0x4e,                                                // current
+            0x20,                                                // current
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn reset_energy_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((RELAY_RESET_ENERGY_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (RELAY_RESET_ENERGY_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                // transmitter address
+            0x23,                                                // transmitter address
+            0x01,                                                // index
+        ];
+
+        let event = RelayResetEnergyEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.relay_address, 0xabab);
+        assert_eq!(event.transmitter_address, 0x0123);
+        assert_eq!(event.index, 0x01);
+    }
+
+    #[test]
+    fn reset_energy_to_packet_test() {
+        let event = RelayResetEnergyEvent {
+            relay_address: 0xabab,
+            transmitter_address: 0x0123,
+            index: 0x01,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((RELAY_RESET_ENERGY_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (RELAY_RESET_ENERGY_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                // transmitter address
+            0x23,                                                // transmitter address
+            0x01,                                                // index
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
 }
@@ -0,0 +1,569 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+use crate::convert_packet::{ConvertPacket, ConvertPacketError};
+use crate::event::event_code::*;
+use crate::event::EventError;
+use crate::packet::Packet;
+
+/// How often a schedule entry fires, relative to its `time`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum ScheduleRecurrence {
+    /// Fires once at `time`, then the entry is removed
+    Once,
+    /// Fires every day at the time of day of `time`
+    Daily,
+    /// Fires at the time of day of `time` on the days set in the mask, bit 0 being Monday
+    ///
+    /// Bit 7 has to be clear.
+    Weekly(u8),
+    /// Fires every given number of seconds, starting at `time`
+    Interval(u32),
+}
+
+impl ScheduleRecurrence {
+    fn serialize(&self) -> Vec<u8> {
+        let (tag, argument) = match *self {
+            Self::Once => (0x00, 0),
+            Self::Daily => (0x01, 0),
+            Self::Weekly(days) => (0x02, days as u32),
+            Self::Interval(period) => (0x03, period),
+        };
+
+        let mut data = vec![tag];
+
+        for byte in u32::to_be_bytes(argument).iter() {
+            data.push(*byte);
+        }
+
+        data
+    }
+
+    fn deserialize(data: &[u8]) -> Result<Self, ConvertPacketError> {
+        if data.len() != 5 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        let argument = u32::from_be_bytes(data[1..=4].try_into().unwrap());
+
+        match data[0] {
+            0x00 => Ok(Self::Once),
+            0x01 => Ok(Self::Daily),
+            0x02 => {
+                if argument & !0x7f != 0 {
+                    return Err(ConvertPacketError::UnknownEnumVariant);
+                }
+
+                Ok(Self::Weekly(argument as u8))
+            }
+            0x03 => Ok(Self::Interval(argument)),
+            _ => Err(ConvertPacketError::UnknownEnumVariant),
+        }
+    }
+}
+
+/// Packet a scheduler sends whenever `recurrence` says so
+///
+/// `time` is in seconds since the unix epoch. Daily and weekly entries use only its time of day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduleEntry {
+    pub id: u8,
+    pub time: u32,
+    pub recurrence: ScheduleRecurrence,
+    pub packet: Packet,
+}
+
+impl ScheduleEntry {
+    fn serialize(&self) -> Vec<u8> {
+        let mut data = vec![self.id];
+
+        for byte in u32::to_be_bytes(self.time).iter() {
+            data.push(*byte);
+        }
+
+        data.append(&mut self.recurrence.serialize());
+
+        for byte in u16::to_be_bytes(self.packet.device_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(if self.packet.is_error { 0x01 } else { 0x00 });
+        data.extend_from_slice(&self.packet.data);
+
+        data
+    }
+
+    fn deserialize(data: &[u8]) -> Result<Self, ConvertPacketError> {
+        if data.len() < 13 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        let id = data[0];
+        let time = u32::from_be_bytes(data[1..=4].try_into().unwrap());
+        let recurrence = ScheduleRecurrence::deserialize(&data[5..=9])?;
+        let device_address = u16::from_be_bytes(data[10..=11].try_into().unwrap());
+        let is_error = data[12] != 0x00;
+
+        Ok(Self {
+            id,
+            time,
+            recurrence,
+            packet: Packet {
+                is_error,
+                device_address,
+                data: data[13..].to_vec(),
+            },
+        })
+    }
+}
+
+/// Adds an entry to a scheduler, replacing any entry with the same id
+#[derive(Debug, PartialEq)]
+pub struct ScheduleAddEvent {
+    pub scheduler_address: u16,
+    pub transmitter_address: u16,
+    pub entry: ScheduleEntry,
+}
+
+impl ConvertPacket<ScheduleAddEvent> for ScheduleAddEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() < 17 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != SCHEDULE_ADD_EVENT_CODE {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let scheduler_address = packet.device_address;
+        let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let entry = ScheduleEntry::deserialize(&packet.data[4..])?;
+
+        Ok(Self {
+            scheduler_address,
+            transmitter_address,
+            entry,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(SCHEDULE_ADD_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.transmitter_address).iter() {
+            data.push(*byte);
+        }
+
+        data.append(&mut self.entry.serialize());
+
+        Packet {
+            is_error: false,
+            device_address: self.scheduler_address,
+            data,
+        }
+    }
+}
+
+/// Removes the schedule entry with the given id
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ScheduleRemoveEvent {
+    pub scheduler_address: u16,
+    pub transmitter_address: u16,
+    pub id: u8,
+}
+
+impl ConvertPacket<ScheduleRemoveEvent> for ScheduleRemoveEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 5 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != SCHEDULE_REMOVE_EVENT_CODE
+        {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let scheduler_address = packet.device_address;
+        let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let id = packet.data[4];
+
+        Ok(Self {
+            scheduler_address,
+            transmitter_address,
+            id,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(SCHEDULE_REMOVE_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.transmitter_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.id);
+
+        Packet {
+            is_error: false,
+            device_address: self.scheduler_address,
+            data,
+        }
+    }
+}
+
+/// Asks a scheduler for its entries, answered with a `ScheduleReportEvent` for every entry
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ScheduleListEvent {
+    pub scheduler_address: u16,
+    pub transmitter_address: u16,
+}
+
+impl ConvertPacket<ScheduleListEvent> for ScheduleListEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 4 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != SCHEDULE_LIST_EVENT_CODE {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let scheduler_address = packet.device_address;
+        let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+
+        Ok(Self {
+            scheduler_address,
+            transmitter_address,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(SCHEDULE_LIST_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.transmitter_address).iter() {
+            data.push(*byte);
+        }
+
+        Packet {
+            is_error: false,
+            device_address: self.scheduler_address,
+            data,
+        }
+    }
+}
+
+/// Reports one entry of a scheduler, sent in reply to a `ScheduleListEvent`
+#[derive(Debug, PartialEq)]
+pub struct ScheduleReportEvent {
+    pub receiver_address: u16,
+    pub scheduler_address: u16,
+    pub entry: ScheduleEntry,
+}
+
+impl ConvertPacket<ScheduleReportEvent> for ScheduleReportEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() < 17 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != SCHEDULE_REPORT_EVENT_CODE
+        {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let receiver_address = packet.device_address;
+        let scheduler_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let entry = ScheduleEntry::deserialize(&packet.data[4..])?;
+
+        Ok(Self {
+            receiver_address,
+            scheduler_address,
+            entry,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(SCHEDULE_REPORT_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.scheduler_address).iter() {
+            data.push(*byte);
+        }
+
+        data.append(&mut self.entry.serialize());
+
+        Packet {
+            is_error: false,
+            device_address: self.receiver_address,
+            data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EVENT_PACKET: Packet = Packet {
+        is_error: false,
+        device_address: 0xabab,
+        data: vec![],
+    };
+
+    fn entry() -> ScheduleEntry {
+        ScheduleEntry {
+            id: 0x05,
+            time: 0x6543210f,
+            recurrence: ScheduleRecurrence::Weekly(0x1f),
+            packet: Packet {
+                is_error: false,
+                device_address: 0x0456,
+                data: vec![0x00, 0x01, 0x02],
+            },
+        }
+    }
+
+    fn entry_data() -> Vec<u8> {
+        vec![
+            0x05, // id
+            0x65, // time
+            0x43, // time
+            0x21, // time
+            0x0f, // time
+            0x02, // recurrence
+            0x00, // recurrence
+            0x00, // recurrence
+            0x00, // recurrence
+            0x1f, // recurrence
+            0x04, // packet device address
+            0x56, // packet device address
+            0x00, // packet is error
+            0x00, // packet data
+            0x01, // packet data
+            0x02, // packet data
+        ]
+    }
+
+    #[test]
+    fn add_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((SCHEDULE_ADD_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (SCHEDULE_ADD_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                          // transmitter address
+            0x23,                                          // transmitter address
+        ];
+        packet.data.append(&mut entry_data());
+
+        let event = ScheduleAddEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.scheduler_address, 0xabab);
+        assert_eq!(event.transmitter_address, 0x0123);
+        assert_eq!(event.entry, entry());
+    }
+
+    #[test]
+    fn add_try_from_packet_unknown_recurrence_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((SCHEDULE_ADD_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (SCHEDULE_ADD_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                          // transmitter address
+            0x23,                                          // transmitter address
+        ];
+        packet.data.append(&mut entry_data());
+        packet.data[9] = 0x04;
+
+        assert_eq!(
+            ScheduleAddEvent::try_from_packet(&packet),
+            Err(ConvertPacketError::UnknownEnumVariant)
+        );
+    }
+
+    #[test]
+    fn add_try_from_packet_invalid_days_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((SCHEDULE_ADD_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (SCHEDULE_ADD_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                          // transmitter address
+            0x23,                                          // transmitter address
+        ];
+        packet.data.append(&mut entry_data());
+        packet.data[13] = 0x9f;
+
+        assert_eq!(
+            ScheduleAddEvent::try_from_packet(&packet),
+            Err(ConvertPacketError::UnknownEnumVariant)
+        );
+
+        packet.data[12] = 0x01;
+        packet.data[13] = 0x1f;
+
+        assert_eq!(
+            ScheduleAddEvent::try_from_packet(&packet),
+            Err(ConvertPacketError::UnknownEnumVariant)
+        );
+    }
+
+    #[test]
+    fn add_to_packet_test() {
+        let event = ScheduleAddEvent {
+            scheduler_address: 0xabab,
+            transmitter_address: 0x0123,
+            entry: entry(),
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((SCHEDULE_ADD_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (SCHEDULE_ADD_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                          // transmitter address
+            0x23,                                          // transmitter address
+        ];
+        packet.data.append(&mut entry_data());
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn remove_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((SCHEDULE_REMOVE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (SCHEDULE_REMOVE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                             // transmitter address
+            0x23,                                             // transmitter address
+            0x05,                                             // id
+        ];
+
+        let event = ScheduleRemoveEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.scheduler_address, 0xabab);
+        assert_eq!(event.transmitter_address, 0x0123);
+        assert_eq!(event.id, 0x05);
+    }
+
+    #[test]
+    fn remove_to_packet_test() {
+        let event = ScheduleRemoveEvent {
+            scheduler_address: 0xabab,
+            transmitter_address: 0x0123,
+            id: 0x05,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((SCHEDULE_REMOVE_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (SCHEDULE_REMOVE_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                             // transmitter address
+            0x23,                                             // transmitter address
+            0x05,                                             // id
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn list_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((SCHEDULE_LIST_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (SCHEDULE_LIST_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                           // transmitter address
+            0x23,                                           // transmitter address
+        ];
+
+        let event = ScheduleListEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.scheduler_address, 0xabab);
+        assert_eq!(event.transmitter_address, 0x0123);
+    }
+
+    #[test]
+    fn list_to_packet_test() {
+        let event = ScheduleListEvent {
+            scheduler_address: 0xabab,
+            transmitter_address: 0x0123,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((SCHEDULE_LIST_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (SCHEDULE_LIST_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                           // transmitter address
+            0x23,                                           // transmitter address
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn report_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((SCHEDULE_REPORT_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (SCHEDULE_REPORT_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                             // scheduler address
+            0x23,                                             // scheduler address
+        ];
+        packet.data.append(&mut entry_data());
+
+        let event = ScheduleReportEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.receiver_address, 0xabab);
+        assert_eq!(event.scheduler_address, 0x0123);
+        assert_eq!(event.entry, entry());
+    }
+
+    #[test]
+    fn report_to_packet_test() {
+        let event = ScheduleReportEvent {
+            receiver_address: 0xabab,
+            scheduler_address: 0x0123,
+            entry: entry(),
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((SCHEDULE_REPORT_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (SCHEDULE_REPORT_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                             // scheduler address
+            0x23,                                             // scheduler address
+        ];
+        packet.data.append(&mut entry_data());
+
+        assert_eq!(event.to_packet(), packet);
+    }
+}
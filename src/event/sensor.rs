@@ -0,0 +1,385 @@
+use alloc::vec;
+use core::convert::TryInto;
+
+use crate::convert_packet::{ConvertPacket, ConvertPacketError};
+use crate::event::event_code::*;
+use crate::event::EventError;
+use crate::packet::Packet;
+
+/// Quantity measured by a sensor
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum SensorKind {
+    Temperature,
+    Humidity,
+    Illuminance,
+    /// Non-zero while motion is detected
+    Motion,
+    Power,
+    Energy,
+    Voltage,
+    Current,
+    Pressure,
+    Co2,
+}
+
+impl SensorKind {
+    fn serialize(self) -> u8 {
+        match self {
+            Self::Temperature => 0x00,
+            Self::Humidity => 0x01,
+            Self::Illuminance => 0x02,
+            Self::Motion => 0x03,
+            Self::Power => 0x04,
+            Self::Energy => 0x05,
+            Self::Voltage => 0x06,
+            Self::Current => 0x07,
+            Self::Pressure => 0x08,
+            Self::Co2 => 0x09,
+        }
+    }
+
+    fn deserialize(data: u8) -> Result<Self, ConvertPacketError> {
+        match data {
+            0x00 => Ok(Self::Temperature),
+            0x01 => Ok(Self::Humidity),
+            0x02 => Ok(Self::Illuminance),
+            0x03 => Ok(Self::Motion),
+            0x04 => Ok(Self::Power),
+            0x05 => Ok(Self::Energy),
+            0x06 => Ok(Self::Voltage),
+            0x07 => Ok(Self::Current),
+            0x08 => Ok(Self::Pressure),
+            0x09 => Ok(Self::Co2),
+            _ => Err(ConvertPacketError::UnknownEnumVariant),
+        }
+    }
+}
+
+/// Unit of a sensor reading
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum SensorUnit {
+    /// Plain number or boolean without a unit
+    None,
+    Celsius,
+    Percent,
+    Lux,
+    Watt,
+    WattHour,
+    Volt,
+    Ampere,
+    Pascal,
+    PartsPerMillion,
+}
+
+impl SensorUnit {
+    fn serialize(self) -> u8 {
+        match self {
+            Self::None => 0x00,
+            Self::Celsius => 0x01,
+            Self::Percent => 0x02,
+            Self::Lux => 0x03,
+            Self::Watt => 0x04,
+            Self::WattHour => 0x05,
+            Self::Volt => 0x06,
+            Self::Ampere => 0x07,
+            Self::Pascal => 0x08,
+            Self::PartsPerMillion => 0x09,
+        }
+    }
+
+    fn deserialize(data: u8) -> Result<Self, ConvertPacketError> {
+        match data {
+            0x00 => Ok(Self::None),
+            0x01 => Ok(Self::Celsius),
+            0x02 => Ok(Self::Percent),
+            0x03 => Ok(Self::Lux),
+            0x04 => Ok(Self::Watt),
+            0x05 => Ok(Self::WattHour),
+            0x06 => Ok(Self::Volt),
+            0x07 => Ok(Self::Ampere),
+            0x08 => Ok(Self::Pascal),
+            0x09 => Ok(Self::PartsPerMillion),
+            _ => Err(ConvertPacketError::UnknownEnumVariant),
+        }
+    }
+}
+
+/// Asks a sensor for its current reading, answered with a `SensorReadingEvent`
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct SensorGetReadingEvent {
+    pub sensor_address: u16,
+    pub transmitter_address: u16,
+    pub index: u8,
+}
+
+impl ConvertPacket<SensorGetReadingEvent> for SensorGetReadingEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 5 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap())
+            != SENSOR_GET_READING_EVENT_CODE
+        {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let sensor_address = packet.device_address;
+        let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = packet.data[4];
+
+        Ok(Self {
+            sensor_address,
+            transmitter_address,
+            index,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(SENSOR_GET_READING_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.transmitter_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.index);
+
+        Packet {
+            is_error: false,
+            device_address: self.sensor_address,
+            data,
+        }
+    }
+}
+
+/// Reports a sensor reading of `value * 10^exponent` in the given unit
+///
+/// Sent in reply to a `SensorGetReadingEvent` and whenever the reading changes.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct SensorReadingEvent {
+    pub receiver_address: u16,
+    pub sensor_address: u16,
+    pub index: u8,
+    pub kind: SensorKind,
+    pub unit: SensorUnit,
+    pub exponent: i8,
+    pub value: i32,
+}
+
+impl ConvertPacket<SensorReadingEvent> for SensorReadingEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 12 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != SENSOR_READING_EVENT_CODE {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let receiver_address = packet.device_address;
+        let sensor_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let index = packet.data[4];
+        let kind = SensorKind::deserialize(packet.data[5])?;
+        let unit = SensorUnit::deserialize(packet.data[6])?;
+        let exponent = packet.data[7] as i8;
+        let value = i32::from_be_bytes(packet.data[8..=11].try_into().unwrap());
+
+        Ok(Self {
+            receiver_address,
+            sensor_address,
+            index,
+            kind,
+            unit,
+            exponent,
+            value,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(SENSOR_READING_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.sensor_address).iter() {
+            data.push(*byte);
+        }
+
+        data.push(self.index);
+        data.push(self.kind.serialize());
+        data.push(self.unit.serialize());
+        data.push(self.exponent as u8);
+
+        for byte in i32::to_be_bytes(self.value).iter() {
+            data.push(*byte);
+        }
+
+        Packet {
+            is_error: false,
+            device_address: self.receiver_address,
+            data,
+        }
+    }
+}
+
+impl SensorReadingEvent {
+    /// Returns the reading scaled to the given exponent, rounding towards zero
+    ///
+    /// Returns `None` if the scaled value doesn't fit into an `i32`.
+    pub fn value_with_exponent(&self, exponent: i8) -> Option<i32> {
+        let shift = self.exponent as i32 - exponent as i32;
+
+        if shift >= 0 {
+            10i32
+                .checked_pow(shift as u32)
+                .and_then(|factor| self.value.checked_mul(factor))
+        } else {
+            match 10i32.checked_pow(-shift as u32) {
+                Some(divisor) => Some(self.value / divisor),
+                None => Some(0),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EVENT_PACKET: Packet = Packet {
+        is_error: false,
+        device_address: 0xabab,
+        data: vec![],
+    };
+
+    #[test]
+    fn get_reading_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((SENSOR_GET_READING_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (SENSOR_GET_READING_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                // transmitter address
+            0x23,                                                // transmitter address
+            0x45,                                                // index
+        ];
+
+        let event = SensorGetReadingEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.sensor_address, 0xabab);
+        assert_eq!(event.transmitter_address, 0x0123);
+        assert_eq!(event.index, 0x45);
+    }
+
+    #[test]
+    fn get_reading_to_packet_test() {
+        let event = SensorGetReadingEvent {
+            sensor_address: 0xabab,
+            transmitter_address: 0x0123,
+            index: 0x45,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((SENSOR_GET_READING_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (SENSOR_GET_READING_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                                // transmitter address
+            0x23,                                                // transmitter address
+            0x45,                                                // index
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn reading_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((SENSOR_READING_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (SENSOR_READING_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                            // sensor address
+            0x23,                                            // sensor address
+            0x45,                                            // index
+            0x00,                                            // kind
+            0x01,                                            // unit
+            0xfe,                                            // exponent
+            0x00,                                            // value
+            0x00,                                            // value
+            0x08,                                            // value
+            0x66,                                            // value
+        ];
+
+        let event = SensorReadingEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.receiver_address, 0xabab);
+        assert_eq!(event.sensor_address, 0x0123);
+        assert_eq!(event.index, 0x45);
+        assert_eq!(event.kind, SensorKind::Temperature);
+        assert_eq!(event.unit, SensorUnit::Celsius);
+        assert_eq!(event.exponent, -2);
+        assert_eq!(event.value, 0x00000866);
+    }
+
+    #[test]
+    fn reading_to_packet_test() {
+        let event = SensorReadingEvent {
+            receiver_address: 0xabab,
+            sensor_address: 0x0123,
+            index: 0x45,
+            kind: SensorKind::Temperature,
+            unit: SensorUnit::Celsius,
+            exponent: -2,
+            value: 0x00000866,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((SENSOR_READING_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (SENSOR_READING_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                            // sensor address
+            0x23,                                            // sensor address
+            0x45,                                            // index
+            0x00,                                            // kind
+            0x01,                                            // unit
+            0xfe,                                            // exponent
+            0x00,                                            // value
+            0x00,                                            // value
+            0x08,                                            // value
+            0x66,                                            // value
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn reading_value_with_exponent_test() {
+        let event = SensorReadingEvent {
+            receiver_address: 0xabab,
+            sensor_address: 0x0123,
+            index: 0x00,
+            kind: SensorKind::Temperature,
+            unit: SensorUnit::Celsius,
+            exponent: -2,
+            value: 2150,
+        };
+
+        assert_eq!(event.value_with_exponent(-2), Some(2150));
+        assert_eq!(event.value_with_exponent(-1), Some(215));
+        assert_eq!(event.value_with_exponent(0), Some(21));
+        assert_eq!(event.value_with_exponent(-3), Some(21500));
+        assert_eq!(event.value_with_exponent(-20), None);
+        assert_eq!(event.value_with_exponent(20), Some(0));
+    }
+}
@@ -0,0 +1,249 @@
+use alloc::vec;
+use core::convert::TryInto;
+
+use crate::convert_packet::{ConvertPacket, ConvertPacketError};
+use crate::event::event_code::*;
+use crate::event::EventError;
+use crate::packet::Packet;
+
+/// Asks the time master for the current time, answered with a `TimeSyncEvent`
+///
+/// Sent by devices that just started and can't wait for the next broadcast.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct TimeGetEvent {
+    pub time_master_address: u16,
+    pub transmitter_address: u16,
+}
+
+impl ConvertPacket<TimeGetEvent> for TimeGetEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 4 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != TIME_GET_EVENT_CODE {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let time_master_address = packet.device_address;
+        let transmitter_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+
+        Ok(Self {
+            time_master_address,
+            transmitter_address,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(TIME_GET_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.transmitter_address).iter() {
+            data.push(*byte);
+        }
+
+        Packet {
+            is_error: false,
+            device_address: self.time_master_address,
+            data,
+        }
+    }
+}
+
+/// Current UTC time of the time master, broadcast periodically and sent in reply to a
+/// `TimeGetEvent`
+///
+/// `seconds` counts from the unix epoch, `milliseconds` the time since the start of that second.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct TimeSyncEvent {
+    pub receiver_address: u16,
+    pub time_master_address: u16,
+    pub seconds: u32,
+    pub milliseconds: u16,
+}
+
+impl ConvertPacket<TimeSyncEvent> for TimeSyncEvent {
+    fn try_from_packet(packet: &Packet) -> Result<Self, ConvertPacketError> {
+        if packet.data.len() != 10 {
+            return Err(ConvertPacketError::WrongSize);
+        }
+
+        if packet.is_error {
+            return Err(ConvertPacketError::WrongType);
+        }
+
+        if u16::from_be_bytes(packet.data[0..=1].try_into().unwrap()) != TIME_SYNC_EVENT_CODE {
+            return Err(ConvertPacketError::Event(EventError::WrongEventType));
+        }
+
+        let receiver_address = packet.device_address;
+        let time_master_address = u16::from_be_bytes(packet.data[2..=3].try_into().unwrap());
+        let seconds = u32::from_be_bytes(packet.data[4..=7].try_into().unwrap());
+        let milliseconds = u16::from_be_bytes(packet.data[8..=9].try_into().unwrap());
+
+        Ok(Self {
+            receiver_address,
+            time_master_address,
+            seconds,
+            milliseconds,
+        })
+    }
+
+    fn to_packet(&self) -> Packet {
+        let mut data = vec![];
+
+        for byte in u16::to_be_bytes(TIME_SYNC_EVENT_CODE).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.time_master_address).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u32::to_be_bytes(self.seconds).iter() {
+            data.push(*byte);
+        }
+
+        for byte in u16::to_be_bytes(self.milliseconds).iter() {
+            data.push(*byte);
+        }
+
+        Packet {
+            is_error: false,
+            device_address: self.receiver_address,
+            data,
+        }
+    }
+}
+
+impl TimeSyncEvent {
+    /// Creates an event carrying `timestamp` milliseconds since the unix epoch
+    pub fn from_timestamp_millis(
+        receiver_address: u16,
+        time_master_address: u16,
+        timestamp: u64,
+    ) -> Self {
+        TimeSyncEvent {
+            receiver_address,
+            time_master_address,
+            seconds: (timestamp / 1000) as u32,
+            milliseconds: (timestamp % 1000) as u16,
+        }
+    }
+
+    /// Returns the time in milliseconds since the unix epoch
+    pub fn timestamp_millis(&self) -> u64 {
+        self.seconds as u64 * 1000 + self.milliseconds as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EVENT_PACKET: Packet = Packet {
+        is_error: false,
+        device_address: 0xabab,
+        data: vec![],
+    };
+
+    #[test]
+    fn get_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((TIME_GET_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (TIME_GET_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                      // transmitter address
+            0x23,                                      // transmitter address
+        ];
+
+        let event = TimeGetEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.time_master_address, 0xabab);
+        assert_eq!(event.transmitter_address, 0x0123);
+    }
+
+    #[test]
+    fn get_to_packet_test() {
+        let event = TimeGetEvent {
+            time_master_address: 0xabab,
+            transmitter_address: 0x0123,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((TIME_GET_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (TIME_GET_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                      // transmitter address
+            0x23,                                      // transmitter address
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn sync_try_from_packet_test() {
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((TIME_SYNC_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (TIME_SYNC_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                       // time master address
+            0x23,                                       // time master address
+            0x65,                                       // seconds
+            0x43,                                       // seconds
+            0x21,                                       // seconds
+            0x0f,                                       // seconds
+            0x01,                                       // milliseconds
+            0xf4,                                       // milliseconds
+        ];
+
+        let event = TimeSyncEvent::try_from_packet(&packet).unwrap();
+
+        assert_eq!(event.receiver_address, 0xabab);
+        assert_eq!(event.time_master_address, 0x0123);
+        assert_eq!(event.seconds, 0x6543210f);
+        assert_eq!(event.milliseconds, 0x01f4);
+    }
+
+    #[test]
+    fn sync_to_packet_test() {
+        let event = TimeSyncEvent {
+            receiver_address: 0xabab,
+            time_master_address: 0x0123,
+            seconds: 0x6543210f,
+            milliseconds: 0x01f4,
+        };
+
+        let mut packet = EVENT_PACKET;
+        packet.data = vec![
+            ((TIME_SYNC_EVENT_CODE >> 8) & 0xff) as u8, // event code
+            (TIME_SYNC_EVENT_CODE & 0xff) as u8,        // event code
+            0x01,                                       // time master address
+            0x23,                                       // time master address
+            0x65,                                       // seconds
+            0x43,                                       // seconds
+            0x21,                                       // seconds
+            0x0f,                                       // seconds
+            0x01,                                       // milliseconds
+            0xf4,                                       // milliseconds
+        ];
+
+        assert_eq!(event.to_packet(), packet);
+    }
+
+    #[test]
+    fn sync_timestamp_millis_test() {
+        let event = TimeSyncEvent::from_timestamp_millis(0xabab, 0x0123, 1_700_000_000_500);
+
+        assert_eq!(event.seconds, 1_700_000_000);
+        assert_eq!(event.milliseconds, 500);
+        assert_eq!(event.timestamp_millis(), 1_700_000_000_500);
+    }
+}
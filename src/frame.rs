@@ -1,6 +1,6 @@
 use alloc::vec;
 use alloc::vec::Vec;
-use cobs::{decode, encode, max_encoding_length};
+use cobs::{decode_in_place, encode, max_encoding_length};
 
 use bxcan::{Data, ExtendedId, Frame as BxFrame, Id};
 
@@ -156,13 +156,18 @@ impl Frame {
     /// byte 4:         DATA_LEN (length of frame data)
     /// bytes 5 - 12:   DATA (frame data)
     pub fn from_usart_frame(encoded: Vec<u8>) -> Result<Self, FrameError> {
-        let mut frame = vec![0; encoded.len()];
-        match decode(&encoded[..], &mut frame[..]) {
+        // COBS encoded data never contains zeroes
+        if encoded.contains(&0x00) {
+            return Err(FrameError::CobsError);
+        }
+
+        let mut frame = encoded;
+        match decode_in_place(&mut frame[..]) {
             Ok(n) => frame.truncate(n),
             Err(_) => return Err(FrameError::CobsError),
         }
 
-        if frame.len() < 5 || frame.len() != frame[4] as usize + 5 {
+        if frame.len() < 5 || frame[4] > 8 || frame.len() != frame[4] as usize + 5 {
             return Err(FrameError::WrongSize);
         }
 
@@ -313,4 +318,42 @@ mod tests {
 
         assert_eq!(usart_frame, usart_frame_expected);
     }
+
+    #[test]
+    fn from_usart_frame_too_long_test() {
+        let usart_frame = vec![
+            0x0f, // cobs
+            0xa5, // byte 0
+            0x55, // frame id
+            0x55, // device address
+            0x55, // device address
+            0x09, // data len
+            0x55, // data
+            0x55, // data
+            0x55, // data
+            0x55, // data
+            0x55, // data
+            0x55, // data
+            0x55, // data
+            0x55, // data
+            0x55, // data
+        ];
+
+        assert_eq!(
+            Frame::from_usart_frame(usart_frame),
+            Err(FrameError::WrongSize)
+        );
+    }
+
+    #[test]
+    fn from_usart_frame_cobs_error_test() {
+        assert_eq!(
+            Frame::from_usart_frame(vec![0x0e, 0xa5, 0x00]),
+            Err(FrameError::CobsError)
+        );
+        assert_eq!(
+            Frame::from_usart_frame(vec![0x0e, 0xa5]),
+            Err(FrameError::CobsError)
+        );
+    }
 }
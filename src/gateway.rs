@@ -0,0 +1,409 @@
+use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+use core::convert::TryInto;
+
+use crate::convert_packet::ConvertPacket;
+use crate::event::gateway::GatewayDiscoverEvent;
+use crate::interface::*;
+use crate::packet::Packet;
+use crate::protocol::{is_group_address, BROADCAST_ADDRESS};
+
+/// Amount of forwarded packets remembered for echo suppression
+const RECENT_PACKET_COUNT: usize = 16;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum GatewaySide {
+    First,
+    Second,
+}
+
+impl GatewaySide {
+    pub fn other(self) -> Self {
+        match self {
+            Self::First => Self::Second,
+            Self::Second => Self::First,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum GatewayError {
+    InterfaceError(GatewaySide, InterfaceError),
+    /// Both sides failed during the same tick, the error of the first side comes first
+    InterfaceErrors((GatewaySide, InterfaceError), (GatewaySide, InterfaceError)),
+}
+
+/// Bridge forwarding packets between two bus segments
+///
+/// The gateway learns on which side every device lives from the transmitter address carried in
+/// bytes 2 and 3 of every event. Packets addressed to a device on the same side they were received
+/// from are not forwarded. To prevent loops when several gateways connect the same segments,
+/// packets that come back on the side they were just forwarded to are dropped.
+///
+/// Packets addressed to groups are forwarded like broadcasts, as members can live on both sides.
+///
+/// Devices discover gateways by broadcasting a `GatewayDiscoverEvent` carrying their own address;
+/// the gateway replies directly to the device with its own address.
+pub struct Gateway<F: Interface, S: Interface> {
+    gateway_address: u16,
+    first: F,
+    second: S,
+    device_sides: BTreeMap<u16, GatewaySide>,
+    blocked_event_codes: BTreeSet<u16>,
+    recent_packets: VecDeque<(GatewaySide, Packet)>,
+}
+
+impl<F: Interface, S: Interface> Gateway<F, S> {
+    pub fn new(gateway_address: u16, first: F, second: S) -> Self {
+        Gateway {
+            gateway_address,
+            first,
+            second,
+            device_sides: BTreeMap::new(),
+            blocked_event_codes: BTreeSet::new(),
+            recent_packets: VecDeque::new(),
+        }
+    }
+
+    pub fn gateway_address(&self) -> u16 {
+        self.gateway_address
+    }
+
+    pub fn first(&mut self) -> &mut F {
+        &mut self.first
+    }
+
+    pub fn second(&mut self) -> &mut S {
+        &mut self.second
+    }
+
+    /// Returns the side a device was last seen on
+    pub fn device_side(&self, device_address: u16) -> Option<GatewaySide> {
+        self.device_sides.get(&device_address).copied()
+    }
+
+    /// Stops forwarding events with the given event code
+    pub fn block_event_code(&mut self, event_code: u16) {
+        self.blocked_event_codes.insert(event_code);
+    }
+
+    pub fn unblock_event_code(&mut self, event_code: u16) {
+        self.blocked_event_codes.remove(&event_code);
+    }
+
+    /// Receives at most one packet from each side and forwards it if needed
+    pub fn tick(&mut self) -> Result<(), GatewayError> {
+        let first_result = self.receive(GatewaySide::First);
+        let second_result = self.receive(GatewaySide::Second);
+
+        match (first_result, second_result) {
+            (
+                Err(GatewayError::InterfaceError(first_side, first_err)),
+                Err(GatewayError::InterfaceError(second_side, second_err)),
+            ) => Err(GatewayError::InterfaceErrors(
+                (first_side, first_err),
+                (second_side, second_err),
+            )),
+            (first_result, second_result) => first_result.and(second_result),
+        }
+    }
+
+    fn receive(&mut self, side: GatewaySide) -> Result<(), GatewayError> {
+        let result = match side {
+            GatewaySide::First => self.first.try_get_packet(),
+            GatewaySide::Second => self.second.try_get_packet(),
+        };
+
+        match result {
+            Ok(packet) => self.handle_packet(side, packet),
+            Err(InterfaceError::NoPacketReceived) => Ok(()),
+            Err(err) => Err(GatewayError::InterfaceError(side, err)),
+        }
+    }
+
+    fn handle_packet(&mut self, side: GatewaySide, packet: Packet) -> Result<(), GatewayError> {
+        if let Some(index) = self
+            .recent_packets
+            .iter()
+            .position(|recent| recent.0 == side && recent.1 == packet)
+        {
+            self.recent_packets.remove(index);
+
+            return Ok(());
+        }
+
+        let transmitter_address = if packet.data.len() >= 4 {
+            Some(u16::from_be_bytes(packet.data[2..=3].try_into().unwrap()))
+        } else {
+            None
+        };
+
+        if let Some(transmitter_address) = transmitter_address {
+            if transmitter_address != self.gateway_address
+                && transmitter_address != BROADCAST_ADDRESS
+                && !is_group_address(transmitter_address)
+            {
+                self.device_sides.insert(transmitter_address, side);
+            }
+        }
+
+        if packet.device_address == BROADCAST_ADDRESS {
+            if let Ok(event) = GatewayDiscoverEvent::try_from_packet(&packet) {
+                let reply = GatewayDiscoverEvent {
+                    device_address: event.gateway_address,
+                    gateway_address: self.gateway_address,
+                };
+
+                return self.send(side, &reply.to_packet());
+            }
+        }
+
+        if packet.device_address == self.gateway_address {
+            return Ok(());
+        }
+
+        if packet.data.len() >= 2 {
+            let event_code = u16::from_be_bytes(packet.data[0..=1].try_into().unwrap());
+
+            if self.blocked_event_codes.contains(&event_code) {
+                return Ok(());
+            }
+        }
+
+        if packet.device_address != BROADCAST_ADDRESS
+            && self.device_side(packet.device_address) == Some(side)
+        {
+            return Ok(());
+        }
+
+        let other_side = side.other();
+
+        if self.recent_packets.len() == RECENT_PACKET_COUNT {
+            self.recent_packets.pop_front();
+        }
+
+        self.recent_packets.push_back((other_side, packet.clone()));
+
+        self.send(other_side, &packet)
+    }
+
+    fn send(&mut self, side: GatewaySide, packet: &Packet) -> Result<(), GatewayError> {
+        let result = match side {
+            GatewaySide::First => self.first.try_send_packet(packet),
+            GatewaySide::Second => self.second.try_send_packet(packet),
+        };
+
+        result.map_err(|err| GatewayError::InterfaceError(side, err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use crate::event::button::ButtonPressedEvent;
+    use crate::event::event_code::BUTTON_PRESSED_EVENT_CODE;
+    use crate::interface::can::CanError;
+
+    const GATEWAY_ADDRESS: u16 = 0x0100;
+
+    struct MockInterface {
+        received: VecDeque<Packet>,
+        sent: Vec<Packet>,
+        overrun: bool,
+    }
+
+    impl MockInterface {
+        fn new() -> Self {
+            MockInterface {
+                received: VecDeque::new(),
+                sent: vec![],
+                overrun: false,
+            }
+        }
+    }
+
+    impl Interface for MockInterface {
+        fn try_get_packet(&mut self) -> Result<Packet, InterfaceError> {
+            if self.overrun {
+                return Err(InterfaceError::CanError(CanError::BufferOverrun));
+            }
+
+            self.received
+                .pop_front()
+                .ok_or(InterfaceError::NoPacketReceived)
+        }
+
+        fn try_send_packet(&mut self, packet: &Packet) -> Result<(), InterfaceError> {
+            self.sent.push(packet.clone());
+
+            Ok(())
+        }
+    }
+
+    fn gateway() -> Gateway<MockInterface, MockInterface> {
+        Gateway::new(GATEWAY_ADDRESS, MockInterface::new(), MockInterface::new())
+    }
+
+    fn button_packet(receiver_address: u16, button_address: u16) -> Packet {
+        ButtonPressedEvent {
+            receiver_address,
+            button_address,
+            index: 0x01,
+        }
+        .to_packet()
+    }
+
+    #[test]
+    fn forward_broadcast_test() {
+        let mut gateway = gateway();
+
+        let packet = button_packet(BROADCAST_ADDRESS, 0x0001);
+        gateway.first().received.push_back(packet.clone());
+        gateway.tick().unwrap();
+
+        assert_eq!(gateway.second().sent, vec![packet]);
+        assert_eq!(gateway.first().sent, vec![]);
+        assert_eq!(gateway.device_side(0x0001), Some(GatewaySide::First));
+    }
+
+    #[test]
+    fn local_traffic_test() {
+        let mut gateway = gateway();
+
+        gateway
+            .first()
+            .received
+            .push_back(button_packet(BROADCAST_ADDRESS, 0x0001));
+        gateway
+            .first()
+            .received
+            .push_back(button_packet(0x0001, 0x0002));
+        gateway
+            .second()
+            .received
+            .push_back(button_packet(0x0001, 0x0003));
+        gateway.tick().unwrap();
+        gateway.tick().unwrap();
+
+        assert_eq!(
+            gateway.second().sent,
+            vec![button_packet(BROADCAST_ADDRESS, 0x0001)]
+        );
+        assert_eq!(gateway.first().sent, vec![button_packet(0x0001, 0x0003)]);
+    }
+
+    #[test]
+    fn forward_group_test() {
+        let mut gateway = gateway();
+
+        // Group addresses are never learned as device sides
+        gateway
+            .second()
+            .received
+            .push_back(button_packet(0x0001, 0xff12));
+        gateway.tick().unwrap();
+
+        let packet = button_packet(0xff12, 0x0001);
+        gateway.second().received.push_back(packet.clone());
+        gateway.tick().unwrap();
+
+        assert_eq!(gateway.device_side(0xff12), None);
+        assert_eq!(
+            gateway.first().sent,
+            vec![button_packet(0x0001, 0xff12), packet]
+        );
+    }
+
+    #[test]
+    fn echo_suppression_test() {
+        let mut gateway = gateway();
+
+        let packet = button_packet(BROADCAST_ADDRESS, 0x0001);
+        gateway.first().received.push_back(packet.clone());
+        gateway.tick().unwrap();
+
+        // Another gateway between the same segments forwards the packet back
+        gateway.second().received.push_back(packet.clone());
+        gateway.tick().unwrap();
+
+        assert_eq!(gateway.second().sent, vec![packet]);
+        assert_eq!(gateway.first().sent, vec![]);
+        assert_eq!(gateway.device_side(0x0001), Some(GatewaySide::First));
+    }
+
+    #[test]
+    fn discover_test() {
+        let mut gateway = gateway();
+
+        let request = GatewayDiscoverEvent {
+            device_address: BROADCAST_ADDRESS,
+            gateway_address: 0x0001,
+        };
+        gateway.second().received.push_back(request.to_packet());
+        gateway.tick().unwrap();
+
+        let reply = GatewayDiscoverEvent {
+            device_address: 0x0001,
+            gateway_address: GATEWAY_ADDRESS,
+        };
+
+        assert_eq!(gateway.second().sent, vec![reply.to_packet()]);
+        assert_eq!(gateway.first().sent, vec![]);
+    }
+
+    #[test]
+    fn blocked_event_code_test() {
+        let mut gateway = gateway();
+        gateway.block_event_code(BUTTON_PRESSED_EVENT_CODE);
+
+        gateway
+            .first()
+            .received
+            .push_back(button_packet(BROADCAST_ADDRESS, 0x0001));
+        gateway.tick().unwrap();
+
+        assert_eq!(gateway.second().sent, vec![]);
+
+        gateway.unblock_event_code(BUTTON_PRESSED_EVENT_CODE);
+
+        gateway
+            .first()
+            .received
+            .push_back(button_packet(BROADCAST_ADDRESS, 0x0001));
+        gateway.tick().unwrap();
+
+        assert_eq!(
+            gateway.second().sent,
+            vec![button_packet(BROADCAST_ADDRESS, 0x0001)]
+        );
+    }
+
+    #[test]
+    fn both_sides_failed_test() {
+        let mut gateway = gateway();
+
+        gateway.second().overrun = true;
+        assert!(matches!(
+            gateway.tick(),
+            Err(GatewayError::InterfaceError(GatewaySide::Second, _))
+        ));
+
+        gateway.first().overrun = true;
+        assert!(matches!(
+            gateway.tick(),
+            Err(GatewayError::InterfaceErrors(
+                (
+                    GatewaySide::First,
+                    InterfaceError::CanError(CanError::BufferOverrun)
+                ),
+                (
+                    GatewaySide::Second,
+                    InterfaceError::CanError(CanError::BufferOverrun)
+                ),
+            ))
+        ));
+    }
+}
@@ -0,0 +1,401 @@
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::convert_packet::ConvertPacket;
+use crate::event::button::*;
+use crate::packet::Packet;
+
+/// Timing thresholds of a `GestureRecognizer`, all in milliseconds
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct GestureConfig {
+    /// Time a button has to be held down to be long pressed
+    pub long_press_time: u32,
+    /// Longest time between releasing a button and pressing it again for a double click
+    ///
+    /// Single clicks are reported only after this time has passed. Set it to 0 to disable double
+    /// clicks and report single clicks right away.
+    pub double_click_time: u32,
+    /// Time between two hold repeats after a long press, 0 disables hold repeats
+    pub hold_repeat_time: u32,
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        GestureConfig {
+            long_press_time: 600,
+            double_click_time: 300,
+            hold_repeat_time: 200,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ButtonGesture {
+    Click,
+    DoubleClick,
+    LongPress,
+    /// Hold repeat with its count, starting from 1
+    HoldRepeat(u16),
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct RecognizedGesture {
+    pub button_address: u16,
+    pub index: u8,
+    pub gesture: ButtonGesture,
+}
+
+impl RecognizedGesture {
+    /// Returns the gesture event for this gesture addressed to `receiver_address`
+    pub fn to_packet(&self, receiver_address: u16) -> Packet {
+        let button_address = self.button_address;
+        let index = self.index;
+
+        match self.gesture {
+            ButtonGesture::Click => ButtonClickedEvent {
+                receiver_address,
+                button_address,
+                index,
+            }
+            .to_packet(),
+            ButtonGesture::DoubleClick => ButtonDoubleClickedEvent {
+                receiver_address,
+                button_address,
+                index,
+            }
+            .to_packet(),
+            ButtonGesture::LongPress => ButtonLongPressedEvent {
+                receiver_address,
+                button_address,
+                index,
+            }
+            .to_packet(),
+            ButtonGesture::HoldRepeat(repeat) => ButtonHeldEvent {
+                receiver_address,
+                button_address,
+                index,
+                repeat,
+            }
+            .to_packet(),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum ButtonState {
+    Pressed { pressed_at: u32, second_press: bool },
+    LongPressed { repeated_at: u32, repeat: u16 },
+    Released { released_at: u32 },
+}
+
+/// Turns raw button presses and releases into clicks, double clicks, long presses and hold
+/// repeats
+///
+/// Buttons are told apart by their address and index, so a single recogniser can be used on the
+/// button device itself as well as on a receiver listening to many buttons. `tick` has to be
+/// called regularly, as long presses, hold repeats and (with double clicks enabled) single clicks
+/// are recognised only as time passes. Times are in milliseconds and may wrap around.
+pub struct GestureRecognizer {
+    config: GestureConfig,
+    buttons: BTreeMap<(u16, u8), ButtonState>,
+}
+
+impl GestureRecognizer {
+    pub fn new() -> Self {
+        Self::with_config(GestureConfig::default())
+    }
+
+    pub fn with_config(config: GestureConfig) -> Self {
+        GestureRecognizer {
+            config,
+            buttons: BTreeMap::new(),
+        }
+    }
+
+    pub fn config(&self) -> GestureConfig {
+        self.config
+    }
+
+    /// Returns the click of the previous press if its double click time ran out before `tick`
+    /// reported it
+    pub fn press(&mut self, button_address: u16, index: u8, now: u32) -> Option<RecognizedGesture> {
+        let (second_press, gesture) = match self.buttons.get(&(button_address, index)) {
+            Some(ButtonState::Released { released_at }) => {
+                if now.wrapping_sub(*released_at) < self.config.double_click_time {
+                    (true, None)
+                } else {
+                    let gesture = RecognizedGesture {
+                        button_address,
+                        index,
+                        gesture: ButtonGesture::Click,
+                    };
+
+                    (false, Some(gesture))
+                }
+            }
+            _ => (false, None),
+        };
+
+        self.buttons.insert(
+            (button_address, index),
+            ButtonState::Pressed {
+                pressed_at: now,
+                second_press,
+            },
+        );
+
+        gesture
+    }
+
+    pub fn release(
+        &mut self,
+        button_address: u16,
+        index: u8,
+        now: u32,
+    ) -> Option<RecognizedGesture> {
+        let gesture = |gesture| {
+            Some(RecognizedGesture {
+                button_address,
+                index,
+                gesture,
+            })
+        };
+
+        match self.buttons.remove(&(button_address, index)) {
+            Some(ButtonState::Pressed {
+                second_press: true, ..
+            }) => gesture(ButtonGesture::DoubleClick),
+            Some(ButtonState::Pressed { .. }) => {
+                if self.config.double_click_time == 0 {
+                    gesture(ButtonGesture::Click)
+                } else {
+                    self.buttons.insert(
+                        (button_address, index),
+                        ButtonState::Released { released_at: now },
+                    );
+
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Feeds a `ButtonPressedEvent` or `ButtonReleasedEvent` to the recogniser, ignoring any other
+    /// packet
+    pub fn handle_packet(&mut self, packet: &Packet, now: u32) -> Option<RecognizedGesture> {
+        if let Ok(event) = ButtonPressedEvent::try_from_packet(packet) {
+            self.press(event.button_address, event.index, now)
+        } else if let Ok(event) = ButtonReleasedEvent::try_from_packet(packet) {
+            self.release(event.button_address, event.index, now)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the gestures recognised since the last tick
+    pub fn tick(&mut self, now: u32) -> Vec<RecognizedGesture> {
+        let config = self.config;
+        let mut gestures = vec![];
+
+        self.buttons.retain(|(button_address, index), state| {
+            let mut push = |gesture| {
+                gestures.push(RecognizedGesture {
+                    button_address: *button_address,
+                    index: *index,
+                    gesture,
+                })
+            };
+
+            match *state {
+                ButtonState::Pressed {
+                    pressed_at,
+                    second_press,
+                } => {
+                    if now.wrapping_sub(pressed_at) >= config.long_press_time {
+                        if second_press {
+                            push(ButtonGesture::Click);
+                        }
+
+                        push(ButtonGesture::LongPress);
+
+                        *state = ButtonState::LongPressed {
+                            repeated_at: now,
+                            repeat: 0,
+                        };
+                    }
+
+                    true
+                }
+                ButtonState::LongPressed {
+                    repeated_at,
+                    repeat,
+                } => {
+                    if config.hold_repeat_time != 0
+                        && now.wrapping_sub(repeated_at) >= config.hold_repeat_time
+                    {
+                        let repeat = repeat.saturating_add(1);
+
+                        push(ButtonGesture::HoldRepeat(repeat));
+
+                        *state = ButtonState::LongPressed {
+                            repeated_at: repeated_at.wrapping_add(config.hold_repeat_time),
+                            repeat,
+                        };
+                    }
+
+                    true
+                }
+                ButtonState::Released { released_at } => {
+                    if now.wrapping_sub(released_at) >= config.double_click_time {
+                        push(ButtonGesture::Click);
+
+                        false
+                    } else {
+                        true
+                    }
+                }
+            }
+        });
+
+        gestures
+    }
+}
+
+impl Default for GestureRecognizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUTTON_ADDRESS: u16 = 0x0123;
+
+    fn gesture(index: u8, gesture: ButtonGesture) -> RecognizedGesture {
+        RecognizedGesture {
+            button_address: BUTTON_ADDRESS,
+            index,
+            gesture,
+        }
+    }
+
+    #[test]
+    fn click_test() {
+        let mut recognizer = GestureRecognizer::new();
+
+        recognizer.press(BUTTON_ADDRESS, 0, 0);
+        assert_eq!(recognizer.release(BUTTON_ADDRESS, 0, 100), None);
+        assert_eq!(recognizer.tick(399), vec![]);
+        assert_eq!(recognizer.tick(400), vec![gesture(0, ButtonGesture::Click)]);
+        assert_eq!(recognizer.tick(1000), vec![]);
+    }
+
+    #[test]
+    fn click_without_double_click_test() {
+        let mut recognizer = GestureRecognizer::with_config(GestureConfig {
+            double_click_time: 0,
+            ..GestureConfig::default()
+        });
+
+        recognizer.press(BUTTON_ADDRESS, 0, 0);
+        assert_eq!(
+            recognizer.release(BUTTON_ADDRESS, 0, 100),
+            Some(gesture(0, ButtonGesture::Click))
+        );
+    }
+
+    #[test]
+    fn double_click_test() {
+        let mut recognizer = GestureRecognizer::new();
+
+        recognizer.press(BUTTON_ADDRESS, 0, 0);
+        recognizer.release(BUTTON_ADDRESS, 0, 100);
+        assert_eq!(recognizer.tick(200), vec![]);
+        assert_eq!(recognizer.press(BUTTON_ADDRESS, 0, 300), None);
+        assert_eq!(
+            recognizer.release(BUTTON_ADDRESS, 0, 400),
+            Some(gesture(0, ButtonGesture::DoubleClick))
+        );
+        assert_eq!(recognizer.tick(1000), vec![]);
+    }
+
+    #[test]
+    fn late_second_press_test() {
+        let mut recognizer = GestureRecognizer::new();
+
+        recognizer.press(BUTTON_ADDRESS, 0, 0);
+        recognizer.release(BUTTON_ADDRESS, 0, 100);
+
+        // The second press comes after the double click time, but before the next tick
+        assert_eq!(
+            recognizer.press(BUTTON_ADDRESS, 0, 400),
+            Some(gesture(0, ButtonGesture::Click))
+        );
+        assert_eq!(recognizer.release(BUTTON_ADDRESS, 0, 450), None);
+        assert_eq!(recognizer.tick(750), vec![gesture(0, ButtonGesture::Click)]);
+    }
+
+    #[test]
+    fn long_press_test() {
+        let mut recognizer = GestureRecognizer::new();
+
+        recognizer.press(BUTTON_ADDRESS, 1, 0);
+        assert_eq!(recognizer.tick(599), vec![]);
+        assert_eq!(
+            recognizer.tick(600),
+            vec![gesture(1, ButtonGesture::LongPress)]
+        );
+        assert_eq!(recognizer.tick(799), vec![]);
+        assert_eq!(
+            recognizer.tick(800),
+            vec![gesture(1, ButtonGesture::HoldRepeat(1))]
+        );
+        assert_eq!(
+            recognizer.tick(1010),
+            vec![gesture(1, ButtonGesture::HoldRepeat(2))]
+        );
+        assert_eq!(
+            recognizer.tick(1200),
+            vec![gesture(1, ButtonGesture::HoldRepeat(3))]
+        );
+        assert_eq!(recognizer.release(BUTTON_ADDRESS, 1, 1300), None);
+        assert_eq!(recognizer.tick(2000), vec![]);
+    }
+
+    #[test]
+    fn handle_packet_test() {
+        let mut recognizer = GestureRecognizer::new();
+
+        let pressed = ButtonPressedEvent {
+            receiver_address: 0xffff,
+            button_address: BUTTON_ADDRESS,
+            index: 2,
+        };
+        let released = ButtonReleasedEvent {
+            receiver_address: 0xffff,
+            button_address: BUTTON_ADDRESS,
+            index: 2,
+        };
+
+        assert_eq!(recognizer.handle_packet(&pressed.to_packet(), 0), None);
+        assert_eq!(recognizer.handle_packet(&released.to_packet(), 50), None);
+        assert_eq!(recognizer.handle_packet(&pressed.to_packet(), 100), None);
+        assert_eq!(
+            recognizer.handle_packet(&released.to_packet(), 150),
+            Some(gesture(2, ButtonGesture::DoubleClick))
+        );
+        assert_eq!(
+            gesture(2, ButtonGesture::DoubleClick).to_packet(0xabab),
+            ButtonDoubleClickedEvent {
+                receiver_address: 0xabab,
+                button_address: BUTTON_ADDRESS,
+                index: 2,
+            }
+            .to_packet()
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use bxcan::{Can as BxCan, Instance};
+use bxcan::{Can as BxCan, Frame as BxFrame, Instance};
 use nb::block;
 
 use crate::frame::*;
@@ -14,6 +14,7 @@ pub enum CanError {
 pub struct Can<I: Instance> {
     can: BxCan<I>,
     packet_builder: Option<PacketBuilder>,
+    statistics: InterfaceStatistics,
 }
 
 impl<I: Instance> Can<I> {
@@ -21,51 +22,87 @@ impl<I: Instance> Can<I> {
         Can {
             can,
             packet_builder: None,
+            statistics: InterfaceStatistics::default(),
         }
     }
 }
 
-impl<I: Instance> Interface for Can<I> {
-    fn try_get_packet(&mut self) -> Result<Packet, InterfaceError> {
-        loop {
-            match self.can.receive() {
-                Ok(frame) => {
-                    let ross_frame = match Frame::from_bxcan_frame(frame) {
-                        Ok(frame) => frame,
-                        Err(err) => return Err(InterfaceError::FrameError(err)),
-                    };
+impl<I: Instance> Can<I> {
+    fn receive_packet(&mut self) -> Result<Packet, InterfaceError> {
+        let can = &mut self.can;
 
-                    if let Some(ref mut packet_builder) = self.packet_builder {
-                        if let Err(err) = packet_builder.add_frame(ross_frame) {
-                            self.packet_builder = None;
+        receive_packet(
+            || can.receive(),
+            &mut self.packet_builder,
+            &mut self.statistics,
+        )
+    }
+}
 
-                            return Err(InterfaceError::BuilderError(err));
-                        }
-                    } else {
-                        self.packet_builder = match PacketBuilder::new(ross_frame) {
-                            Ok(builder) => Some(builder),
-                            Err(err) => return Err(InterfaceError::BuilderError(err)),
-                        };
+/// Builds a packet out of the frames returned by `receive` until one is complete or no frame is
+/// left
+fn receive_packet<R: FnMut() -> nb::Result<BxFrame, ()>>(
+    mut receive: R,
+    packet_builder: &mut Option<PacketBuilder>,
+    statistics: &mut InterfaceStatistics,
+) -> Result<Packet, InterfaceError> {
+    loop {
+        match receive() {
+            Ok(frame) => {
+                statistics.record_frame_received();
+
+                let ross_frame = match Frame::from_bxcan_frame(frame) {
+                    Ok(frame) => frame,
+                    Err(err) => return Err(InterfaceError::FrameError(err)),
+                };
+
+                if let Some(ref mut builder) = packet_builder {
+                    if let Err(err) = builder.add_frame(ross_frame) {
+                        *packet_builder = None;
+
+                        return Err(InterfaceError::BuilderError(err));
                     }
+                } else {
+                    *packet_builder = match PacketBuilder::new(ross_frame) {
+                        Ok(builder) => Some(builder),
+                        Err(err) => return Err(InterfaceError::BuilderError(err)),
+                    };
+                }
 
-                    if let Some(ref mut packet_builder) = self.packet_builder {
-                        if packet_builder.frames_left() == 0 {
-                            let packet = match packet_builder.build() {
-                                Ok(packet) => packet,
-                                Err(err) => return Err(InterfaceError::BuilderError(err)),
-                            };
+                if let Some(ref mut builder) = packet_builder {
+                    if builder.frames_left() == 0 {
+                        let packet = match builder.build() {
+                            Ok(packet) => packet,
+                            Err(err) => return Err(InterfaceError::BuilderError(err)),
+                        };
 
-                            self.packet_builder = None;
+                        *packet_builder = None;
 
-                            return Ok(packet);
-                        }
+                        return Ok(packet);
                     }
                 }
-                Err(_) => break,
             }
+            // The receive FIFO overflowed and at least one frame was lost
+            Err(nb::Error::Other(())) => {
+                return Err(InterfaceError::CanError(CanError::BufferOverrun));
+            }
+            Err(nb::Error::WouldBlock) => break,
+        }
+    }
+
+    Err(InterfaceError::NoPacketReceived)
+}
+
+impl<I: Instance> Interface for Can<I> {
+    fn try_get_packet(&mut self) -> Result<Packet, InterfaceError> {
+        let result = self.receive_packet();
+
+        match result {
+            Ok(_) => self.statistics.record_packet_received(),
+            Err(ref err) => self.statistics.record_error(err),
         }
 
-        Err(InterfaceError::NoPacketReceived)
+        result
     }
 
     fn try_send_packet(&mut self, packet: &Packet) -> Result<(), InterfaceError> {
@@ -73,10 +110,69 @@ impl<I: Instance> Interface for Can<I> {
 
         for frame in frames {
             if let Ok(Some(_)) = block!(self.can.transmit(&frame.to_bxcan_frame())) {
-                return Err(InterfaceError::CanError(CanError::MailboxFull));
+                let err = InterfaceError::CanError(CanError::MailboxFull);
+                self.statistics.record_error(&err);
+
+                return Err(err);
             }
         }
 
+        self.statistics.record_packet_sent(packet);
+
         Ok(())
     }
+
+    fn statistics(&self) -> InterfaceStatistics {
+        self.statistics
+    }
+
+    fn reset_statistics(&mut self) {
+        self.statistics = InterfaceStatistics::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::collections::VecDeque;
+    use alloc::vec;
+
+    #[test]
+    fn receive_overrun_test() {
+        let packet = Packet {
+            is_error: false,
+            device_address: 0x0123,
+            data: vec![0x00, 0x01, 0x02],
+        };
+
+        let mut received: VecDeque<nb::Result<BxFrame, ()>> = VecDeque::new();
+        received.push_back(Err(nb::Error::Other(())));
+
+        for frame in packet.to_frames() {
+            received.push_back(Ok(frame.to_bxcan_frame()));
+        }
+
+        let mut receive = || received.pop_front().unwrap_or(Err(nb::Error::WouldBlock));
+        let mut packet_builder = None;
+        let mut statistics = InterfaceStatistics::default();
+
+        let err = receive_packet(&mut receive, &mut packet_builder, &mut statistics).unwrap_err();
+        assert!(matches!(
+            err,
+            InterfaceError::CanError(CanError::BufferOverrun)
+        ));
+
+        statistics.record_error(&err);
+        assert_eq!(statistics.overruns, 1);
+
+        assert_eq!(
+            receive_packet(&mut receive, &mut packet_builder, &mut statistics).unwrap(),
+            packet
+        );
+        assert!(matches!(
+            receive_packet(&mut receive, &mut packet_builder, &mut statistics),
+            Err(InterfaceError::NoPacketReceived)
+        ));
+    }
 }
@@ -1,28 +1,149 @@
+use core::ops::{Add, AddAssign};
+
 use crate::frame::FrameError;
 use crate::interface::can::CanError;
+use crate::interface::reliable::ReliableError;
 #[cfg(feature = "std")]
-use crate::interface::serial::SerialError;
+use crate::interface::stream::StreamError;
 use crate::interface::usart::UsartError;
 use crate::packet::Packet;
 use crate::packet::PacketBuilderError;
 
 pub mod can;
 #[cfg(feature = "std")]
+pub mod net;
+pub mod reliable;
+#[cfg(feature = "std")]
 pub mod serial;
+#[cfg(feature = "std")]
+pub mod stream;
 pub mod usart;
+pub mod usart_decoder;
 
 #[derive(Debug)]
 pub enum InterfaceError {
     CanError(CanError),
     UsartError(UsartError),
     #[cfg(feature = "std")]
-    SerialError(SerialError),
+    StreamError(StreamError),
     BuilderError(PacketBuilderError),
     FrameError(FrameError),
+    ReliableError(ReliableError),
     NoPacketReceived,
 }
 
 pub trait Interface {
     fn try_get_packet(&mut self) -> Result<Packet, InterfaceError>;
     fn try_send_packet(&mut self, packet: &Packet) -> Result<(), InterfaceError>;
+
+    /// Returns the traffic and error counters of the interface
+    ///
+    /// Interfaces that don't keep counters report all of them as zero.
+    fn statistics(&self) -> InterfaceStatistics {
+        InterfaceStatistics::default()
+    }
+
+    fn reset_statistics(&mut self) {}
+}
+
+/// Traffic and error counters of an interface, all of which wrap around
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct InterfaceStatistics {
+    pub frames_received: u32,
+    pub frames_sent: u32,
+    pub packets_received: u32,
+    pub packets_sent: u32,
+    /// Received frames whose COBS encoding was corrupted
+    pub cobs_errors: u32,
+    /// Received frames that were dropped for any other reason
+    pub frame_errors: u32,
+    /// Received frames that were not the next frame of the packet being built
+    pub out_of_order_frames: u32,
+    /// Partially received packets that were dropped
+    pub builder_resets: u32,
+    /// Sent packets that were never acknowledged
+    pub timeouts: u32,
+    /// Packets that could not be sent because all transmit mailboxes were full
+    pub mailbox_full: u32,
+    /// Times received data was dropped because a receive buffer was full
+    pub overruns: u32,
+    /// Read and write errors of the underlying transport
+    pub io_errors: u32,
+}
+
+impl InterfaceStatistics {
+    /// Counts a received frame
+    pub fn record_frame_received(&mut self) {
+        self.frames_received = self.frames_received.wrapping_add(1);
+    }
+
+    /// Counts a received packet
+    pub fn record_packet_received(&mut self) {
+        self.packets_received = self.packets_received.wrapping_add(1);
+    }
+
+    /// Counts a sent packet and its frames
+    pub fn record_packet_sent(&mut self, packet: &Packet) {
+        self.frames_sent = self.frames_sent.wrapping_add(packet.frame_count() as u32);
+        self.packets_sent = self.packets_sent.wrapping_add(1);
+    }
+
+    /// Counts a dropped partially received packet
+    pub fn record_builder_reset(&mut self) {
+        self.builder_resets = self.builder_resets.wrapping_add(1);
+    }
+
+    /// Counts an error returned by an interface
+    pub fn record_error(&mut self, err: &InterfaceError) {
+        let counter = match err {
+            InterfaceError::CanError(CanError::BufferOverrun) => &mut self.overruns,
+            InterfaceError::CanError(CanError::MailboxFull) => &mut self.mailbox_full,
+            InterfaceError::UsartError(UsartError::BufferOverrun) => &mut self.overruns,
+            InterfaceError::UsartError(UsartError::ReadError) => &mut self.io_errors,
+            #[cfg(feature = "std")]
+            InterfaceError::StreamError(_) => &mut self.io_errors,
+            InterfaceError::BuilderError(PacketBuilderError::OutOfOrder) => {
+                self.builder_resets = self.builder_resets.wrapping_add(1);
+
+                &mut self.out_of_order_frames
+            }
+            InterfaceError::BuilderError(_) => &mut self.builder_resets,
+            InterfaceError::FrameError(FrameError::CobsError) => &mut self.cobs_errors,
+            InterfaceError::FrameError(_) => &mut self.frame_errors,
+            InterfaceError::ReliableError(ReliableError::DeliveryFailed { .. }) => {
+                &mut self.timeouts
+            }
+            InterfaceError::ReliableError(_) | InterfaceError::NoPacketReceived => return,
+        };
+
+        *counter = counter.wrapping_add(1);
+    }
+}
+
+impl Add for InterfaceStatistics {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+impl AddAssign for InterfaceStatistics {
+    fn add_assign(&mut self, other: Self) {
+        self.frames_received = self.frames_received.wrapping_add(other.frames_received);
+        self.frames_sent = self.frames_sent.wrapping_add(other.frames_sent);
+        self.packets_received = self.packets_received.wrapping_add(other.packets_received);
+        self.packets_sent = self.packets_sent.wrapping_add(other.packets_sent);
+        self.cobs_errors = self.cobs_errors.wrapping_add(other.cobs_errors);
+        self.frame_errors = self.frame_errors.wrapping_add(other.frame_errors);
+        self.out_of_order_frames = self
+            .out_of_order_frames
+            .wrapping_add(other.out_of_order_frames);
+        self.builder_resets = self.builder_resets.wrapping_add(other.builder_resets);
+        self.timeouts = self.timeouts.wrapping_add(other.timeouts);
+        self.mailbox_full = self.mailbox_full.wrapping_add(other.mailbox_full);
+        self.overruns = self.overruns.wrapping_add(other.overruns);
+        self.io_errors = self.io_errors.wrapping_add(other.io_errors);
+    }
 }
@@ -0,0 +1,694 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::io::{ErrorKind, Result as IOResult};
+use std::net::{
+    Ipv4Addr, SocketAddr, SocketAddrV4, TcpListener, TcpStream, ToSocketAddrs, UdpSocket,
+};
+use std::time::Duration;
+
+use socket2::{Domain, Socket, Type};
+
+use crate::interface::stream::{StreamError, StreamInterface, DEFAULT_WRITE_TIMEOUT};
+use crate::interface::usart_decoder::{UsartDecoder, UsartFraming};
+use crate::interface::*;
+use crate::packet::*;
+
+/// Largest datagram accepted by `Udp`
+const MAX_DATAGRAM_SIZE: usize = 2048;
+/// Most senders a `Udp` keeps a decoder for
+pub const MAX_UDP_SENDERS: usize = 32;
+
+/// TCP connection to a `TcpServer` (usually running on a gateway)
+pub struct TcpClient {
+    stream: StreamInterface<TcpStream>,
+}
+
+impl TcpClient {
+    pub fn connect<A: ToSocketAddrs>(addr: A) -> IOResult<Self> {
+        Self::connect_with_framing(addr, UsartFraming::LengthPrefixed)
+    }
+
+    pub fn connect_with_framing<A: ToSocketAddrs>(
+        addr: A,
+        framing: UsartFraming,
+    ) -> IOResult<Self> {
+        let stream = TcpStream::connect(addr)?;
+        stream.set_nodelay(true)?;
+        stream.set_nonblocking(true)?;
+
+        Ok(TcpClient {
+            stream: StreamInterface::with_framing(stream, framing),
+        })
+    }
+
+    pub fn local_addr(&self) -> IOResult<SocketAddr> {
+        self.stream.get_ref().local_addr()
+    }
+
+    pub fn peer_addr(&self) -> IOResult<SocketAddr> {
+        self.stream.get_ref().peer_addr()
+    }
+
+    /// Sets how long a send may wait for the server to read before it fails
+    pub fn set_write_timeout(&mut self, write_timeout: Duration) {
+        self.stream.set_write_timeout(write_timeout);
+    }
+}
+
+impl Interface for TcpClient {
+    fn try_get_packet(&mut self) -> Result<Packet, InterfaceError> {
+        self.stream.try_get_packet()
+    }
+
+    fn try_send_packet(&mut self, packet: &Packet) -> Result<(), InterfaceError> {
+        self.stream.try_send_packet(packet)
+    }
+
+    fn statistics(&self) -> InterfaceStatistics {
+        self.stream.statistics()
+    }
+
+    fn reset_statistics(&mut self) {
+        self.stream.reset_statistics()
+    }
+}
+
+/// TCP server accepting any number of `TcpClient`s
+///
+/// All connected clients are treated as a single bus segment: sent packets go to every client and
+/// packets received from one client are relayed to all others. Clients that disconnect, fail or
+/// stop reading for longer than the write timeout are dropped without affecting the rest. The
+/// statistics add up the traffic of all clients, including the ones that have disconnected since.
+pub struct TcpServer {
+    listener: TcpListener,
+    framing: UsartFraming,
+    clients: Vec<StreamInterface<TcpStream>>,
+    next_client: usize,
+    write_timeout: Duration,
+    dropped_statistics: InterfaceStatistics,
+}
+
+impl TcpServer {
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> IOResult<Self> {
+        Self::bind_with_framing(addr, UsartFraming::LengthPrefixed)
+    }
+
+    pub fn bind_with_framing<A: ToSocketAddrs>(addr: A, framing: UsartFraming) -> IOResult<Self> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+
+        Ok(TcpServer {
+            listener,
+            framing,
+            clients: vec![],
+            next_client: 0,
+            write_timeout: DEFAULT_WRITE_TIMEOUT,
+            dropped_statistics: InterfaceStatistics::default(),
+        })
+    }
+
+    pub fn local_addr(&self) -> IOResult<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Sets how long a send may wait for a client to read before the client is dropped
+    pub fn set_write_timeout(&mut self, write_timeout: Duration) {
+        self.write_timeout = write_timeout;
+
+        for client in self.clients.iter_mut() {
+            client.set_write_timeout(write_timeout);
+        }
+    }
+
+    /// Accepts all pending connections, returning the number of connected clients
+    pub fn accept_clients(&mut self) -> usize {
+        loop {
+            match self.listener.accept() {
+                Ok((stream, _)) => {
+                    if stream.set_nodelay(true).is_ok() && stream.set_nonblocking(true).is_ok() {
+                        let mut client = StreamInterface::with_framing(stream, self.framing);
+                        client.set_write_timeout(self.write_timeout);
+
+                        self.clients.push(client);
+                    }
+                }
+                Err(ref err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(_) => break,
+            }
+        }
+
+        self.clients.len()
+    }
+
+    pub fn client_count(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Sends a packet to every client except the one at `skip_index`
+    fn send_to_clients(&mut self, packet: &Packet, skip_index: Option<usize>) {
+        let mut index = 0;
+        let dropped_statistics = &mut self.dropped_statistics;
+
+        self.clients.retain_mut(|client| {
+            let keep = Some(index) == skip_index || client.try_send_packet(packet).is_ok();
+            index += 1;
+
+            if !keep {
+                *dropped_statistics += client.statistics();
+            }
+
+            keep
+        });
+    }
+}
+
+impl Interface for TcpServer {
+    fn try_get_packet(&mut self) -> Result<Packet, InterfaceError> {
+        self.accept_clients();
+
+        let mut checked = 0;
+
+        while checked < self.clients.len() {
+            let index = self.next_client % self.clients.len();
+
+            match self.clients[index].try_get_packet() {
+                Ok(packet) => {
+                    self.next_client = index + 1;
+                    self.send_to_clients(&packet, Some(index));
+
+                    return Ok(packet);
+                }
+                Err(InterfaceError::NoPacketReceived) => {
+                    self.next_client = index + 1;
+                    checked += 1;
+                }
+                Err(InterfaceError::StreamError(_)) => {
+                    self.dropped_statistics += self.clients.remove(index).statistics();
+                }
+                Err(err) => {
+                    self.next_client = index + 1;
+
+                    return Err(err);
+                }
+            }
+        }
+
+        Err(InterfaceError::NoPacketReceived)
+    }
+
+    fn try_send_packet(&mut self, packet: &Packet) -> Result<(), InterfaceError> {
+        self.accept_clients();
+        self.send_to_clients(packet, None);
+
+        Ok(())
+    }
+
+    fn statistics(&self) -> InterfaceStatistics {
+        self.clients
+            .iter()
+            .fold(self.dropped_statistics, |statistics, client| {
+                statistics + client.statistics()
+            })
+    }
+
+    fn reset_statistics(&mut self) {
+        for client in self.clients.iter_mut() {
+            client.reset_statistics();
+        }
+
+        self.dropped_statistics = InterfaceStatistics::default();
+    }
+}
+
+/// UDP transport sending every packet as a single datagram
+///
+/// Datagrams are sent to a fixed peer, which may be a multicast group. Every sender gets its own
+/// decoder, so interleaved packets from different hosts don't corrupt each other. Only the
+/// `MAX_UDP_SENDERS` most recently heard senders keep their decoder, so senders rotating their
+/// address can't exhaust memory.
+pub struct Udp {
+    socket: UdpSocket,
+    /// Socket sending the datagrams, if they are not sent from `socket`
+    send_socket: Option<UdpSocket>,
+    peer: SocketAddr,
+    framing: UsartFraming,
+    /// Source address of the datagrams sent by this transport, if they may be looped back to it
+    own_addr: Option<SocketAddr>,
+    /// Decoder of every sender, along with the number of the last datagram received from it
+    decoders: BTreeMap<SocketAddr, (u64, UsartDecoder)>,
+    datagram_count: u64,
+    received_packets: VecDeque<Packet>,
+    statistics: InterfaceStatistics,
+}
+
+impl Udp {
+    /// Creates a unicast transport bound to `addr` and sending to `peer`
+    pub fn bind<A: ToSocketAddrs>(addr: A, peer: SocketAddr) -> IOResult<Self> {
+        Self::bind_with_framing(addr, peer, UsartFraming::LengthPrefixed)
+    }
+
+    pub fn bind_with_framing<A: ToSocketAddrs>(
+        addr: A,
+        peer: SocketAddr,
+        framing: UsartFraming,
+    ) -> IOResult<Self> {
+        let socket = UdpSocket::bind(addr)?;
+        socket.set_nonblocking(true)?;
+
+        Ok(Self::from_socket(socket, peer, framing))
+    }
+
+    /// Creates a transport that joins the IPv4 multicast `group` on the given network interface
+    ///
+    /// The group port is bound with address reuse, so several programs on the same host can join
+    /// the same group. Datagrams are sent out of `interface` (chosen by the routing table if it is
+    /// unspecified) from a socket of their own on an ephemeral port. Multicast loopback is enabled,
+    /// so other programs on the same host receive the sent packets as well. Packets looped back to
+    /// this transport itself are dropped.
+    pub fn multicast_v4(
+        group: Ipv4Addr,
+        port: u16,
+        interface: Ipv4Addr,
+        framing: UsartFraming,
+    ) -> IOResult<Self> {
+        let socket = Socket::new(Domain::IPV4, Type::DGRAM, None)?;
+        socket.set_reuse_address(true)?;
+        #[cfg(all(
+            unix,
+            not(any(target_os = "solaris", target_os = "illumos", target_os = "cygwin"))
+        ))]
+        socket.set_reuse_port(true)?;
+        socket.bind(&SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, port)).into())?;
+        socket.join_multicast_v4(&group, &interface)?;
+        socket.set_nonblocking(true)?;
+
+        let send_socket = Socket::new(Domain::IPV4, Type::DGRAM, None)?;
+        send_socket.bind(&SocketAddr::V4(SocketAddrV4::new(interface, 0)).into())?;
+        send_socket.set_multicast_if_v4(&interface)?;
+        send_socket.set_multicast_loop_v4(true)?;
+        send_socket.set_nonblocking(true)?;
+
+        let send_socket: UdpSocket = send_socket.into();
+        let send_port = send_socket.local_addr()?.port();
+        let peer = SocketAddr::V4(SocketAddrV4::new(group, port));
+
+        let own_addr = if interface.is_unspecified() {
+            Self::source_addr(peer, send_port)?
+        } else {
+            SocketAddr::V4(SocketAddrV4::new(interface, send_port))
+        };
+
+        let mut udp = Self::from_socket(socket.into(), peer, framing);
+        udp.send_socket = Some(send_socket);
+        udp.own_addr = Some(own_addr);
+
+        Ok(udp)
+    }
+
+    /// Enables or disables receiving the sent multicast packets on this host
+    pub fn set_multicast_loop_v4(&self, multicast_loop: bool) -> IOResult<()> {
+        self.sending_socket().set_multicast_loop_v4(multicast_loop)
+    }
+
+    pub fn multicast_loop_v4(&self) -> IOResult<bool> {
+        self.sending_socket().multicast_loop_v4()
+    }
+
+    /// Returns the source address of datagrams sent from `port` to `peer`
+    fn source_addr(peer: SocketAddr, port: u16) -> IOResult<SocketAddr> {
+        let socket = UdpSocket::bind(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0))?;
+        socket.connect(peer)?;
+
+        Ok(SocketAddr::new(socket.local_addr()?.ip(), port))
+    }
+
+    fn sending_socket(&self) -> &UdpSocket {
+        self.send_socket.as_ref().unwrap_or(&self.socket)
+    }
+
+    fn from_socket(socket: UdpSocket, peer: SocketAddr, framing: UsartFraming) -> Self {
+        Udp {
+            socket,
+            send_socket: None,
+            peer,
+            framing,
+            own_addr: None,
+            decoders: BTreeMap::new(),
+            datagram_count: 0,
+            received_packets: VecDeque::new(),
+            statistics: InterfaceStatistics::default(),
+        }
+    }
+
+    pub fn local_addr(&self) -> IOResult<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    /// Returns the decoder of a sender, forgetting the least recently heard sender if needed
+    fn decoder(&mut self, source: SocketAddr) -> &mut UsartDecoder {
+        self.datagram_count += 1;
+
+        if !self.decoders.contains_key(&source) && self.decoders.len() >= MAX_UDP_SENDERS {
+            let least_recent = self
+                .decoders
+                .iter()
+                .min_by_key(|(_, (last_datagram, _))| *last_datagram)
+                .map(|(source, _)| *source);
+
+            if let Some((_, decoder)) =
+                least_recent.and_then(|source| self.decoders.remove(&source))
+            {
+                self.statistics += decoder.statistics();
+            }
+        }
+
+        let framing = self.framing;
+        let (last_datagram, decoder) = self
+            .decoders
+            .entry(source)
+            .or_insert_with(|| (0, UsartDecoder::with_framing(framing)));
+        *last_datagram = self.datagram_count;
+
+        decoder
+    }
+}
+
+impl Interface for Udp {
+    fn try_get_packet(&mut self) -> Result<Packet, InterfaceError> {
+        let mut buf = [0x00; MAX_DATAGRAM_SIZE];
+
+        while self.received_packets.is_empty() {
+            let (length, source) = match self.socket.recv_from(&mut buf) {
+                Ok(received) => received,
+                Err(err) => match err.kind() {
+                    ErrorKind::Interrupted => continue,
+                    ErrorKind::TimedOut | ErrorKind::WouldBlock => {
+                        return Err(InterfaceError::NoPacketReceived)
+                    }
+                    _ => {
+                        let err = InterfaceError::StreamError(StreamError::ReadError(err));
+                        self.statistics.record_error(&err);
+
+                        return Err(err);
+                    }
+                },
+            };
+
+            if Some(source) == self.own_addr {
+                continue;
+            }
+
+            let mut packets = vec![];
+            let mut error = None;
+            let decoder = self.decoder(source);
+
+            for byte in buf[..length].iter() {
+                match decoder.decode_byte(*byte) {
+                    Ok(Some(packet)) => packets.push(packet),
+                    Ok(None) => {}
+                    Err(err) => error = Some(err),
+                }
+            }
+
+            self.received_packets.extend(packets);
+
+            if let Some(err) = error {
+                if self.received_packets.is_empty() {
+                    return Err(err);
+                }
+            }
+        }
+
+        match self.received_packets.pop_front() {
+            Some(packet) => Ok(packet),
+            None => Err(InterfaceError::NoPacketReceived),
+        }
+    }
+
+    fn try_send_packet(&mut self, packet: &Packet) -> Result<(), InterfaceError> {
+        let buf = self.framing.encode_packet(packet);
+
+        match self.sending_socket().send_to(&buf, self.peer) {
+            Ok(_) => {
+                self.statistics.record_packet_sent(packet);
+
+                Ok(())
+            }
+            Err(err) => {
+                let err = InterfaceError::StreamError(StreamError::WriteError(err));
+                self.statistics.record_error(&err);
+
+                Err(err)
+            }
+        }
+    }
+
+    fn statistics(&self) -> InterfaceStatistics {
+        self.decoders
+            .values()
+            .fold(self.statistics, |statistics, (_, decoder)| {
+                statistics + decoder.statistics()
+            })
+    }
+
+    fn reset_statistics(&mut self) {
+        for (_, decoder) in self.decoders.values_mut() {
+            decoder.reset_statistics();
+        }
+
+        self.statistics = InterfaceStatistics::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::thread::sleep;
+    use std::time::{Duration, Instant};
+
+    fn packet(data_len: u8) -> Packet {
+        Packet {
+            is_error: false,
+            device_address: 0xabab,
+            data: (0..data_len).collect(),
+        }
+    }
+
+    fn receive<I: Interface>(interface: &mut I) -> Packet {
+        let start = Instant::now();
+
+        loop {
+            match interface.try_get_packet() {
+                Ok(packet) => return packet,
+                Err(InterfaceError::NoPacketReceived) => {
+                    assert!(start.elapsed() < Duration::from_secs(5));
+                    sleep(Duration::from_millis(1));
+                }
+                Err(err) => panic!("{:?}", err),
+            }
+        }
+    }
+
+    #[test]
+    fn tcp_test() {
+        let mut server = TcpServer::bind("127.0.0.1:0").unwrap();
+        let addr = server.local_addr().unwrap();
+
+        let mut first_client = TcpClient::connect(addr).unwrap();
+        let mut second_client = TcpClient::connect(addr).unwrap();
+
+        let start = Instant::now();
+        while server.accept_clients() != 2 {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            sleep(Duration::from_millis(1));
+        }
+
+        server.try_send_packet(&packet(20)).unwrap();
+        assert_eq!(receive(&mut first_client), packet(20));
+        assert_eq!(receive(&mut second_client), packet(20));
+
+        first_client.try_send_packet(&packet(3)).unwrap();
+        assert_eq!(receive(&mut server), packet(3));
+        assert_eq!(receive(&mut second_client), packet(3));
+    }
+
+    #[test]
+    fn tcp_client_disconnect_test() {
+        let mut server = TcpServer::bind("127.0.0.1:0").unwrap();
+        let addr = server.local_addr().unwrap();
+
+        let client = TcpClient::connect(addr).unwrap();
+
+        let start = Instant::now();
+        while server.accept_clients() != 1 {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            sleep(Duration::from_millis(1));
+        }
+
+        drop(client);
+
+        let start = Instant::now();
+        while server.client_count() != 0 {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            assert!(matches!(
+                server.try_get_packet(),
+                Err(InterfaceError::NoPacketReceived)
+            ));
+            sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn tcp_stalled_client_test() {
+        let mut server = TcpServer::bind("127.0.0.1:0").unwrap();
+        server.set_write_timeout(Duration::from_millis(50));
+        let addr = server.local_addr().unwrap();
+
+        // Never reads anything, so the socket buffers eventually fill up
+        let _client = TcpClient::connect(addr).unwrap();
+
+        let start = Instant::now();
+        while server.accept_clients() != 1 {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            sleep(Duration::from_millis(1));
+        }
+
+        let start = Instant::now();
+        while server.client_count() != 0 {
+            assert!(start.elapsed() < Duration::from_secs(10));
+            server.try_send_packet(&packet(250)).unwrap();
+        }
+    }
+
+    #[test]
+    fn udp_test() {
+        let first_socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let second_socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let first_addr = first_socket.local_addr().unwrap();
+        let second_addr = second_socket.local_addr().unwrap();
+        drop(first_socket);
+        drop(second_socket);
+
+        let mut first =
+            Udp::bind_with_framing(first_addr, second_addr, UsartFraming::ZeroDelimited).unwrap();
+        let mut second =
+            Udp::bind_with_framing(second_addr, first_addr, UsartFraming::ZeroDelimited).unwrap();
+
+        first.try_send_packet(&packet(20)).unwrap();
+        first.try_send_packet(&packet(3)).unwrap();
+        assert_eq!(receive(&mut second), packet(20));
+        assert_eq!(receive(&mut second), packet(3));
+
+        second.try_send_packet(&packet(5)).unwrap();
+        assert_eq!(receive(&mut first), packet(5));
+    }
+
+    #[test]
+    fn udp_sender_limit_test() {
+        let mut receiver = Udp::bind("127.0.0.1:0", "127.0.0.1:9".parse().unwrap()).unwrap();
+        let addr = receiver.local_addr().unwrap();
+
+        let senders: Vec<Udp> = (0..MAX_UDP_SENDERS + 8)
+            .map(|_| Udp::bind("127.0.0.1:0", addr).unwrap())
+            .collect();
+
+        for sender in senders.iter() {
+            receiver.decoder(sender.local_addr().unwrap());
+        }
+
+        assert_eq!(receiver.decoders.len(), MAX_UDP_SENDERS);
+
+        // The most recently heard senders are kept
+        let last_addr = senders.last().unwrap().local_addr().unwrap();
+        assert!(receiver.decoders.contains_key(&last_addr));
+
+        for mut sender in senders.into_iter() {
+            sender.try_send_packet(&packet(3)).unwrap();
+            assert_eq!(receive(&mut receiver), packet(3));
+        }
+
+        assert_eq!(receiver.decoders.len(), MAX_UDP_SENDERS);
+        assert_eq!(
+            receiver.statistics().packets_received,
+            MAX_UDP_SENDERS as u32 + 8
+        );
+    }
+
+    #[test]
+    fn udp_multicast_test() {
+        let group = Ipv4Addr::new(239, 255, 82, 83);
+        let port = UdpSocket::bind("0.0.0.0:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let group_addr = SocketAddr::V4(SocketAddrV4::new(group, port));
+
+        let mut member = Udp::multicast_v4(
+            group,
+            port,
+            Ipv4Addr::UNSPECIFIED,
+            UsartFraming::ZeroDelimited,
+        )
+        .unwrap();
+        let mut sender =
+            Udp::bind_with_framing("0.0.0.0:0", group_addr, UsartFraming::ZeroDelimited).unwrap();
+
+        assert!(member.multicast_loop_v4().unwrap());
+
+        // The member hears other programs on this host, but not its own packets
+        member.try_send_packet(&packet(4)).unwrap();
+        sender.try_send_packet(&packet(20)).unwrap();
+        assert_eq!(receive(&mut member), packet(20));
+
+        sleep(Duration::from_millis(50));
+        assert!(matches!(
+            member.try_get_packet(),
+            Err(InterfaceError::NoPacketReceived)
+        ));
+
+        member.set_multicast_loop_v4(false).unwrap();
+        assert!(!member.multicast_loop_v4().unwrap());
+    }
+
+    #[test]
+    fn udp_multicast_same_port_test() {
+        let group = Ipv4Addr::new(239, 255, 82, 84);
+        let port = UdpSocket::bind("0.0.0.0:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        let mut first = Udp::multicast_v4(
+            group,
+            port,
+            Ipv4Addr::UNSPECIFIED,
+            UsartFraming::ZeroDelimited,
+        )
+        .unwrap();
+        let mut second = Udp::multicast_v4(
+            group,
+            port,
+            Ipv4Addr::UNSPECIFIED,
+            UsartFraming::ZeroDelimited,
+        )
+        .unwrap();
+
+        first.try_send_packet(&packet(20)).unwrap();
+        assert_eq!(receive(&mut second), packet(20));
+
+        second.try_send_packet(&packet(3)).unwrap();
+        assert_eq!(receive(&mut first), packet(3));
+
+        sleep(Duration::from_millis(50));
+        assert!(matches!(
+            first.try_get_packet(),
+            Err(InterfaceError::NoPacketReceived)
+        ));
+        assert!(matches!(
+            second.try_get_packet(),
+            Err(InterfaceError::NoPacketReceived)
+        ));
+    }
+}
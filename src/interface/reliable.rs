@@ -0,0 +1,678 @@
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+use crate::convert_packet::ConvertPacket;
+use crate::event::general::{AckDetails, AckStatus, ExtendedAckEvent, SequencedEvent};
+use crate::interface::*;
+use crate::packet::Packet;
+use crate::protocol::{is_group_address, BROADCAST_ADDRESS};
+
+/// Default time to wait for an acknowledgement before retransmitting, in milliseconds
+pub const DEFAULT_RETRANSMIT_TIMEOUT: u32 = 100;
+/// Default amount of retransmissions before a packet is given up on
+pub const DEFAULT_MAX_RETRIES: u8 = 3;
+/// Amount of packets that may wait for an acknowledgement at the same time
+pub const MAX_PENDING_PACKETS: usize = 16;
+
+/// Amount of recent sequence numbers remembered per transmitter for duplicate suppression
+const SEQUENCE_WINDOW_SIZE: u16 = 32;
+
+#[derive(Debug, PartialEq)]
+pub enum ReliableError {
+    /// Too many packets are waiting for an acknowledgement
+    QueueFull,
+    /// The packet was not acknowledged after all retransmissions
+    DeliveryFailed {
+        receiver_address: u16,
+        sequence: u16,
+    },
+    /// The receiver acknowledged the packet, but did not accept it
+    Rejected {
+        receiver_address: u16,
+        sequence: u16,
+        status: AckStatus,
+    },
+}
+
+struct PendingPacket {
+    receiver_address: u16,
+    event_code: u16,
+    sequence: u16,
+    packet: Packet,
+    sent_at: u32,
+    retries: u8,
+}
+
+/// Event code of the packet data in a `SequencedEvent`, or `0x0000` if it is too short to have one
+fn event_code(data: &[u8]) -> u16 {
+    if data.len() >= 2 {
+        u16::from_be_bytes(data[0..=1].try_into().unwrap())
+    } else {
+        0x0000
+    }
+}
+
+/// Recently received sequence numbers of a single transmitter
+struct SequenceWindow {
+    session: u8,
+    last_sequence: u16,
+    /// Bit `n` is set if `last_sequence - n` has been received
+    received: u32,
+}
+
+impl SequenceWindow {
+    fn new(session: u8, sequence: u16) -> Self {
+        SequenceWindow {
+            session,
+            last_sequence: sequence,
+            received: 0x0000_0001,
+        }
+    }
+
+    /// Marks a sequence number as received, returning `false` if it already was
+    fn receive(&mut self, session: u8, sequence: u16) -> bool {
+        if session != self.session {
+            // The transmitter restarted and counts from the start again
+            *self = Self::new(session, sequence);
+
+            return true;
+        }
+
+        let ahead = sequence.wrapping_sub(self.last_sequence);
+        let behind = self.last_sequence.wrapping_sub(sequence);
+
+        if ahead != 0 && ahead < SEQUENCE_WINDOW_SIZE {
+            self.received = (self.received << ahead) | 0x0000_0001;
+            self.last_sequence = sequence;
+
+            true
+        } else if behind < SEQUENCE_WINDOW_SIZE {
+            let was_received = self.received & (1 << behind) != 0;
+            self.received |= 1 << behind;
+
+            !was_received
+        } else {
+            // Too far from the window to tell whether it is a duplicate
+            *self = Self::new(session, sequence);
+
+            true
+        }
+    }
+}
+
+/// Interface adding acknowledged delivery on top of another interface
+///
+/// Unicast packets sent through it are wrapped in a `SequencedEvent` with a per-receiver sequence
+/// number and retransmitted until the receiver acknowledges them with an `ExtendedAckEvent`.
+/// Received sequenced packets addressed to this device are acknowledged automatically, unwrapped
+/// and delivered once, even if they are received several times. Broadcasts, group packets,
+/// acknowledgements and error packets are passed through unchanged.
+///
+/// Both sides have to use a `ReliableInterface`. `session` has to be different every time the
+/// device starts, for example a boot counter kept in storage or a random number, so receivers do
+/// not drop the packets of a restarted device as duplicates. `clock` returns the current time in
+/// milliseconds and may wrap around. Retransmissions happen while receiving, so `try_get_packet`
+/// has to be called regularly (which `Protocol::tick` does).
+pub struct ReliableInterface<I: Interface, C: Fn() -> u32> {
+    device_address: u16,
+    session: u8,
+    interface: I,
+    clock: C,
+    retransmit_timeout: u32,
+    max_retries: u8,
+    next_sequences: BTreeMap<u16, u16>,
+    pending_packets: Vec<PendingPacket>,
+    sequence_windows: BTreeMap<u16, SequenceWindow>,
+    timeouts: u32,
+}
+
+impl<I: Interface, C: Fn() -> u32> ReliableInterface<I, C> {
+    pub fn new(device_address: u16, session: u8, interface: I, clock: C) -> Self {
+        Self::with_retransmission(
+            device_address,
+            session,
+            interface,
+            clock,
+            DEFAULT_RETRANSMIT_TIMEOUT,
+            DEFAULT_MAX_RETRIES,
+        )
+    }
+
+    pub fn with_retransmission(
+        device_address: u16,
+        session: u8,
+        interface: I,
+        clock: C,
+        retransmit_timeout: u32,
+        max_retries: u8,
+    ) -> Self {
+        ReliableInterface {
+            device_address,
+            session,
+            interface,
+            clock,
+            retransmit_timeout,
+            max_retries,
+            next_sequences: BTreeMap::new(),
+            pending_packets: vec![],
+            sequence_windows: BTreeMap::new(),
+            timeouts: 0,
+        }
+    }
+
+    pub fn get_ref(&self) -> &I {
+        &self.interface
+    }
+
+    pub fn get_mut(&mut self) -> &mut I {
+        &mut self.interface
+    }
+
+    /// Returns the amount of packets that have not been acknowledged yet
+    pub fn pending_count(&self) -> usize {
+        self.pending_packets.len()
+    }
+
+    fn retransmit(&mut self) -> Result<(), InterfaceError> {
+        let now = (self.clock)();
+        let mut index = 0;
+
+        while index < self.pending_packets.len() {
+            let pending = &mut self.pending_packets[index];
+
+            if now.wrapping_sub(pending.sent_at) < self.retransmit_timeout {
+                index += 1;
+                continue;
+            }
+
+            if pending.retries >= self.max_retries {
+                let pending = self.pending_packets.remove(index);
+                self.timeouts = self.timeouts.wrapping_add(1);
+
+                return Err(InterfaceError::ReliableError(
+                    ReliableError::DeliveryFailed {
+                        receiver_address: pending.receiver_address,
+                        sequence: pending.sequence,
+                    },
+                ));
+            }
+
+            pending.sent_at = now;
+            pending.retries += 1;
+
+            self.interface.try_send_packet(&pending.packet)?;
+            index += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the index of the pending packet an acknowledgement belongs to
+    fn find_pending(&self, ack: &ExtendedAckEvent) -> Option<usize> {
+        let details = ack.details?;
+
+        self.pending_packets.iter().position(|pending| {
+            pending.receiver_address == ack.transmitter_address
+                && pending.sequence == details.sequence
+                && pending.event_code == details.event_code
+        })
+    }
+
+    fn handle_ack(&mut self, index: usize, status: AckStatus) -> Result<(), InterfaceError> {
+        match status {
+            AckStatus::Ok => {
+                self.pending_packets.remove(index);
+
+                Ok(())
+            }
+            // The receiver will get the retransmission once it's no longer busy
+            AckStatus::Busy => Ok(()),
+            status => {
+                let pending = self.pending_packets.remove(index);
+
+                Err(InterfaceError::ReliableError(ReliableError::Rejected {
+                    receiver_address: pending.receiver_address,
+                    sequence: pending.sequence,
+                    status,
+                }))
+            }
+        }
+    }
+
+    /// Acknowledges a sequenced packet, returning `true` if it has not been received before
+    fn handle_sequenced(&mut self, event: &SequencedEvent) -> Result<bool, InterfaceError> {
+        let ack = ExtendedAckEvent {
+            receiver_address: event.transmitter_address,
+            transmitter_address: self.device_address,
+            details: Some(AckDetails {
+                event_code: event_code(&event.data),
+                sequence: event.sequence,
+                status: AckStatus::Ok,
+            }),
+        };
+
+        self.interface.try_send_packet(&ack.to_packet())?;
+
+        let is_new = match self.sequence_windows.get_mut(&event.transmitter_address) {
+            Some(window) => window.receive(event.session, event.sequence),
+            None => {
+                self.sequence_windows.insert(
+                    event.transmitter_address,
+                    SequenceWindow::new(event.session, event.sequence),
+                );
+
+                true
+            }
+        };
+
+        Ok(is_new)
+    }
+}
+
+impl<I: Interface, C: Fn() -> u32> Interface for ReliableInterface<I, C> {
+    fn try_get_packet(&mut self) -> Result<Packet, InterfaceError> {
+        self.retransmit()?;
+
+        loop {
+            let packet = self.interface.try_get_packet()?;
+
+            if packet.device_address == self.device_address {
+                if let Ok(ack) = ExtendedAckEvent::try_from_packet(&packet) {
+                    // Acknowledgements of anything but a pending packet are for the application
+                    if let Some(index) = self.find_pending(&ack) {
+                        self.handle_ack(index, ack.details.unwrap().status)?;
+
+                        continue;
+                    }
+                }
+            }
+
+            if let Ok(event) = SequencedEvent::try_from_packet(&packet) {
+                if event.receiver_address == self.device_address
+                    && !self.handle_sequenced(&event)?
+                {
+                    continue;
+                }
+
+                return Ok(Packet {
+                    is_error: false,
+                    device_address: event.receiver_address,
+                    data: event.data,
+                });
+            }
+
+            return Ok(packet);
+        }
+    }
+
+    fn try_send_packet(&mut self, packet: &Packet) -> Result<(), InterfaceError> {
+        if packet.is_error
+            || packet.device_address == BROADCAST_ADDRESS
+            || is_group_address(packet.device_address)
+            || ExtendedAckEvent::try_from_packet(packet).is_ok()
+        {
+            return self.interface.try_send_packet(packet);
+        }
+
+        if self.pending_packets.len() >= MAX_PENDING_PACKETS {
+            return Err(InterfaceError::ReliableError(ReliableError::QueueFull));
+        }
+
+        let next_sequence = self
+            .next_sequences
+            .entry(packet.device_address)
+            .or_insert(0x0000);
+        let sequence = *next_sequence;
+        *next_sequence = sequence.wrapping_add(1);
+
+        let sequenced_packet = SequencedEvent {
+            receiver_address: packet.device_address,
+            transmitter_address: self.device_address,
+            session: self.session,
+            sequence,
+            data: packet.data.clone(),
+        }
+        .to_packet();
+
+        self.interface.try_send_packet(&sequenced_packet)?;
+
+        self.pending_packets.push(PendingPacket {
+            receiver_address: packet.device_address,
+            event_code: event_code(&packet.data),
+            sequence,
+            packet: sequenced_packet,
+            sent_at: (self.clock)(),
+            retries: 0,
+        });
+
+        Ok(())
+    }
+
+    /// Returns the counters of the wrapped interface, which include retransmissions and
+    /// acknowledgements, along with the packets that were never acknowledged
+    fn statistics(&self) -> InterfaceStatistics {
+        let mut statistics = self.interface.statistics();
+        statistics.timeouts = statistics.timeouts.wrapping_add(self.timeouts);
+
+        statistics
+    }
+
+    fn reset_statistics(&mut self) {
+        self.interface.reset_statistics();
+        self.timeouts = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::collections::VecDeque;
+    use core::cell::Cell;
+
+    use crate::event::relay::{RelaySetValueEvent, RelayValue};
+
+    const FIRST_ADDRESS: u16 = 0x0001;
+    const SECOND_ADDRESS: u16 = 0x0002;
+
+    struct MockInterface {
+        received: VecDeque<Packet>,
+        sent: Vec<Packet>,
+    }
+
+    impl MockInterface {
+        fn new() -> Self {
+            MockInterface {
+                received: VecDeque::new(),
+                sent: vec![],
+            }
+        }
+    }
+
+    impl Interface for MockInterface {
+        fn try_get_packet(&mut self) -> Result<Packet, InterfaceError> {
+            self.received
+                .pop_front()
+                .ok_or(InterfaceError::NoPacketReceived)
+        }
+
+        fn try_send_packet(&mut self, packet: &Packet) -> Result<(), InterfaceError> {
+            self.sent.push(packet.clone());
+
+            Ok(())
+        }
+    }
+
+    fn relay_packet() -> Packet {
+        RelaySetValueEvent {
+            relay_address: SECOND_ADDRESS,
+            transmitter_address: FIRST_ADDRESS,
+            index: 0x00,
+            value: RelayValue::Single(true),
+        }
+        .to_packet()
+    }
+
+    /// Moves everything one interface sent into the receive queue of the other
+    fn transfer<A: Fn() -> u32, B: Fn() -> u32>(
+        from: &mut ReliableInterface<MockInterface, A>,
+        to: &mut ReliableInterface<MockInterface, B>,
+    ) {
+        for packet in from.get_mut().sent.drain(..) {
+            to.get_mut().received.push_back(packet);
+        }
+    }
+
+    #[test]
+    fn delivery_test() {
+        let now = Cell::new(0);
+        let mut first =
+            ReliableInterface::new(FIRST_ADDRESS, 0x00, MockInterface::new(), || now.get());
+        let mut second =
+            ReliableInterface::new(SECOND_ADDRESS, 0x00, MockInterface::new(), || now.get());
+
+        first.try_send_packet(&relay_packet()).unwrap();
+        assert_eq!(first.pending_count(), 1);
+
+        transfer(&mut first, &mut second);
+        assert_eq!(second.try_get_packet().unwrap(), relay_packet());
+
+        transfer(&mut second, &mut first);
+        assert!(matches!(
+            first.try_get_packet(),
+            Err(InterfaceError::NoPacketReceived)
+        ));
+        assert_eq!(first.pending_count(), 0);
+    }
+
+    #[test]
+    fn retransmit_test() {
+        let now = Cell::new(0);
+        let mut first = ReliableInterface::with_retransmission(
+            FIRST_ADDRESS,
+            0x00,
+            MockInterface::new(),
+            || now.get(),
+            100,
+            2,
+        );
+        let mut second =
+            ReliableInterface::new(SECOND_ADDRESS, 0x00, MockInterface::new(), || now.get());
+
+        first.try_send_packet(&relay_packet()).unwrap();
+
+        // The first transmission is lost
+        first.get_mut().sent.clear();
+
+        now.set(99);
+        assert!(matches!(
+            first.try_get_packet(),
+            Err(InterfaceError::NoPacketReceived)
+        ));
+        assert_eq!(first.get_ref().sent, vec![]);
+
+        now.set(100);
+        assert!(matches!(
+            first.try_get_packet(),
+            Err(InterfaceError::NoPacketReceived)
+        ));
+        assert_eq!(first.get_ref().sent.len(), 1);
+
+        transfer(&mut first, &mut second);
+        assert_eq!(second.try_get_packet().unwrap(), relay_packet());
+
+        transfer(&mut second, &mut first);
+        assert!(matches!(
+            first.try_get_packet(),
+            Err(InterfaceError::NoPacketReceived)
+        ));
+        assert_eq!(first.pending_count(), 0);
+    }
+
+    #[test]
+    fn delivery_failed_test() {
+        let now = Cell::new(0);
+        let mut first = ReliableInterface::with_retransmission(
+            FIRST_ADDRESS,
+            0x00,
+            MockInterface::new(),
+            || now.get(),
+            100,
+            1,
+        );
+
+        first.try_send_packet(&relay_packet()).unwrap();
+
+        now.set(100);
+        assert!(matches!(
+            first.try_get_packet(),
+            Err(InterfaceError::NoPacketReceived)
+        ));
+
+        now.set(200);
+        assert!(matches!(
+            first.try_get_packet(),
+            Err(InterfaceError::ReliableError(
+                ReliableError::DeliveryFailed {
+                    receiver_address: SECOND_ADDRESS,
+                    sequence: 0,
+                }
+            ))
+        ));
+        assert_eq!(first.get_ref().sent.len(), 2);
+        assert_eq!(first.pending_count(), 0);
+        assert_eq!(first.statistics().timeouts, 1);
+
+        first.reset_statistics();
+        assert_eq!(first.statistics().timeouts, 0);
+    }
+
+    #[test]
+    fn duplicate_suppression_test() {
+        let now = Cell::new(0);
+        let mut first =
+            ReliableInterface::new(FIRST_ADDRESS, 0x00, MockInterface::new(), || now.get());
+        let mut second =
+            ReliableInterface::new(SECOND_ADDRESS, 0x00, MockInterface::new(), || now.get());
+
+        first.try_send_packet(&relay_packet()).unwrap();
+        let sequenced_packet = first.get_ref().sent[0].clone();
+
+        // The acknowledgement gets lost, so the packet is received twice
+        second
+            .get_mut()
+            .received
+            .push_back(sequenced_packet.clone());
+        second.get_mut().received.push_back(sequenced_packet);
+
+        assert_eq!(second.try_get_packet().unwrap(), relay_packet());
+        assert!(matches!(
+            second.try_get_packet(),
+            Err(InterfaceError::NoPacketReceived)
+        ));
+
+        // Both copies are acknowledged
+        assert_eq!(second.get_ref().sent.len(), 2);
+    }
+
+    #[test]
+    fn sequence_window_test() {
+        let mut window = SequenceWindow::new(0x00, 0xfffe);
+
+        assert!(!window.receive(0x00, 0xfffe));
+        assert!(window.receive(0x00, 0x0001));
+        assert!(window.receive(0x00, 0xffff));
+        assert!(!window.receive(0x00, 0xffff));
+        assert!(window.receive(0x00, 0x0000));
+        assert!(!window.receive(0x00, 0xfffe));
+        assert!(window.receive(0x00, 0x8000));
+        assert!(window.receive(0x01, 0x8000));
+        assert!(!window.receive(0x01, 0x8000));
+    }
+
+    #[test]
+    fn restart_test() {
+        let now = Cell::new(0);
+        let mut first =
+            ReliableInterface::new(FIRST_ADDRESS, 0x00, MockInterface::new(), || now.get());
+        let mut second =
+            ReliableInterface::new(SECOND_ADDRESS, 0x00, MockInterface::new(), || now.get());
+
+        for _ in 0..6 {
+            first.try_send_packet(&relay_packet()).unwrap();
+            transfer(&mut first, &mut second);
+            assert_eq!(second.try_get_packet().unwrap(), relay_packet());
+        }
+
+        // The first device restarts before the acknowledgements reach it and counts from sequence
+        // 0 again
+        second.get_mut().sent.clear();
+        let mut first =
+            ReliableInterface::new(FIRST_ADDRESS, 0x01, MockInterface::new(), || now.get());
+
+        for _ in 0..6 {
+            first.try_send_packet(&relay_packet()).unwrap();
+            transfer(&mut first, &mut second);
+            assert_eq!(second.try_get_packet().unwrap(), relay_packet());
+        }
+
+        transfer(&mut second, &mut first);
+        assert!(matches!(
+            first.try_get_packet(),
+            Err(InterfaceError::NoPacketReceived)
+        ));
+        assert_eq!(first.pending_count(), 0);
+    }
+
+    #[test]
+    fn application_ack_test() {
+        let now = Cell::new(0);
+        let mut first =
+            ReliableInterface::new(FIRST_ADDRESS, 0x00, MockInterface::new(), || now.get());
+
+        first.try_send_packet(&relay_packet()).unwrap();
+
+        // A status reply of the application that happens to share the pending sequence number
+        let ack = ExtendedAckEvent {
+            receiver_address: FIRST_ADDRESS,
+            transmitter_address: SECOND_ADDRESS,
+            details: Some(AckDetails {
+                event_code: 0x0000,
+                sequence: 0x0000,
+                status: AckStatus::Invalid,
+            }),
+        }
+        .to_packet();
+
+        first.get_mut().received.push_back(ack.clone());
+
+        assert_eq!(first.try_get_packet().unwrap(), ack);
+        assert_eq!(first.pending_count(), 1);
+    }
+
+    #[test]
+    fn broadcast_test() {
+        let now = Cell::new(0);
+        let mut first =
+            ReliableInterface::new(FIRST_ADDRESS, 0x00, MockInterface::new(), || now.get());
+
+        let mut packet = relay_packet();
+        packet.device_address = BROADCAST_ADDRESS;
+
+        first.try_send_packet(&packet).unwrap();
+
+        assert_eq!(first.get_ref().sent, vec![packet]);
+        assert_eq!(first.pending_count(), 0);
+    }
+
+    #[test]
+    fn group_test() {
+        let now = Cell::new(0);
+        let mut first =
+            ReliableInterface::new(FIRST_ADDRESS, 0x00, MockInterface::new(), || now.get());
+        let mut second =
+            ReliableInterface::new(SECOND_ADDRESS, 0x00, MockInterface::new(), || now.get());
+
+        let mut packet = relay_packet();
+        packet.device_address = 0xff01;
+
+        first.try_send_packet(&packet).unwrap();
+        assert_eq!(first.get_ref().sent, vec![packet.clone()]);
+        assert_eq!(first.pending_count(), 0);
+
+        transfer(&mut first, &mut second);
+        assert_eq!(second.try_get_packet().unwrap(), packet);
+
+        // Group members do not acknowledge, so nothing is retransmitted
+        assert_eq!(second.get_ref().sent, vec![]);
+
+        now.set(1000);
+        assert!(matches!(
+            first.try_get_packet(),
+            Err(InterfaceError::NoPacketReceived)
+        ));
+        assert_eq!(first.get_ref().sent, vec![]);
+    }
+}
@@ -0,0 +1,360 @@
+use std::io::{Error as IOError, ErrorKind, Read, Write};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+use crate::interface::usart_decoder::{UsartDecoder, UsartFraming};
+use crate::interface::*;
+use crate::packet::*;
+
+/// Default time a write may stay blocked before it is given up on
+pub const DEFAULT_WRITE_TIMEOUT: Duration = Duration::from_millis(500);
+
+const READ_BUFFER_SIZE: usize = 64;
+
+#[derive(Debug)]
+pub enum StreamError {
+    ReadError(IOError),
+    WriteError(IOError),
+    /// The other end has closed the stream
+    Closed,
+    /// The other end stopped reading for longer than the write timeout
+    WriteTimedOut,
+}
+
+/// Interface carrying the USART wire format over any `std::io` stream
+///
+/// Read timeouts (`TimedOut` and `WouldBlock`) are reported as `NoPacketReceived`, while every
+/// other I/O error is reported as a `StreamError`. This makes it usable with blocking streams that
+/// have a read timeout set (like serial ports) as well as with non-blocking ones (like sockets).
+/// Writes to a non-blocking stream that stay blocked for longer than the write timeout fail with
+/// `StreamError::WriteTimedOut`, so a peer that stops reading can't stall the caller.
+pub struct StreamInterface<T: Read + Write> {
+    stream: T,
+    decoder: UsartDecoder,
+    statistics: InterfaceStatistics,
+    write_timeout: Duration,
+    read_buffer: [u8; READ_BUFFER_SIZE],
+    read_position: usize,
+    read_length: usize,
+}
+
+impl<T: Read + Write> StreamInterface<T> {
+    pub fn new(stream: T) -> Self {
+        Self::with_framing(stream, UsartFraming::LengthPrefixed)
+    }
+
+    pub fn with_framing(stream: T, framing: UsartFraming) -> Self {
+        StreamInterface {
+            stream,
+            decoder: UsartDecoder::with_framing(framing),
+            statistics: InterfaceStatistics::default(),
+            write_timeout: DEFAULT_WRITE_TIMEOUT,
+            read_buffer: [0x00; READ_BUFFER_SIZE],
+            read_position: 0,
+            read_length: 0,
+        }
+    }
+
+    pub fn write_timeout(&self) -> Duration {
+        self.write_timeout
+    }
+
+    pub fn set_write_timeout(&mut self, write_timeout: Duration) {
+        self.write_timeout = write_timeout;
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.stream
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.stream
+    }
+
+    pub fn into_inner(self) -> T {
+        self.stream
+    }
+
+    fn read_bytes(&mut self) -> Result<(), InterfaceError> {
+        loop {
+            match self.stream.read(&mut self.read_buffer[..]) {
+                Ok(0) => {
+                    self.decoder.reset();
+
+                    return Err(InterfaceError::StreamError(StreamError::Closed));
+                }
+                Ok(length) => {
+                    self.read_position = 0;
+                    self.read_length = length;
+
+                    return Ok(());
+                }
+                Err(err) => match err.kind() {
+                    ErrorKind::Interrupted => continue,
+                    ErrorKind::TimedOut | ErrorKind::WouldBlock => {
+                        return Err(InterfaceError::NoPacketReceived)
+                    }
+                    _ => {
+                        self.decoder.reset();
+
+                        let err = InterfaceError::StreamError(StreamError::ReadError(err));
+                        self.statistics.record_error(&err);
+
+                        return Err(err);
+                    }
+                },
+            }
+        }
+    }
+
+    fn write_bytes(&mut self, mut buf: &[u8]) -> Result<(), StreamError> {
+        let deadline = Instant::now() + self.write_timeout;
+
+        while !buf.is_empty() {
+            match self.stream.write(buf) {
+                Ok(0) => return Err(StreamError::Closed),
+                Ok(length) => buf = &buf[length..],
+                Err(err) => match err.kind() {
+                    ErrorKind::Interrupted => {}
+                    ErrorKind::WouldBlock => Self::wait_until_writable(deadline)?,
+                    _ => return Err(StreamError::WriteError(err)),
+                },
+            }
+        }
+
+        loop {
+            match self.stream.flush() {
+                Ok(_) => return Ok(()),
+                Err(err) => match err.kind() {
+                    ErrorKind::Interrupted => {}
+                    ErrorKind::WouldBlock => Self::wait_until_writable(deadline)?,
+                    _ => return Err(StreamError::WriteError(err)),
+                },
+            }
+        }
+    }
+
+    fn wait_until_writable(deadline: Instant) -> Result<(), StreamError> {
+        if Instant::now() >= deadline {
+            return Err(StreamError::WriteTimedOut);
+        }
+
+        sleep(Duration::from_millis(1));
+
+        Ok(())
+    }
+}
+
+impl<T: Read + Write> Interface for StreamInterface<T> {
+    fn try_get_packet(&mut self) -> Result<Packet, InterfaceError> {
+        loop {
+            while self.read_position < self.read_length {
+                let byte = self.read_buffer[self.read_position];
+                self.read_position += 1;
+
+                if let Some(packet) = self.decoder.decode_byte(byte)? {
+                    return Ok(packet);
+                }
+            }
+
+            self.read_bytes()?;
+        }
+    }
+
+    fn try_send_packet(&mut self, packet: &Packet) -> Result<(), InterfaceError> {
+        let buf = self.decoder.framing().encode_packet(packet);
+
+        match self.write_bytes(&buf) {
+            Ok(()) => {
+                self.statistics.record_packet_sent(packet);
+
+                Ok(())
+            }
+            Err(err) => {
+                let err = InterfaceError::StreamError(err);
+                self.statistics.record_error(&err);
+
+                Err(err)
+            }
+        }
+    }
+
+    fn statistics(&self) -> InterfaceStatistics {
+        self.decoder.statistics() + self.statistics
+    }
+
+    fn reset_statistics(&mut self) {
+        self.decoder.reset_statistics();
+        self.statistics = InterfaceStatistics::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::VecDeque;
+
+    /// In-memory stream that reports `WouldBlock` once all queued input has been read
+    struct Pipe {
+        input: VecDeque<u8>,
+        output: Vec<u8>,
+        read_error: Option<ErrorKind>,
+        /// Amount of bytes that can be written before writes block
+        write_capacity: usize,
+    }
+
+    impl Pipe {
+        fn new(input: Vec<u8>) -> Self {
+            Pipe {
+                input: input.into(),
+                output: vec![],
+                read_error: Some(ErrorKind::WouldBlock),
+                write_capacity: usize::MAX,
+            }
+        }
+    }
+
+    impl Read for Pipe {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.input.is_empty() {
+                return match self.read_error {
+                    Some(kind) => Err(IOError::from(kind)),
+                    None => Ok(0),
+                };
+            }
+
+            let mut length = 0;
+
+            while length < buf.len() {
+                match self.input.pop_front() {
+                    Some(byte) => buf[length] = byte,
+                    None => break,
+                }
+
+                length += 1;
+            }
+
+            Ok(length)
+        }
+    }
+
+    impl Write for Pipe {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            let length = buf.len().min(self.write_capacity - self.output.len());
+
+            if length == 0 {
+                return Err(IOError::from(ErrorKind::WouldBlock));
+            }
+
+            self.output.extend_from_slice(&buf[..length]);
+
+            Ok(length)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn packet(data_len: u8) -> Packet {
+        Packet {
+            is_error: false,
+            device_address: 0xabab,
+            data: (0..data_len).collect(),
+        }
+    }
+
+    #[test]
+    fn send_receive_test() {
+        let mut sender = StreamInterface::new(Pipe::new(vec![]));
+        sender.try_send_packet(&packet(20)).unwrap();
+        sender.try_send_packet(&packet(3)).unwrap();
+
+        let mut receiver = StreamInterface::new(Pipe::new(sender.into_inner().output));
+
+        assert_eq!(receiver.try_get_packet().unwrap(), packet(20));
+        assert_eq!(receiver.try_get_packet().unwrap(), packet(3));
+        assert!(matches!(
+            receiver.try_get_packet(),
+            Err(InterfaceError::NoPacketReceived)
+        ));
+    }
+
+    #[test]
+    fn partial_packet_test() {
+        let bytes = UsartFraming::ZeroDelimited.encode_packet(&packet(20));
+
+        let mut receiver = StreamInterface::with_framing(
+            Pipe::new(bytes[..10].to_vec()),
+            UsartFraming::ZeroDelimited,
+        );
+
+        assert!(matches!(
+            receiver.try_get_packet(),
+            Err(InterfaceError::NoPacketReceived)
+        ));
+
+        receiver.get_mut().input.extend(bytes[10..].iter());
+
+        assert_eq!(receiver.try_get_packet().unwrap(), packet(20));
+    }
+
+    #[test]
+    fn timed_out_test() {
+        let mut pipe = Pipe::new(vec![]);
+        pipe.read_error = Some(ErrorKind::TimedOut);
+
+        let mut receiver = StreamInterface::new(pipe);
+
+        assert!(matches!(
+            receiver.try_get_packet(),
+            Err(InterfaceError::NoPacketReceived)
+        ));
+    }
+
+    #[test]
+    fn read_error_test() {
+        let mut pipe = Pipe::new(vec![]);
+        pipe.read_error = Some(ErrorKind::BrokenPipe);
+
+        let mut receiver = StreamInterface::new(pipe);
+
+        assert!(matches!(
+            receiver.try_get_packet(),
+            Err(InterfaceError::StreamError(StreamError::ReadError(_)))
+        ));
+    }
+
+    #[test]
+    fn closed_test() {
+        let mut pipe = Pipe::new(vec![]);
+        pipe.read_error = None;
+
+        let mut receiver = StreamInterface::new(pipe);
+
+        assert!(matches!(
+            receiver.try_get_packet(),
+            Err(InterfaceError::StreamError(StreamError::Closed))
+        ));
+    }
+
+    #[test]
+    fn write_timed_out_test() {
+        let mut pipe = Pipe::new(vec![]);
+        pipe.write_capacity = 10;
+
+        let mut sender = StreamInterface::new(pipe);
+        sender.set_write_timeout(Duration::from_millis(20));
+
+        let start = Instant::now();
+
+        assert!(matches!(
+            sender.try_send_packet(&packet(20)),
+            Err(InterfaceError::StreamError(StreamError::WriteTimedOut))
+        ));
+        assert!(start.elapsed() >= Duration::from_millis(20));
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert_eq!(sender.get_ref().output.len(), 10);
+    }
+}
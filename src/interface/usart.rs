@@ -1,26 +1,34 @@
-use alloc::vec;
 use embedded_hal::serial::{Read, Write};
 use nb::block;
 
-use crate::frame::*;
+use crate::interface::usart_decoder::{UsartDecoder, UsartFraming};
 use crate::interface::*;
 use crate::packet::*;
+use crate::ring_buffer::Consumer;
 
 #[derive(Debug, PartialEq)]
 pub enum UsartError {
     ReadError,
+    /// Received bytes were dropped because the receive buffer was full
+    BufferOverrun,
 }
 
 pub struct Usart<S: Read<u8> + Write<u8>> {
     serial: S,
-    packet_builder: Option<PacketBuilder>,
+    decoder: UsartDecoder,
+    statistics: InterfaceStatistics,
 }
 
 impl<S: Read<u8> + Write<u8>> Usart<S> {
     pub fn new(serial: S) -> Self {
+        Self::with_framing(serial, UsartFraming::LengthPrefixed)
+    }
+
+    pub fn with_framing(serial: S, framing: UsartFraming) -> Self {
         Usart {
             serial,
-            packet_builder: None,
+            decoder: UsartDecoder::with_framing(framing),
+            statistics: InterfaceStatistics::default(),
         }
     }
 }
@@ -29,63 +37,20 @@ impl<S: Read<u8> + Write<u8>> Interface for Usart<S> {
     fn try_get_packet(&mut self) -> Result<Packet, InterfaceError> {
         loop {
             match self.serial.read() {
-                Ok(frame_start) => {
-                    if frame_start == 0x00 {
-                        let mut frame = vec![];
-
-                        let expected_length = match block!(self.serial.read()) {
-                            Ok(length) => length,
-                            Err(_) => {
-                                return Err(InterfaceError::UsartError(UsartError::ReadError))
-                            }
-                        };
-
-                        loop {
-                            match block!(self.serial.read()) {
-                                Ok(byte) => frame.push(byte),
-                                Err(_) => {
-                                    return Err(InterfaceError::UsartError(UsartError::ReadError))
-                                }
-                            }
-
-                            if frame.len() == expected_length as usize {
-                                break;
-                            }
-                        }
-
-                        let ross_frame = match Frame::from_usart_frame(frame) {
-                            Ok(frame) => frame,
-                            Err(err) => return Err(InterfaceError::FrameError(err)),
-                        };
-
-                        if let Some(ref mut packet_builder) = self.packet_builder {
-                            if let Err(err) = packet_builder.add_frame(ross_frame) {
-                                self.packet_builder = None;
-
-                                return Err(InterfaceError::BuilderError(err));
-                            }
-                        } else {
-                            self.packet_builder = match PacketBuilder::new(ross_frame) {
-                                Ok(builder) => Some(builder),
-                                Err(err) => return Err(InterfaceError::BuilderError(err)),
-                            };
-                        }
-
-                        if let Some(ref mut packet_builder) = self.packet_builder {
-                            if packet_builder.frames_left() == 0 {
-                                let packet = match packet_builder.build() {
-                                    Ok(packet) => packet,
-                                    Err(err) => return Err(InterfaceError::BuilderError(err)),
-                                };
-
-                                self.packet_builder = None;
-
-                                return Ok(packet);
-                            }
-                        }
+                Ok(byte) => {
+                    if let Some(packet) = self.decoder.decode_byte(byte)? {
+                        return Ok(packet);
                     }
                 }
-                Err(_) => break,
+                Err(nb::Error::WouldBlock) => break,
+                Err(nb::Error::Other(_)) => {
+                    self.decoder.reset();
+
+                    let err = InterfaceError::UsartError(UsartError::ReadError);
+                    self.statistics.record_error(&err);
+
+                    return Err(err);
+                }
             }
         }
 
@@ -93,20 +58,88 @@ impl<S: Read<u8> + Write<u8>> Interface for Usart<S> {
     }
 
     fn try_send_packet(&mut self, packet: &Packet) -> Result<(), InterfaceError> {
-        let frames = packet.to_frames();
+        write_packet(&mut self.serial, self.decoder.framing(), packet);
+        self.statistics.record_packet_sent(packet);
+
+        Ok(())
+    }
+
+    fn statistics(&self) -> InterfaceStatistics {
+        self.decoder.statistics() + self.statistics
+    }
+
+    fn reset_statistics(&mut self) {
+        self.decoder.reset_statistics();
+        self.statistics = InterfaceStatistics::default();
+    }
+}
+
+/// USART interface that receives bytes through a `RingBuffer`
+///
+/// The receive interrupt pushes every received byte into the producer half of the ring buffer,
+/// while this interface drains the consumer half on every `try_get_packet` call. Transmission is
+/// done directly through the provided serial writer.
+pub struct BufferedUsart<'a, W: Write<u8>, const N: usize> {
+    consumer: Consumer<'a, N>,
+    serial: W,
+    decoder: UsartDecoder,
+    statistics: InterfaceStatistics,
+}
+
+impl<'a, W: Write<u8>, const N: usize> BufferedUsart<'a, W, N> {
+    pub fn new(consumer: Consumer<'a, N>, serial: W) -> Self {
+        Self::with_framing(consumer, serial, UsartFraming::LengthPrefixed)
+    }
+
+    pub fn with_framing(consumer: Consumer<'a, N>, serial: W, framing: UsartFraming) -> Self {
+        BufferedUsart {
+            consumer,
+            serial,
+            decoder: UsartDecoder::with_framing(framing),
+            statistics: InterfaceStatistics::default(),
+        }
+    }
+}
 
-        for frame in frames {
-            let _ = block!(self.serial.write(0x00));
+impl<'a, W: Write<u8>, const N: usize> Interface for BufferedUsart<'a, W, N> {
+    fn try_get_packet(&mut self) -> Result<Packet, InterfaceError> {
+        if self.consumer.take_overrun() {
+            self.decoder.reset();
 
-            let usart_frame = frame.to_usart_frame();
+            let err = InterfaceError::UsartError(UsartError::BufferOverrun);
+            self.statistics.record_error(&err);
 
-            let _ = block!(self.serial.write(usart_frame.len() as u8));
+            return Err(err);
+        }
 
-            for byte in frame.to_usart_frame().iter() {
-                let _ = block!(self.serial.write(*byte));
+        while let Some(byte) = self.consumer.pop() {
+            if let Some(packet) = self.decoder.decode_byte(byte)? {
+                return Ok(packet);
             }
         }
 
+        Err(InterfaceError::NoPacketReceived)
+    }
+
+    fn try_send_packet(&mut self, packet: &Packet) -> Result<(), InterfaceError> {
+        write_packet(&mut self.serial, self.decoder.framing(), packet);
+        self.statistics.record_packet_sent(packet);
+
         Ok(())
     }
+
+    fn statistics(&self) -> InterfaceStatistics {
+        self.decoder.statistics() + self.statistics
+    }
+
+    fn reset_statistics(&mut self) {
+        self.decoder.reset_statistics();
+        self.statistics = InterfaceStatistics::default();
+    }
+}
+
+fn write_packet<W: Write<u8>>(serial: &mut W, framing: UsartFraming, packet: &Packet) {
+    for byte in framing.encode_packet(packet).iter() {
+        let _ = block!(serial.write(*byte));
+    }
 }
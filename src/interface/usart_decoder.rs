@@ -0,0 +1,501 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::frame::*;
+use crate::interface::*;
+use crate::packet::*;
+
+/// Largest possible COBS encoded USART frame (5 header bytes + 8 data bytes + 1 COBS overhead byte)
+pub const MAX_ENCODED_FRAME_LENGTH: usize = 14;
+
+/// Wire format used to delimit frames on a byte stream
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum UsartFraming {
+    /// `0x00`, a length byte and then the COBS encoded frame
+    ///
+    /// This is the format used by older devices. A corrupted length byte makes the receiver consume
+    /// the wrong amount of bytes, but it resynchronises on the next `0x00` delimiter.
+    #[default]
+    LengthPrefixed,
+    /// COBS encoded frame enclosed in `0x00` delimiters
+    ///
+    /// Since COBS never produces `0x00`, every delimiter is a guaranteed frame boundary and the
+    /// receiver resynchronises after at most one lost frame. The leading delimiter lets a receiver
+    /// that starts listening mid-stream decode the very first complete frame.
+    ZeroDelimited,
+}
+
+impl UsartFraming {
+    /// Encodes a frame into the bytes to be put on the wire
+    pub fn encode_frame(self, frame: &Frame) -> Vec<u8> {
+        let usart_frame = frame.to_usart_frame();
+        let mut bytes = Vec::with_capacity(usart_frame.len() + 2);
+
+        match self {
+            Self::LengthPrefixed => {
+                bytes.push(0x00);
+                bytes.push(usart_frame.len() as u8);
+                bytes.extend_from_slice(&usart_frame);
+            }
+            Self::ZeroDelimited => {
+                bytes.push(0x00);
+                bytes.extend_from_slice(&usart_frame);
+                bytes.push(0x00);
+            }
+        }
+
+        bytes
+    }
+
+    /// Encodes all frames of a packet into the bytes to be put on the wire
+    pub fn encode_packet(self, packet: &Packet) -> Vec<u8> {
+        let mut bytes = vec![];
+
+        for frame in packet.to_frames().iter() {
+            bytes.append(&mut self.encode_frame(frame));
+        }
+
+        bytes
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum DecoderState {
+    /// Waiting for a `0x00` frame delimiter
+    WaitingForStart,
+    /// Waiting for the length byte following the delimiter
+    WaitingForLength,
+    /// Collecting the given amount of encoded frame bytes
+    ReadingFrame(u8),
+    /// Collecting encoded frame bytes until the next `0x00` delimiter
+    ReadingDelimitedFrame,
+}
+
+/// Incremental decoder for the USART wire format
+///
+/// Bytes are fed one at a time, so the decoder never has to wait for the rest of a frame and can be
+/// driven from a receive interrupt or a polling loop alike. Since COBS encoded data never contains
+/// `0x00`, a delimiter in the middle of a frame means that bytes were lost; the partial frame is
+/// dropped and decoding restarts from that delimiter.
+#[derive(Debug)]
+pub struct UsartDecoder {
+    framing: UsartFraming,
+    state: DecoderState,
+    frame: Vec<u8>,
+    packet_builder: Option<PacketBuilder>,
+    statistics: InterfaceStatistics,
+}
+
+impl UsartDecoder {
+    pub fn new() -> Self {
+        Self::with_framing(UsartFraming::LengthPrefixed)
+    }
+
+    pub fn with_framing(framing: UsartFraming) -> Self {
+        UsartDecoder {
+            framing,
+            state: DecoderState::WaitingForStart,
+            frame: vec![],
+            packet_builder: None,
+            statistics: InterfaceStatistics::default(),
+        }
+    }
+
+    pub fn framing(&self) -> UsartFraming {
+        self.framing
+    }
+
+    /// Returns the counters of the received frames and packets and of the decoding errors
+    pub fn statistics(&self) -> InterfaceStatistics {
+        self.statistics
+    }
+
+    pub fn reset_statistics(&mut self) {
+        self.statistics = InterfaceStatistics::default();
+    }
+
+    /// Drops any partially received frame or packet
+    pub fn reset(&mut self) {
+        if self.packet_builder.is_some() {
+            self.statistics.record_builder_reset();
+        }
+
+        self.state = DecoderState::WaitingForStart;
+        self.frame.clear();
+        self.packet_builder = None;
+    }
+
+    /// Feeds a single byte into the decoder, returning a frame once one is complete
+    pub fn decode_frame_byte(&mut self, byte: u8) -> Result<Option<Frame>, FrameError> {
+        match self.framing {
+            UsartFraming::LengthPrefixed => self.decode_length_prefixed_byte(byte),
+            UsartFraming::ZeroDelimited => self.decode_zero_delimited_byte(byte),
+        }
+    }
+
+    fn decode_zero_delimited_byte(&mut self, byte: u8) -> Result<Option<Frame>, FrameError> {
+        if byte == 0x00 {
+            let discarding = self.state == DecoderState::WaitingForStart;
+            self.state = DecoderState::ReadingDelimitedFrame;
+
+            if discarding || self.frame.is_empty() {
+                self.frame.clear();
+
+                return Ok(None);
+            }
+
+            return Frame::from_usart_frame(core::mem::take(&mut self.frame)).map(Some);
+        }
+
+        if self.state == DecoderState::ReadingDelimitedFrame {
+            if self.frame.len() == MAX_ENCODED_FRAME_LENGTH {
+                // Too long to be a frame, skip everything until the next delimiter
+                self.frame.clear();
+                self.state = DecoderState::WaitingForStart;
+
+                return Err(FrameError::WrongSize);
+            }
+
+            self.frame.push(byte);
+        }
+
+        Ok(None)
+    }
+
+    fn decode_length_prefixed_byte(&mut self, byte: u8) -> Result<Option<Frame>, FrameError> {
+        if byte == 0x00 {
+            self.frame.clear();
+            self.state = DecoderState::WaitingForLength;
+
+            return Ok(None);
+        }
+
+        match self.state {
+            DecoderState::WaitingForStart => Ok(None),
+            DecoderState::WaitingForLength => {
+                if byte as usize > MAX_ENCODED_FRAME_LENGTH {
+                    self.state = DecoderState::WaitingForStart;
+
+                    return Err(FrameError::WrongSize);
+                }
+
+                self.state = DecoderState::ReadingFrame(byte);
+
+                Ok(None)
+            }
+            DecoderState::ReadingFrame(expected_length) => {
+                self.frame.push(byte);
+
+                if self.frame.len() < expected_length as usize {
+                    return Ok(None);
+                }
+
+                self.state = DecoderState::WaitingForStart;
+
+                Frame::from_usart_frame(core::mem::take(&mut self.frame)).map(Some)
+            }
+            DecoderState::ReadingDelimitedFrame => Ok(None),
+        }
+    }
+
+    /// Feeds a single byte into the decoder, returning a packet once one is complete
+    pub fn decode_byte(&mut self, byte: u8) -> Result<Option<Packet>, InterfaceError> {
+        let result = match self.decode_frame_byte(byte) {
+            Ok(Some(frame)) => {
+                self.statistics.record_frame_received();
+                self.add_frame(frame)
+            }
+            Ok(None) => Ok(None),
+            Err(err) => Err(InterfaceError::FrameError(err)),
+        };
+
+        match result {
+            Ok(Some(_)) => self.statistics.record_packet_received(),
+            Ok(None) => {}
+            Err(ref err) => self.statistics.record_error(err),
+        }
+
+        result
+    }
+
+    fn add_frame(&mut self, frame: Frame) -> Result<Option<Packet>, InterfaceError> {
+        // A start frame always begins a new packet, even if the previous one was never finished
+        if frame.start_frame_flag && self.packet_builder.take().is_some() {
+            self.statistics.record_builder_reset();
+        }
+
+        if let Some(ref mut packet_builder) = self.packet_builder {
+            if let Err(err) = packet_builder.add_frame(frame) {
+                self.packet_builder = None;
+
+                return Err(InterfaceError::BuilderError(err));
+            }
+        } else {
+            self.packet_builder = match PacketBuilder::new(frame) {
+                Ok(builder) => Some(builder),
+                Err(err) => return Err(InterfaceError::BuilderError(err)),
+            };
+        }
+
+        if let Some(ref mut packet_builder) = self.packet_builder {
+            if packet_builder.frames_left() == 0 {
+                let packet = packet_builder.build();
+
+                self.packet_builder = None;
+
+                return match packet {
+                    Ok(packet) => Ok(Some(packet)),
+                    Err(err) => Err(InterfaceError::BuilderError(err)),
+                };
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+impl Default for UsartDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_packet(packet: &Packet) -> Vec<u8> {
+        UsartFraming::LengthPrefixed.encode_packet(packet)
+    }
+
+    fn decode_all(decoder: &mut UsartDecoder, bytes: &[u8]) -> Vec<Packet> {
+        let mut packets = vec![];
+
+        for byte in bytes.iter() {
+            if let Ok(Some(packet)) = decoder.decode_byte(*byte) {
+                packets.push(packet);
+            }
+        }
+
+        packets
+    }
+
+    #[test]
+    fn decode_single_frame_packet_test() {
+        let packet = Packet {
+            is_error: false,
+            device_address: 0xabab,
+            data: vec![0x01, 0x02, 0x03],
+        };
+
+        let mut decoder = UsartDecoder::new();
+
+        assert_eq!(
+            decode_all(&mut decoder, &encode_packet(&packet)),
+            vec![packet]
+        );
+    }
+
+    #[test]
+    fn decode_multi_frame_packet_test() {
+        let packet = Packet {
+            is_error: false,
+            device_address: 0xabab,
+            data: (0..30).collect(),
+        };
+
+        let mut decoder = UsartDecoder::new();
+
+        assert_eq!(
+            decode_all(&mut decoder, &encode_packet(&packet)),
+            vec![packet]
+        );
+    }
+
+    #[test]
+    fn decode_partial_frame_test() {
+        let packet = Packet {
+            is_error: false,
+            device_address: 0xabab,
+            data: vec![0x01, 0x02, 0x03],
+        };
+        let bytes = encode_packet(&packet);
+
+        let mut decoder = UsartDecoder::new();
+
+        assert_eq!(decode_all(&mut decoder, &bytes[..4]), vec![]);
+        assert_eq!(decode_all(&mut decoder, &bytes[4..]), vec![packet]);
+    }
+
+    #[test]
+    fn decode_truncated_frame_test() {
+        let packet = Packet {
+            is_error: false,
+            device_address: 0xabab,
+            data: vec![0x01, 0x02, 0x03],
+        };
+        let bytes = encode_packet(&packet);
+
+        let mut truncated = bytes[..5].to_vec();
+        truncated.extend_from_slice(&bytes);
+
+        let mut decoder = UsartDecoder::new();
+
+        assert_eq!(decode_all(&mut decoder, &truncated), vec![packet]);
+    }
+
+    #[test]
+    fn decode_garbage_test() {
+        let packet = Packet {
+            is_error: false,
+            device_address: 0xabab,
+            data: vec![0x01, 0x02, 0x03],
+        };
+
+        let mut bytes = vec![0x55, 0xff, 0x00, 0xff, 0x12];
+        bytes.extend_from_slice(&encode_packet(&packet));
+
+        let mut decoder = UsartDecoder::new();
+
+        assert_eq!(decode_all(&mut decoder, &bytes), vec![packet]);
+    }
+
+    #[test]
+    fn encode_frame_test() {
+        let packet = Packet {
+            is_error: false,
+            device_address: 0xabab,
+            data: vec![0x01],
+        };
+        let frame = &packet.to_frames()[0];
+        let usart_frame = frame.to_usart_frame();
+
+        let mut expected = vec![0x00, usart_frame.len() as u8];
+        expected.extend_from_slice(&usart_frame);
+        assert_eq!(UsartFraming::LengthPrefixed.encode_frame(frame), expected);
+
+        let mut expected = vec![0x00];
+        expected.extend_from_slice(&usart_frame);
+        expected.push(0x00);
+        assert_eq!(UsartFraming::ZeroDelimited.encode_frame(frame), expected);
+    }
+
+    #[test]
+    fn decode_zero_delimited_test() {
+        let packet = Packet {
+            is_error: false,
+            device_address: 0xabab,
+            data: (0..30).collect(),
+        };
+
+        let mut decoder = UsartDecoder::with_framing(UsartFraming::ZeroDelimited);
+        let bytes = UsartFraming::ZeroDelimited.encode_packet(&packet);
+
+        assert_eq!(decode_all(&mut decoder, &bytes), vec![packet]);
+    }
+
+    #[test]
+    fn decode_zero_delimited_garbage_test() {
+        let packet = Packet {
+            is_error: false,
+            device_address: 0xabab,
+            data: vec![0x01, 0x02, 0x03],
+        };
+
+        // Unterminated garbage longer than any frame, followed by a truncated frame
+        let mut bytes = vec![0x55; 40];
+        bytes.push(0x00);
+        bytes.extend_from_slice(&UsartFraming::ZeroDelimited.encode_packet(&packet)[3..]);
+        bytes.extend_from_slice(&UsartFraming::ZeroDelimited.encode_packet(&packet));
+
+        let mut decoder = UsartDecoder::with_framing(UsartFraming::ZeroDelimited);
+
+        assert_eq!(decode_all(&mut decoder, &bytes), vec![packet]);
+    }
+
+    #[test]
+    fn decode_zero_delimited_truncated_leading_frame_test() {
+        let packet = Packet {
+            is_error: false,
+            device_address: 0xabab,
+            data: vec![0x01, 0x02, 0x03],
+        };
+
+        // Decoder attached in the middle of a frame, the tail must not be decoded as a frame
+        let mut bytes = UsartFraming::ZeroDelimited.encode_packet(&packet)[4..].to_vec();
+        bytes.extend_from_slice(&UsartFraming::ZeroDelimited.encode_packet(&packet));
+
+        let mut decoder = UsartDecoder::with_framing(UsartFraming::ZeroDelimited);
+        let mut packets = vec![];
+
+        for byte in bytes.iter() {
+            if let Some(packet) = decoder.decode_byte(*byte).unwrap() {
+                packets.push(packet);
+            }
+        }
+
+        assert_eq!(packets, vec![packet]);
+    }
+
+    #[test]
+    fn decode_zero_delimited_corrupted_frame_test() {
+        let packet = Packet {
+            is_error: false,
+            device_address: 0xabab,
+            data: vec![0x01, 0x02, 0x03],
+        };
+
+        let mut decoder = UsartDecoder::with_framing(UsartFraming::ZeroDelimited);
+
+        assert_eq!(decoder.decode_frame_byte(0x00), Ok(None));
+
+        // Frame with a lost byte
+        let mut bytes = UsartFraming::ZeroDelimited.encode_packet(&packet);
+        bytes.remove(4);
+        bytes.extend_from_slice(&UsartFraming::ZeroDelimited.encode_packet(&packet));
+
+        assert_eq!(decode_all(&mut decoder, &bytes), vec![packet]);
+    }
+
+    #[test]
+    fn statistics_test() {
+        let packet = Packet {
+            is_error: false,
+            device_address: 0xabab,
+            data: (0..30).collect(),
+        };
+        let frames = packet.to_frames();
+
+        let mut bytes = vec![];
+
+        // A packet cut short by the start of the next one
+        for frame in frames[..2].iter() {
+            bytes.append(&mut UsartFraming::LengthPrefixed.encode_frame(frame));
+        }
+
+        bytes.append(&mut encode_packet(&packet));
+
+        // A packet missing its third frame
+        for index in [0, 1, 3].iter() {
+            bytes.append(&mut UsartFraming::LengthPrefixed.encode_frame(&frames[*index]));
+        }
+
+        let mut decoder = UsartDecoder::new();
+
+        assert_eq!(decode_all(&mut decoder, &bytes), vec![packet]);
+        assert_eq!(
+            decoder.statistics(),
+            InterfaceStatistics {
+                frames_received: 10,
+                packets_received: 1,
+                out_of_order_frames: 1,
+                builder_resets: 2,
+                ..Default::default()
+            }
+        );
+
+        decoder.reset_statistics();
+
+        assert_eq!(decoder.statistics(), InterfaceStatistics::default());
+    }
+}
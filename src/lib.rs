@@ -2,9 +2,19 @@
 
 extern crate alloc;
 
+pub mod animation;
+pub mod clock;
+pub mod color;
 pub mod convert_packet;
 pub mod event;
 pub mod frame;
+pub mod gateway;
+pub mod gesture;
 pub mod interface;
+pub mod object_dictionary;
 pub mod packet;
 pub mod protocol;
+pub mod reporter;
+pub mod ring_buffer;
+pub mod scheduler;
+pub mod storage;
@@ -0,0 +1,546 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::convert_packet::ConvertPacket;
+use crate::event::error::{ErrorCode, ErrorEvent};
+use crate::event::event_code::*;
+use crate::event::parameter::*;
+use crate::packet::Packet;
+use crate::storage::key_value::*;
+use crate::storage::*;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ParameterError {
+    UnknownIndex,
+    AlreadyRegistered,
+    ReadOnly,
+    /// The value has a different type than the parameter
+    WrongType,
+    /// The value is outside of the range of the parameter
+    OutOfRange,
+}
+
+impl ParameterError {
+    fn error_code(self) -> ErrorCode {
+        match self {
+            Self::ReadOnly => ErrorCode::InvalidState,
+            _ => ErrorCode::InvalidValue,
+        }
+    }
+}
+
+struct Parameter {
+    value: ParameterValue,
+    access: ParameterAccess,
+    min: ParameterValue,
+    max: ParameterValue,
+}
+
+/// Typed parameters of a device, readable and writable at runtime by index
+///
+/// Firmware registers its tunables with their default value and range, reads them with `get`
+/// wherever they are used and passes incoming packets to `handle_packet`, which answers
+/// `ParameterReadEvent`s, `ParameterWriteEvent`s and `ParameterDescribeEvent`s. Failed requests
+/// are answered with an `ErrorEvent` carrying the parameter index as its detail.
+pub struct ObjectDictionary {
+    device_address: u16,
+    parameters: BTreeMap<u16, Parameter>,
+    written: BTreeSet<u16>,
+}
+
+impl ObjectDictionary {
+    pub fn new(device_address: u16) -> Self {
+        ObjectDictionary {
+            device_address,
+            parameters: BTreeMap::new(),
+            written: BTreeSet::new(),
+        }
+    }
+
+    /// Adds a parameter ranging from `min` to `max`, all of which must have the same type
+    pub fn register(
+        &mut self,
+        index: u16,
+        value: ParameterValue,
+        access: ParameterAccess,
+        min: ParameterValue,
+        max: ParameterValue,
+    ) -> Result<(), ParameterError> {
+        if self.parameters.contains_key(&index) {
+            return Err(ParameterError::AlreadyRegistered);
+        }
+
+        if min.parameter_type() != value.parameter_type()
+            || max.parameter_type() != value.parameter_type()
+        {
+            return Err(ParameterError::WrongType);
+        }
+
+        if value < min || value > max {
+            return Err(ParameterError::OutOfRange);
+        }
+
+        self.parameters.insert(
+            index,
+            Parameter {
+                value,
+                access,
+                min,
+                max,
+            },
+        );
+
+        Ok(())
+    }
+
+    pub fn get(&self, index: u16) -> Option<ParameterValue> {
+        self.parameters.get(&index).map(|parameter| parameter.value)
+    }
+
+    /// Changes a parameter from the firmware itself, read-only parameters included
+    pub fn set(&mut self, index: u16, value: ParameterValue) -> Result<(), ParameterError> {
+        let parameter = self
+            .parameters
+            .get_mut(&index)
+            .ok_or(ParameterError::UnknownIndex)?;
+
+        if value.parameter_type() != parameter.value.parameter_type() {
+            return Err(ParameterError::WrongType);
+        }
+
+        if value < parameter.min || value > parameter.max {
+            return Err(ParameterError::OutOfRange);
+        }
+
+        parameter.value = value;
+
+        Ok(())
+    }
+
+    /// Returns the indexes of the parameters written remotely since the last call, so firmware can
+    /// apply or persist them
+    pub fn take_written(&mut self) -> Vec<u16> {
+        let written = self.written.iter().copied().collect();
+
+        self.written.clear();
+
+        written
+    }
+
+    /// Stores the values of all read-write parameters under `PARAMETERS_KEY`
+    pub fn save<S: Storage>(&self, store: &mut KeyValueStore<S>) -> Result<(), StorageError> {
+        let mut data = vec![];
+
+        for (index, parameter) in self.parameters.iter() {
+            if parameter.access == ParameterAccess::ReadWrite {
+                data.extend_from_slice(&index.to_be_bytes());
+                data.append(&mut parameter.value.serialize());
+            }
+        }
+
+        store.set(PARAMETERS_KEY, &data)
+    }
+
+    /// Restores the parameter values stored by `save`
+    ///
+    /// Values of parameters that are no longer registered, or whose type or range has changed
+    /// since, are skipped, so stored values never break newer firmware.
+    pub fn load<S: Storage>(&mut self, store: &mut KeyValueStore<S>) -> Result<(), StorageError> {
+        let data = match store.get(PARAMETERS_KEY)? {
+            Some(data) => data,
+            None => return Ok(()),
+        };
+
+        let mut offset = 0;
+
+        while offset + 2 < data.len() {
+            let index = u16::from_be_bytes([data[offset], data[offset + 1]]);
+            let value_data = &data[offset + 2..];
+
+            let value_len = match ParameterValue::serialized_len(value_data) {
+                Ok(value_len) if value_len <= value_data.len() => value_len,
+                _ => break,
+            };
+
+            if let Ok(value) = ParameterValue::deserialize(&value_data[..value_len]) {
+                let _ = self.set(index, value);
+            }
+
+            offset += 2 + value_len;
+        }
+
+        Ok(())
+    }
+
+    /// Handles parameter events addressed to this device, returning the reply to send
+    pub fn handle_packet(&mut self, packet: &Packet) -> Option<Packet> {
+        if packet.device_address != self.device_address {
+            return None;
+        }
+
+        if let Ok(event) = ParameterReadEvent::try_from_packet(packet) {
+            Some(match self.get(event.index) {
+                Some(value) => self.report(event.transmitter_address, event.index, value),
+                None => self.error(
+                    event.transmitter_address,
+                    PARAMETER_READ_EVENT_CODE,
+                    event.index,
+                    ParameterError::UnknownIndex,
+                ),
+            })
+        } else if let Ok(event) = ParameterWriteEvent::try_from_packet(packet) {
+            Some(match self.write(event.index, event.value) {
+                Ok(()) => self.report(event.transmitter_address, event.index, event.value),
+                Err(err) => self.error(
+                    event.transmitter_address,
+                    PARAMETER_WRITE_EVENT_CODE,
+                    event.index,
+                    err,
+                ),
+            })
+        } else if let Ok(event) = ParameterDescribeEvent::try_from_packet(packet) {
+            Some(match self.parameters.get(&event.index) {
+                Some(parameter) => ParameterDescriptionEvent {
+                    receiver_address: event.transmitter_address,
+                    device_address: self.device_address,
+                    index: event.index,
+                    access: parameter.access,
+                    min: parameter.min,
+                    max: parameter.max,
+                }
+                .to_packet(),
+                None => self.error(
+                    event.transmitter_address,
+                    PARAMETER_DESCRIBE_EVENT_CODE,
+                    event.index,
+                    ParameterError::UnknownIndex,
+                ),
+            })
+        } else {
+            None
+        }
+    }
+
+    fn write(&mut self, index: u16, value: ParameterValue) -> Result<(), ParameterError> {
+        match self.parameters.get(&index) {
+            None => return Err(ParameterError::UnknownIndex),
+            Some(parameter) if parameter.access == ParameterAccess::ReadOnly => {
+                return Err(ParameterError::ReadOnly)
+            }
+            Some(_) => {}
+        }
+
+        self.set(index, value)?;
+        self.written.insert(index);
+
+        Ok(())
+    }
+
+    fn report(&self, receiver_address: u16, index: u16, value: ParameterValue) -> Packet {
+        ParameterReportEvent {
+            receiver_address,
+            device_address: self.device_address,
+            index,
+            value,
+        }
+        .to_packet()
+    }
+
+    fn error(
+        &self,
+        receiver_address: u16,
+        event_code: u16,
+        index: u16,
+        error: ParameterError,
+    ) -> Packet {
+        ErrorEvent {
+            receiver_address,
+            transmitter_address: self.device_address,
+            event_code,
+            error_code: error.error_code(),
+            detail: index.to_be_bytes().to_vec(),
+        }
+        .to_packet()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::storage::memory::MemoryStorage;
+
+    const DEVICE_ADDRESS: u16 = 0x0123;
+    const DEBOUNCE_TIME: u16 = 0x0001;
+    const FIRMWARE_VERSION: u16 = 0x0002;
+
+    fn dictionary() -> ObjectDictionary {
+        let mut dictionary = ObjectDictionary::new(DEVICE_ADDRESS);
+
+        dictionary
+            .register(
+                DEBOUNCE_TIME,
+                ParameterValue::U16(50),
+                ParameterAccess::ReadWrite,
+                ParameterValue::U16(10),
+                ParameterValue::U16(1000),
+            )
+            .unwrap();
+        dictionary
+            .register(
+                FIRMWARE_VERSION,
+                ParameterValue::U32(3),
+                ParameterAccess::ReadOnly,
+                ParameterValue::U32(0),
+                ParameterValue::U32(u32::MAX),
+            )
+            .unwrap();
+
+        dictionary
+    }
+
+    fn error(reply: Option<Packet>) -> (u16, ErrorCode, Vec<u8>) {
+        let event = ErrorEvent::try_from_packet(&reply.unwrap()).unwrap();
+
+        (event.event_code, event.error_code, event.detail)
+    }
+
+    #[test]
+    fn register_test() {
+        let mut dictionary = dictionary();
+
+        assert_eq!(
+            dictionary.register(
+                DEBOUNCE_TIME,
+                ParameterValue::Bool(false),
+                ParameterAccess::ReadWrite,
+                ParameterValue::Bool(false),
+                ParameterValue::Bool(true),
+            ),
+            Err(ParameterError::AlreadyRegistered)
+        );
+        assert_eq!(
+            dictionary.register(
+                0x0003,
+                ParameterValue::U8(0),
+                ParameterAccess::ReadWrite,
+                ParameterValue::U16(0),
+                ParameterValue::U8(10),
+            ),
+            Err(ParameterError::WrongType)
+        );
+        assert_eq!(
+            dictionary.register(
+                0x0003,
+                ParameterValue::I16(-20),
+                ParameterAccess::ReadWrite,
+                ParameterValue::I16(-10),
+                ParameterValue::I16(10),
+            ),
+            Err(ParameterError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn set_test() {
+        let mut dictionary = dictionary();
+
+        assert_eq!(
+            dictionary.set(FIRMWARE_VERSION, ParameterValue::U32(4)),
+            Ok(())
+        );
+        assert_eq!(
+            dictionary.get(FIRMWARE_VERSION),
+            Some(ParameterValue::U32(4))
+        );
+        assert_eq!(
+            dictionary.set(DEBOUNCE_TIME, ParameterValue::U16(5)),
+            Err(ParameterError::OutOfRange)
+        );
+        assert_eq!(
+            dictionary.set(DEBOUNCE_TIME, ParameterValue::U32(50)),
+            Err(ParameterError::WrongType)
+        );
+        assert_eq!(
+            dictionary.set(0x0003, ParameterValue::U8(0)),
+            Err(ParameterError::UnknownIndex)
+        );
+        assert_eq!(dictionary.take_written(), vec![]);
+    }
+
+    #[test]
+    fn read_test() {
+        let mut dictionary = dictionary();
+
+        let read = |index| {
+            ParameterReadEvent {
+                device_address: DEVICE_ADDRESS,
+                transmitter_address: 0xabab,
+                index,
+            }
+            .to_packet()
+        };
+
+        assert_eq!(
+            dictionary.handle_packet(&read(DEBOUNCE_TIME)),
+            Some(
+                ParameterReportEvent {
+                    receiver_address: 0xabab,
+                    device_address: DEVICE_ADDRESS,
+                    index: DEBOUNCE_TIME,
+                    value: ParameterValue::U16(50),
+                }
+                .to_packet()
+            )
+        );
+        assert_eq!(
+            error(dictionary.handle_packet(&read(0x0003))),
+            (
+                PARAMETER_READ_EVENT_CODE,
+                ErrorCode::InvalidValue,
+                vec![0x00, 0x03]
+            )
+        );
+    }
+
+    #[test]
+    fn write_test() {
+        let mut dictionary = dictionary();
+
+        let write = |index, value| {
+            ParameterWriteEvent {
+                device_address: DEVICE_ADDRESS,
+                transmitter_address: 0xabab,
+                index,
+                value,
+            }
+            .to_packet()
+        };
+
+        assert_eq!(
+            dictionary.handle_packet(&write(DEBOUNCE_TIME, ParameterValue::U16(100))),
+            Some(
+                ParameterReportEvent {
+                    receiver_address: 0xabab,
+                    device_address: DEVICE_ADDRESS,
+                    index: DEBOUNCE_TIME,
+                    value: ParameterValue::U16(100),
+                }
+                .to_packet()
+            )
+        );
+        assert_eq!(
+            dictionary.get(DEBOUNCE_TIME),
+            Some(ParameterValue::U16(100))
+        );
+        assert_eq!(dictionary.take_written(), vec![DEBOUNCE_TIME]);
+        assert_eq!(dictionary.take_written(), vec![]);
+
+        assert_eq!(
+            error(dictionary.handle_packet(&write(DEBOUNCE_TIME, ParameterValue::U16(2000)))),
+            (
+                PARAMETER_WRITE_EVENT_CODE,
+                ErrorCode::InvalidValue,
+                vec![0x00, 0x01]
+            )
+        );
+        assert_eq!(
+            error(dictionary.handle_packet(&write(FIRMWARE_VERSION, ParameterValue::U32(4)))),
+            (
+                PARAMETER_WRITE_EVENT_CODE,
+                ErrorCode::InvalidState,
+                vec![0x00, 0x02]
+            )
+        );
+        assert_eq!(
+            dictionary.get(FIRMWARE_VERSION),
+            Some(ParameterValue::U32(3))
+        );
+        assert_eq!(dictionary.take_written(), vec![]);
+    }
+
+    #[test]
+    fn describe_test() {
+        let mut dictionary = dictionary();
+
+        let describe = ParameterDescribeEvent {
+            device_address: DEVICE_ADDRESS,
+            transmitter_address: 0xabab,
+            index: DEBOUNCE_TIME,
+        };
+
+        assert_eq!(
+            dictionary.handle_packet(&describe.to_packet()),
+            Some(
+                ParameterDescriptionEvent {
+                    receiver_address: 0xabab,
+                    device_address: DEVICE_ADDRESS,
+                    index: DEBOUNCE_TIME,
+                    access: ParameterAccess::ReadWrite,
+                    min: ParameterValue::U16(10),
+                    max: ParameterValue::U16(1000),
+                }
+                .to_packet()
+            )
+        );
+
+        // Packets for other devices are ignored
+        let mut packet = describe.to_packet();
+        packet.device_address = 0x0456;
+
+        assert_eq!(dictionary.handle_packet(&packet), None);
+    }
+
+    #[test]
+    fn save_load_test() {
+        let mut store = KeyValueStore::mount(MemoryStorage::new(2, 256, 1)).unwrap();
+
+        let mut dictionary = dictionary();
+        dictionary
+            .set(DEBOUNCE_TIME, ParameterValue::U16(200))
+            .unwrap();
+        dictionary
+            .set(FIRMWARE_VERSION, ParameterValue::U32(4))
+            .unwrap();
+        dictionary.save(&mut store).unwrap();
+
+        assert_eq!(
+            store.get(PARAMETERS_KEY).unwrap(),
+            Some(vec![
+                0x00, // index
+                0x01, // index
+                0x02, // value type
+                0x00, // value
+                0xc8, // value
+            ])
+        );
+
+        let mut dictionary = self::dictionary();
+        dictionary.load(&mut store).unwrap();
+
+        assert_eq!(
+            dictionary.get(DEBOUNCE_TIME),
+            Some(ParameterValue::U16(200))
+        );
+        assert_eq!(
+            dictionary.get(FIRMWARE_VERSION),
+            Some(ParameterValue::U32(3))
+        );
+
+        // The range of the parameter shrank after a firmware update
+        let mut dictionary = ObjectDictionary::new(DEVICE_ADDRESS);
+        dictionary
+            .register(
+                DEBOUNCE_TIME,
+                ParameterValue::U16(50),
+                ParameterAccess::ReadWrite,
+                ParameterValue::U16(10),
+                ParameterValue::U16(100),
+            )
+            .unwrap();
+        dictionary.load(&mut store).unwrap();
+
+        assert_eq!(dictionary.get(DEBOUNCE_TIME), Some(ParameterValue::U16(50)));
+    }
+}
@@ -14,6 +14,15 @@ pub struct Packet {
 }
 
 impl Packet {
+    /// Returns the amount of frames `to_frames` splits the packet into
+    pub fn frame_count(&self) -> usize {
+        if self.data.len() <= 8 {
+            1
+        } else {
+            (self.data.len() - 1) / 7 + 1
+        }
+    }
+
     pub fn to_frames(&self) -> Vec<Frame> {
         if self.data.len() <= 8 {
             let mut data = [0; 8];
@@ -33,7 +42,7 @@ impl Packet {
             }];
         }
 
-        let frame_count = (self.data.len() - 1) / 7 + 1;
+        let frame_count = self.frame_count();
         let mut frames = vec![];
 
         for i in 0..frame_count {
@@ -178,8 +187,13 @@ impl PacketBuilder {
         for frame in self.frames.iter() {
             let start_index = if frame.multi_frame_flag { 1 } else { 0 };
 
-            for i in start_index..frame.data_len {
-                data.push(frame.data[i as usize]);
+            for byte in frame
+                .data
+                .iter()
+                .take(frame.data_len as usize)
+                .skip(start_index)
+            {
+                data.push(*byte);
             }
         }
 
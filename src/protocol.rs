@@ -1,26 +1,60 @@
 #![allow(mutable_transmutes)]
 use alloc::boxed::Box;
-use alloc::collections::BTreeMap;
+use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::vec;
 use alloc::vec::Vec;
+use core::convert::TryInto;
 use core::mem::transmute;
 
 use crate::convert_packet::ConvertPacket;
+use crate::event::diagnostics::*;
+use crate::event::error::{ErrorCode, ErrorEvent};
+use crate::event::event_code::*;
+use crate::event::group::*;
 use crate::interface::*;
 use crate::packet::Packet;
 
 pub const BROADCAST_ADDRESS: u16 = 0xffff;
+/// First address of the range reserved for groups
+pub const GROUP_ADDRESS_START: u16 = 0xff00;
+/// Last address of the range reserved for groups
+pub const GROUP_ADDRESS_END: u16 = 0xfffe;
+
+/// Returns whether the address is in the range reserved for groups
+pub fn is_group_address(address: u16) -> bool {
+    (GROUP_ADDRESS_START..=GROUP_ADDRESS_END).contains(&address)
+}
 
 #[derive(Debug)]
 pub enum ProtocolError {
     InterfaceError(InterfaceError),
     NoSuchHandler,
     PacketTimeout,
+    /// The packet is too short to carry an event code and a transmitter address
+    MalformedPacket,
+    /// The other device replied to an exchanged packet with an error
+    ErrorReceived(ErrorEvent),
+    /// The address is outside of the range reserved for groups
+    InvalidGroupAddress,
+}
+
+/// Counters of the packets passing through a `Protocol`, all of which wrap around
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct ProtocolStatistics {
+    pub packets_received: u32,
+    /// Received packets addressed to this device, to the broadcast address or to one of its groups
+    pub packets_handled: u32,
+    pub packets_sent: u32,
+    pub handler_invocations: u32,
+    /// Errors returned by the interface while receiving or sending
+    pub interface_errors: u32,
 }
 
 pub struct Protocol<'a, I: Interface> {
     device_address: u16,
     interface: I,
+    groups: BTreeSet<u16>,
+    statistics: ProtocolStatistics,
     #[cfg(not(feature = "send"))]
     handlers: BTreeMap<u32, (Box<dyn FnMut(&Packet, &mut Self) + 'a>, bool)>,
     #[cfg(feature = "send")]
@@ -32,22 +66,37 @@ impl<'a, I: Interface> Protocol<'a, I> {
         Protocol {
             device_address,
             interface,
+            groups: BTreeSet::new(),
+            statistics: ProtocolStatistics::default(),
             handlers: BTreeMap::new(),
         }
     }
 
     pub fn tick(&mut self) -> Result<(), ProtocolError> {
-        match self.interface.try_get_packet() {
+        match self.try_get_packet() {
             Ok(packet) => {
+                let mut result = Ok(());
+
+                // A failed reply must not keep the packet from the handlers, so it is returned
+                // after dispatching
+                if packet.device_address == self.device_address {
+                    result = self
+                        .handle_group_event(&packet)
+                        .and(self.handle_diagnostics_event(&packet));
+                }
+
                 if packet.device_address == self.device_address
                     || packet.device_address == BROADCAST_ADDRESS
+                    || self.groups.contains(&packet.device_address)
                 {
-                    self.handle_packet(&packet, true);
+                    self.statistics.packets_handled =
+                        self.statistics.packets_handled.wrapping_add(1);
+                    self.dispatch_packet(&packet, true);
                 } else {
-                    self.handle_packet(&packet, false);
+                    self.dispatch_packet(&packet, false);
                 }
 
-                Ok(())
+                result
             }
             Err(err) => match err {
                 InterfaceError::NoPacketReceived => Ok(()),
@@ -58,17 +107,103 @@ impl<'a, I: Interface> Protocol<'a, I> {
 
     pub fn send_packet(&mut self, packet: &Packet) -> Result<(), ProtocolError> {
         if packet.device_address == self.device_address {
-            self.handle_packet(&packet, true);
+            self.dispatch_packet(&packet, true);
 
             if self.device_address != BROADCAST_ADDRESS {
                 return Ok(());
             }
+        } else if self.groups.contains(&packet.device_address) {
+            self.dispatch_packet(packet, true);
         }
 
         match self.interface.try_send_packet(packet) {
-            Ok(_) => Ok(()),
-            Err(err) => Err(ProtocolError::InterfaceError(err)),
+            Ok(_) => {
+                self.statistics.packets_sent = self.statistics.packets_sent.wrapping_add(1);
+
+                Ok(())
+            }
+            Err(err) => {
+                self.statistics.interface_errors = self.statistics.interface_errors.wrapping_add(1);
+
+                Err(ProtocolError::InterfaceError(err))
+            }
+        }
+    }
+
+    pub fn statistics(&self) -> ProtocolStatistics {
+        self.statistics
+    }
+
+    pub fn interface_statistics(&self) -> InterfaceStatistics {
+        self.interface.statistics()
+    }
+
+    /// Resets the counters of the protocol and of its interface
+    pub fn reset_statistics(&mut self) {
+        self.statistics = ProtocolStatistics::default();
+        self.interface.reset_statistics();
+    }
+
+    /// Sends an error reply for an event with the given event code
+    pub fn send_error(
+        &mut self,
+        receiver_address: u16,
+        event_code: u16,
+        error_code: ErrorCode,
+        detail: Vec<u8>,
+    ) -> Result<(), ProtocolError> {
+        let event = ErrorEvent {
+            receiver_address,
+            transmitter_address: self.device_address,
+            event_code,
+            error_code,
+            detail,
+        };
+
+        self.send_packet(&event.to_packet())
+    }
+
+    /// Replies with an error to the transmitter of a failed request
+    pub fn reply_with_error(
+        &mut self,
+        request: &Packet,
+        error_code: ErrorCode,
+        detail: Vec<u8>,
+    ) -> Result<(), ProtocolError> {
+        if request.data.len() < 4 {
+            return Err(ProtocolError::MalformedPacket);
+        }
+
+        let event_code = u16::from_be_bytes(request.data[0..=1].try_into().unwrap());
+        let transmitter_address = u16::from_be_bytes(request.data[2..=3].try_into().unwrap());
+
+        self.send_error(transmitter_address, event_code, error_code, detail)
+    }
+
+    /// Makes this device a member of a group, so packets addressed to it get handled
+    pub fn join_group(&mut self, group_address: u16) -> Result<(), ProtocolError> {
+        if !is_group_address(group_address) {
+            return Err(ProtocolError::InvalidGroupAddress);
         }
+
+        self.groups.insert(group_address);
+
+        Ok(())
+    }
+
+    pub fn leave_group(&mut self, group_address: u16) -> Result<(), ProtocolError> {
+        match self.groups.remove(&group_address) {
+            true => Ok(()),
+            false => Err(ProtocolError::InvalidGroupAddress),
+        }
+    }
+
+    pub fn is_group_member(&self, group_address: u16) -> bool {
+        self.groups.contains(&group_address)
+    }
+
+    pub fn groups(&self) -> impl Iterator<Item = &u16> {
+        self.groups.iter()
     }
 
     pub fn add_packet_handler<'s>(
@@ -104,7 +239,7 @@ impl<'a, I: Interface> Protocol<'a, I> {
         wait_closure();
 
         loop {
-            match self.interface.try_get_packet() {
+            match self.try_get_packet() {
                 Ok(received_packet) => {
                     if capture_all_addresses
                         || received_packet.device_address == self.device_address
@@ -114,6 +249,14 @@ impl<'a, I: Interface> Protocol<'a, I> {
                             return Ok(received_event);
                         }
                     }
+
+                    if received_packet.device_address == self.device_address
+                        && received_packet.data.get(0..=1) == packet.data.get(0..=1)
+                    {
+                        if let Ok(error) = ErrorEvent::try_from_packet(&received_packet) {
+                            return Err(ProtocolError::ErrorReceived(error));
+                        }
+                    }
                 }
                 Err(err) => match err {
                     InterfaceError::NoPacketReceived => break,
@@ -138,7 +281,7 @@ impl<'a, I: Interface> Protocol<'a, I> {
         wait_closure();
 
         loop {
-            match self.interface.try_get_packet() {
+            match self.try_get_packet() {
                 Ok(received_packet) => {
                     if capture_all_addresses
                         || received_packet.device_address == self.device_address
@@ -159,14 +302,97 @@ impl<'a, I: Interface> Protocol<'a, I> {
         return Ok(events);
     }
 
-    fn handle_packet(&self, packet: &Packet, owned_address: bool) {
+    /// Applies group membership events addressed to this device
+    ///
+    /// The packet is still passed to the handlers afterwards, so firmware can persist the groups.
+    fn handle_group_event(&mut self, packet: &Packet) -> Result<(), ProtocolError> {
+        if let Ok(event) = GroupAddEvent::try_from_packet(packet) {
+            if self.join_group(event.group_address).is_err() {
+                self.send_error(
+                    event.transmitter_address,
+                    GROUP_ADD_EVENT_CODE,
+                    ErrorCode::InvalidValue,
+                    vec![],
+                )?;
+            }
+        } else if let Ok(event) = GroupRemoveEvent::try_from_packet(packet) {
+            if self.leave_group(event.group_address).is_err() {
+                self.send_error(
+                    event.transmitter_address,
+                    GROUP_REMOVE_EVENT_CODE,
+                    ErrorCode::InvalidValue,
+                    vec![],
+                )?;
+            }
+        } else if let Ok(event) = GroupGetEvent::try_from_packet(packet) {
+            let report = GroupReportEvent {
+                receiver_address: event.transmitter_address,
+                device_address: self.device_address,
+                group_addresses: self.groups.iter().copied().collect(),
+            };
+
+            self.send_packet(&report.to_packet())?;
+        }
+
+        Ok(())
+    }
+
+    /// Answers a `DiagnosticsGetEvent` addressed to this device with its statistics
+    fn handle_diagnostics_event(&mut self, packet: &Packet) -> Result<(), ProtocolError> {
+        if let Ok(event) = DiagnosticsGetEvent::try_from_packet(packet) {
+            let report = DiagnosticsReportEvent {
+                receiver_address: event.transmitter_address,
+                device_address: self.device_address,
+                interface_statistics: self.interface.statistics(),
+                protocol_statistics: self.statistics,
+            };
+
+            self.send_packet(&report.to_packet())?;
+        }
+
+        Ok(())
+    }
+
+    /// Receives a packet from the interface, counting it or the error
+    fn try_get_packet(&mut self) -> Result<Packet, InterfaceError> {
+        let result = self.interface.try_get_packet();
+
+        match result {
+            Ok(_) => {
+                self.statistics.packets_received = self.statistics.packets_received.wrapping_add(1);
+            }
+            Err(InterfaceError::NoPacketReceived) => {}
+            Err(_) => {
+                self.statistics.interface_errors = self.statistics.interface_errors.wrapping_add(1);
+            }
+        }
+
+        result
+    }
+
+    /// Passes a packet to the handlers, counting the invocations
+    fn dispatch_packet(&mut self, packet: &Packet, owned_address: bool) {
+        let invocations = self.handle_packet(packet, owned_address);
+
+        self.statistics.handler_invocations = self
+            .statistics
+            .handler_invocations
+            .wrapping_add(invocations);
+    }
+
+    fn handle_packet(&self, packet: &Packet, owned_address: bool) -> u32 {
+        let mut invocations = 0;
+
         unsafe {
             for handler in transmute::<&Self, &mut Self>(self).handlers.values_mut() {
                 if owned_address || handler.1 {
                     handler.0(packet, transmute(self));
+                    invocations += 1;
                 }
             }
         }
+
+        invocations
     }
 
     fn get_next_handler_id(&self) -> u32 {
@@ -181,3 +407,250 @@ impl<'a, I: Interface> Protocol<'a, I> {
         return first_available_id;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::collections::VecDeque;
+    use alloc::rc::Rc;
+    use core::cell::RefCell;
+
+    use crate::event::button::ButtonPressedEvent;
+    use crate::interface::reliable::ReliableError;
+
+    const DEVICE_ADDRESS: u16 = 0x0123;
+
+    struct MockInterface {
+        received: VecDeque<Packet>,
+        sent: Vec<Packet>,
+        fail_sends: bool,
+    }
+
+    impl Interface for MockInterface {
+        fn try_get_packet(&mut self) -> Result<Packet, InterfaceError> {
+            self.received
+                .pop_front()
+                .ok_or(InterfaceError::NoPacketReceived)
+        }
+
+        fn try_send_packet(&mut self, packet: &Packet) -> Result<(), InterfaceError> {
+            if self.fail_sends {
+                return Err(InterfaceError::ReliableError(ReliableError::QueueFull));
+            }
+
+            self.sent.push(packet.clone());
+
+            Ok(())
+        }
+    }
+
+    fn protocol<'a>(received: Vec<Packet>) -> Protocol<'a, MockInterface> {
+        Protocol::new(
+            DEVICE_ADDRESS,
+            MockInterface {
+                received: received.into_iter().collect(),
+                sent: vec![],
+                fail_sends: false,
+            },
+        )
+    }
+
+    fn button_packet(receiver_address: u16) -> Packet {
+        ButtonPressedEvent {
+            receiver_address,
+            button_address: 0xabab,
+            index: 0x00,
+        }
+        .to_packet()
+    }
+
+    #[test]
+    fn is_group_address_test() {
+        assert!(!is_group_address(0xfeff));
+        assert!(is_group_address(0xff00));
+        assert!(is_group_address(0xfffe));
+        assert!(!is_group_address(BROADCAST_ADDRESS));
+    }
+
+    #[test]
+    fn join_group_test() {
+        let mut protocol = protocol(vec![]);
+
+        assert!(protocol.join_group(0xff12).is_ok());
+        assert!(matches!(
+            protocol.join_group(0x0456),
+            Err(ProtocolError::InvalidGroupAddress)
+        ));
+        assert!(protocol.is_group_member(0xff12));
+        assert!(protocol.leave_group(0xff12).is_ok());
+        assert!(matches!(
+            protocol.leave_group(0xff12),
+            Err(ProtocolError::InvalidGroupAddress)
+        ));
+        assert_eq!(protocol.groups().count(), 0);
+    }
+
+    #[test]
+    fn group_delivery_test() {
+        let received = Rc::new(RefCell::new(vec![]));
+        let mut protocol = protocol(vec![
+            GroupAddEvent {
+                device_address: DEVICE_ADDRESS,
+                transmitter_address: 0xabab,
+                group_address: 0xff12,
+            }
+            .to_packet(),
+            button_packet(0xff12),
+            button_packet(0xff34),
+        ]);
+
+        let handler_received = Rc::clone(&received);
+        protocol
+            .add_packet_handler(
+                Box::new(
+                    move |packet: &Packet, _protocol: &mut Protocol<MockInterface>| {
+                        handler_received.borrow_mut().push(packet.device_address);
+                    },
+                ),
+                false,
+            )
+            .unwrap();
+
+        for _ in 0..3 {
+            protocol.tick().unwrap();
+        }
+
+        assert_eq!(*received.borrow(), vec![DEVICE_ADDRESS, 0xff12]);
+        assert!(protocol.is_group_member(0xff12));
+
+        // Packets sent to a group the device is a member of are handled locally as well
+        protocol.send_packet(&button_packet(0xff12)).unwrap();
+
+        assert_eq!(*received.borrow(), vec![DEVICE_ADDRESS, 0xff12, 0xff12]);
+        assert_eq!(protocol.interface.sent, vec![button_packet(0xff12)]);
+    }
+
+    #[test]
+    fn group_events_test() {
+        let mut protocol = protocol(vec![
+            GroupAddEvent {
+                device_address: DEVICE_ADDRESS,
+                transmitter_address: 0xabab,
+                group_address: 0x0456,
+            }
+            .to_packet(),
+            GroupGetEvent {
+                device_address: DEVICE_ADDRESS,
+                transmitter_address: 0xabab,
+            }
+            .to_packet(),
+        ]);
+
+        protocol.join_group(0xff34).unwrap();
+        protocol.tick().unwrap();
+        protocol.tick().unwrap();
+
+        let error = ErrorEvent::try_from_packet(&protocol.interface.sent[0]).unwrap();
+
+        assert_eq!(error.receiver_address, 0xabab);
+        assert_eq!(error.event_code, GROUP_ADD_EVENT_CODE);
+        assert_eq!(error.error_code, ErrorCode::InvalidValue);
+        assert_eq!(
+            GroupReportEvent::try_from_packet(&protocol.interface.sent[1]),
+            Ok(GroupReportEvent {
+                receiver_address: 0xabab,
+                device_address: DEVICE_ADDRESS,
+                group_addresses: vec![0xff34],
+            })
+        );
+    }
+
+    #[test]
+    fn group_event_reply_failed_test() {
+        let received = Rc::new(RefCell::new(0));
+        let mut protocol = protocol(vec![GroupGetEvent {
+            device_address: DEVICE_ADDRESS,
+            transmitter_address: 0xabab,
+        }
+        .to_packet()]);
+        protocol.interface.fail_sends = true;
+
+        let handler_received = Rc::clone(&received);
+        protocol
+            .add_packet_handler(
+                Box::new(
+                    move |_packet: &Packet, _protocol: &mut Protocol<MockInterface>| {
+                        *handler_received.borrow_mut() += 1;
+                    },
+                ),
+                false,
+            )
+            .unwrap();
+
+        assert!(matches!(
+            protocol.tick(),
+            Err(ProtocolError::InterfaceError(
+                InterfaceError::ReliableError(ReliableError::QueueFull)
+            ))
+        ));
+        assert_eq!(*received.borrow(), 1);
+        assert_eq!(protocol.statistics().interface_errors, 1);
+    }
+
+    #[test]
+    fn diagnostics_test() {
+        let mut protocol = protocol(vec![
+            button_packet(DEVICE_ADDRESS),
+            button_packet(0x0456),
+            DiagnosticsGetEvent {
+                device_address: DEVICE_ADDRESS,
+                transmitter_address: 0xabab,
+            }
+            .to_packet(),
+        ]);
+
+        protocol
+            .add_packet_handler(
+                Box::new(|_packet: &Packet, _protocol: &mut Protocol<MockInterface>| {}),
+                false,
+            )
+            .unwrap();
+
+        protocol.tick().unwrap();
+        protocol.tick().unwrap();
+        protocol.send_packet(&button_packet(0x0789)).unwrap();
+        protocol.tick().unwrap();
+        protocol.tick().unwrap();
+
+        let report = DiagnosticsReportEvent::try_from_packet(&protocol.interface.sent[1]).unwrap();
+
+        assert_eq!(report.receiver_address, 0xabab);
+        assert_eq!(report.device_address, DEVICE_ADDRESS);
+        assert_eq!(report.interface_statistics, InterfaceStatistics::default());
+        assert_eq!(
+            report.protocol_statistics,
+            ProtocolStatistics {
+                packets_received: 3,
+                packets_handled: 1,
+                packets_sent: 1,
+                handler_invocations: 1,
+                interface_errors: 0,
+            }
+        );
+        assert_eq!(
+            protocol.statistics(),
+            ProtocolStatistics {
+                packets_received: 3,
+                packets_handled: 2,
+                packets_sent: 2,
+                handler_invocations: 2,
+                interface_errors: 0,
+            }
+        );
+
+        protocol.reset_statistics();
+
+        assert_eq!(protocol.statistics(), ProtocolStatistics::default());
+    }
+}
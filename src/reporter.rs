@@ -0,0 +1,292 @@
+use crate::event::sensor::{SensorKind, SensorReadingEvent, SensorUnit};
+
+/// Settings of a `ChangeReporter`, times in milliseconds
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ReporterConfig {
+    /// Smallest change from the last reported value that gets reported
+    pub hysteresis: u32,
+    /// Shortest time between two reports
+    pub min_interval: u32,
+    /// Longest time between two reports, the value is reported again after it even if it didn't
+    /// change. 0 disables periodic reports.
+    pub max_interval: u32,
+}
+
+impl Default for ReporterConfig {
+    fn default() -> Self {
+        ReporterConfig {
+            hysteresis: 1,
+            min_interval: 100,
+            max_interval: 0,
+        }
+    }
+}
+
+/// Decides when an absolute value (like an analog input or a sensor reading) has to be reported
+///
+/// Values are reported when they differ from the last reported value by at least the hysteresis,
+/// but no more often than the minimum interval allows. Changes held back by the rate limit are
+/// reported by the first update after the interval has passed. Times are in milliseconds and may
+/// wrap around.
+pub struct ChangeReporter {
+    config: ReporterConfig,
+    last_report: Option<(i32, u32)>,
+}
+
+impl ChangeReporter {
+    pub fn new(config: ReporterConfig) -> Self {
+        ChangeReporter {
+            config,
+            last_report: None,
+        }
+    }
+
+    pub fn config(&self) -> ReporterConfig {
+        self.config
+    }
+
+    /// Returns the last reported value
+    pub fn last_value(&self) -> Option<i32> {
+        self.last_report.map(|(value, _)| value)
+    }
+
+    /// Takes a new sample, returning it if it should be reported
+    pub fn update(&mut self, value: i32, now: u32) -> Option<i32> {
+        let should_report = match self.last_report {
+            None => true,
+            Some((last_value, reported_at)) => {
+                let elapsed = now.wrapping_sub(reported_at);
+                let change = (value as i64 - last_value as i64).unsigned_abs();
+
+                if elapsed < self.config.min_interval {
+                    false
+                } else if self.config.max_interval != 0 && elapsed >= self.config.max_interval {
+                    true
+                } else {
+                    change != 0 && change >= self.config.hysteresis as u64
+                }
+            }
+        };
+
+        if should_report {
+            self.last_report = Some((value, now));
+
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Forgets the last reported value, so the next sample gets reported right away
+    pub fn reset(&mut self) {
+        self.last_report = None;
+    }
+}
+
+/// Collects relative changes (like rotary encoder steps) and reports their sum at a limited rate
+pub struct DeltaAccumulator {
+    min_interval: u32,
+    delta: i16,
+    reported_at: Option<u32>,
+}
+
+impl DeltaAccumulator {
+    pub fn new(min_interval: u32) -> Self {
+        DeltaAccumulator {
+            min_interval,
+            delta: 0,
+            reported_at: None,
+        }
+    }
+
+    /// Adds a change, saturating at the limits of `i16`
+    pub fn add(&mut self, delta: i16) {
+        self.delta = self.delta.saturating_add(delta);
+    }
+
+    /// Returns the sum of all changes since the last report if it is time to report them
+    pub fn take(&mut self, now: u32) -> Option<i16> {
+        if self.delta == 0 {
+            return None;
+        }
+
+        if let Some(reported_at) = self.reported_at {
+            if now.wrapping_sub(reported_at) < self.min_interval {
+                return None;
+            }
+        }
+
+        let delta = self.delta;
+        self.delta = 0;
+        self.reported_at = Some(now);
+
+        Some(delta)
+    }
+}
+
+/// Reports the readings of a single sensor channel as `SensorReadingEvent`s
+///
+/// Readings are given as mantissas of the configured exponent, so the hysteresis of the config is
+/// in the same fixed-point steps (a hysteresis of 50 at an exponent of -2 is 0.5 units).
+pub struct SensorReporter {
+    sensor_address: u16,
+    index: u8,
+    kind: SensorKind,
+    unit: SensorUnit,
+    exponent: i8,
+    reporter: ChangeReporter,
+}
+
+impl SensorReporter {
+    pub fn new(
+        sensor_address: u16,
+        index: u8,
+        kind: SensorKind,
+        unit: SensorUnit,
+        exponent: i8,
+        config: ReporterConfig,
+    ) -> Self {
+        SensorReporter {
+            sensor_address,
+            index,
+            kind,
+            unit,
+            exponent,
+            reporter: ChangeReporter::new(config),
+        }
+    }
+
+    /// Returns the last reported reading
+    pub fn last_value(&self) -> Option<i32> {
+        self.reporter.last_value()
+    }
+
+    /// Takes a new reading, returning the event to send to `receiver_address` if it should be
+    /// reported
+    pub fn update(
+        &mut self,
+        receiver_address: u16,
+        value: i32,
+        now: u32,
+    ) -> Option<SensorReadingEvent> {
+        self.reporter
+            .update(value, now)
+            .map(|value| SensorReadingEvent {
+                receiver_address,
+                sensor_address: self.sensor_address,
+                index: self.index,
+                kind: self.kind,
+                unit: self.unit,
+                exponent: self.exponent,
+                value,
+            })
+    }
+
+    /// Forgets the last reported reading, so the next one gets reported right away
+    pub fn reset(&mut self) {
+        self.reporter.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn change_reporter_test() {
+        let mut reporter = ChangeReporter::new(ReporterConfig {
+            hysteresis: 10,
+            min_interval: 100,
+            max_interval: 0,
+        });
+
+        assert_eq!(reporter.update(500, 0), Some(500));
+        // Rate limited
+        assert_eq!(reporter.update(600, 50), None);
+        assert_eq!(reporter.update(600, 100), Some(600));
+        // Below the hysteresis
+        assert_eq!(reporter.update(609, 300), None);
+        assert_eq!(reporter.update(591, 400), None);
+        assert_eq!(reporter.update(590, 500), Some(590));
+        assert_eq!(reporter.last_value(), Some(590));
+        assert_eq!(reporter.update(590, 100_000), None);
+    }
+
+    #[test]
+    fn change_reporter_max_interval_test() {
+        let mut reporter = ChangeReporter::new(ReporterConfig {
+            hysteresis: 10,
+            min_interval: 100,
+            max_interval: 1000,
+        });
+
+        assert_eq!(reporter.update(-500, 0), Some(-500));
+        assert_eq!(reporter.update(-500, 999), None);
+        assert_eq!(reporter.update(-501, 1000), Some(-501));
+
+        reporter.reset();
+
+        assert_eq!(reporter.update(-501, 1001), Some(-501));
+    }
+
+    #[test]
+    fn delta_accumulator_test() {
+        let mut accumulator = DeltaAccumulator::new(100);
+
+        assert_eq!(accumulator.take(0), None);
+
+        accumulator.add(1);
+        accumulator.add(2);
+        assert_eq!(accumulator.take(0), Some(3));
+
+        accumulator.add(-1);
+        accumulator.add(-1);
+        assert_eq!(accumulator.take(50), None);
+        assert_eq!(accumulator.take(100), Some(-2));
+
+        accumulator.add(i16::MAX);
+        accumulator.add(1);
+        assert_eq!(accumulator.take(200), Some(i16::MAX));
+    }
+
+    #[test]
+    fn sensor_reporter_test() {
+        let mut reporter = SensorReporter::new(
+            0x0123,
+            0x01,
+            SensorKind::Temperature,
+            SensorUnit::Celsius,
+            -2,
+            ReporterConfig {
+                hysteresis: 50,
+                min_interval: 1000,
+                max_interval: 60_000,
+            },
+        );
+
+        assert_eq!(
+            reporter.update(0xabab, 2150, 0),
+            Some(SensorReadingEvent {
+                receiver_address: 0xabab,
+                sensor_address: 0x0123,
+                index: 0x01,
+                kind: SensorKind::Temperature,
+                unit: SensorUnit::Celsius,
+                exponent: -2,
+                value: 2150,
+            })
+        );
+        assert_eq!(reporter.update(0xabab, 2190, 5000), None);
+        assert_eq!(
+            reporter.update(0xabab, 2200, 6000).map(|event| event.value),
+            Some(2200)
+        );
+        assert_eq!(
+            reporter
+                .update(0xabab, 2200, 66_000)
+                .map(|event| event.value),
+            Some(2200)
+        );
+        assert_eq!(reporter.last_value(), Some(2200));
+    }
+}
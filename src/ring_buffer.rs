@@ -0,0 +1,176 @@
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// Lock-free single-producer single-consumer byte queue
+///
+/// The buffer is meant to be shared between an interrupt handler (producer) and the main loop
+/// (consumer). One slot is always kept free, so the usable capacity is `N - 1` and `N` has to be
+/// at least 2, which is checked at compile time.
+pub struct RingBuffer<const N: usize> {
+    buffer: UnsafeCell<[u8; N]>,
+    /// Index of the next slot to be written by the producer
+    head: AtomicUsize,
+    /// Index of the next slot to be read by the consumer
+    tail: AtomicUsize,
+    /// Set by the producer whenever a byte had to be dropped because the buffer was full
+    overrun: AtomicBool,
+}
+
+unsafe impl<const N: usize> Sync for RingBuffer<N> {}
+
+impl<const N: usize> RingBuffer<N> {
+    const VALID_SIZE: () = assert!(N >= 2, "a RingBuffer needs at least 2 slots");
+
+    pub const fn new() -> Self {
+        let () = Self::VALID_SIZE;
+
+        RingBuffer {
+            buffer: UnsafeCell::new([0x00; N]),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            overrun: AtomicBool::new(false),
+        }
+    }
+
+    /// Splits the buffer into its producer and consumer halves
+    pub fn split(&mut self) -> (Producer<'_, N>, Consumer<'_, N>) {
+        let ring_buffer: &Self = self;
+
+        (Producer { ring_buffer }, Consumer { ring_buffer })
+    }
+
+    pub fn capacity(&self) -> usize {
+        N - 1
+    }
+
+    pub fn len(&self) -> usize {
+        let head = self.head.load(Ordering::Acquire);
+        let tail = self.tail.load(Ordering::Acquire);
+
+        (head + N - tail) % N
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<const N: usize> Default for RingBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Writing half of a `RingBuffer`, to be owned by the interrupt handler
+pub struct Producer<'a, const N: usize> {
+    ring_buffer: &'a RingBuffer<N>,
+}
+
+impl<'a, const N: usize> Producer<'a, N> {
+    /// Appends a byte to the buffer, giving it back if the buffer is full
+    pub fn push(&mut self, byte: u8) -> Result<(), u8> {
+        let head = self.ring_buffer.head.load(Ordering::Relaxed);
+        let next_head = (head + 1) % N;
+
+        if next_head == self.ring_buffer.tail.load(Ordering::Acquire) {
+            self.ring_buffer.overrun.store(true, Ordering::Release);
+
+            return Err(byte);
+        }
+
+        unsafe {
+            (*self.ring_buffer.buffer.get())[head] = byte;
+        }
+
+        self.ring_buffer.head.store(next_head, Ordering::Release);
+
+        Ok(())
+    }
+}
+
+/// Reading half of a `RingBuffer`, to be owned by the main loop
+pub struct Consumer<'a, const N: usize> {
+    ring_buffer: &'a RingBuffer<N>,
+}
+
+impl<'a, const N: usize> Consumer<'a, N> {
+    /// Removes the oldest byte from the buffer
+    pub fn pop(&mut self) -> Option<u8> {
+        let tail = self.ring_buffer.tail.load(Ordering::Relaxed);
+
+        if tail == self.ring_buffer.head.load(Ordering::Acquire) {
+            return None;
+        }
+
+        let byte = unsafe { (*self.ring_buffer.buffer.get())[tail] };
+
+        self.ring_buffer
+            .tail
+            .store((tail + 1) % N, Ordering::Release);
+
+        Some(byte)
+    }
+
+    /// Returns true if any bytes were dropped since the last call
+    pub fn take_overrun(&mut self) -> bool {
+        self.ring_buffer.overrun.swap(false, Ordering::AcqRel)
+    }
+
+    pub fn len(&self) -> usize {
+        self.ring_buffer.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ring_buffer.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_pop_test() {
+        let mut ring_buffer = RingBuffer::<4>::new();
+        let (mut producer, mut consumer) = ring_buffer.split();
+
+        producer.push(0x01).unwrap();
+        producer.push(0x02).unwrap();
+
+        assert_eq!(consumer.len(), 2);
+        assert_eq!(consumer.pop(), Some(0x01));
+        assert_eq!(consumer.pop(), Some(0x02));
+        assert_eq!(consumer.pop(), None);
+    }
+
+    #[test]
+    fn wrap_around_test() {
+        let mut ring_buffer = RingBuffer::<4>::new();
+        let (mut producer, mut consumer) = ring_buffer.split();
+
+        for i in 0..10 {
+            producer.push(i).unwrap();
+            producer.push(i + 1).unwrap();
+
+            assert_eq!(consumer.pop(), Some(i));
+            assert_eq!(consumer.pop(), Some(i + 1));
+        }
+
+        assert!(consumer.is_empty());
+    }
+
+    #[test]
+    fn overrun_test() {
+        let mut ring_buffer = RingBuffer::<4>::new();
+        let (mut producer, mut consumer) = ring_buffer.split();
+
+        producer.push(0x01).unwrap();
+        producer.push(0x02).unwrap();
+        producer.push(0x03).unwrap();
+
+        assert_eq!(producer.push(0x04), Err(0x04));
+        assert!(consumer.take_overrun());
+        assert!(!consumer.take_overrun());
+        assert_eq!(consumer.pop(), Some(0x01));
+    }
+}
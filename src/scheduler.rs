@@ -0,0 +1,416 @@
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::clock::SyncedClock;
+use crate::convert_packet::ConvertPacket;
+use crate::event::error::{ErrorCode, ErrorEvent};
+use crate::event::event_code::*;
+use crate::event::schedule::*;
+use crate::interface::Interface;
+use crate::packet::Packet;
+use crate::protocol::{Protocol, ProtocolError};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Table of packets to send at wall-clock times
+///
+/// Entries are programmed with `ScheduleAddEvent`, `ScheduleRemoveEvent` and `ScheduleListEvent`
+/// (see `handle_packet`) or directly with `add` and `remove`. `tick` has to be called regularly
+/// with the current time of a synchronised clock. Daily and weekly entries fire at the time of day
+/// in the configured UTC offset.
+///
+/// Nothing fires on the first tick or when the clock goes back, so entries in the past are not
+/// replayed after a restart or a clock correction. If the clock jumps forward, every entry that
+/// was due in between fires once.
+pub struct Scheduler {
+    device_address: u16,
+    capacity: usize,
+    utc_offset: i32,
+    entries: BTreeMap<u8, ScheduleEntry>,
+    last_tick: Option<u32>,
+}
+
+impl Scheduler {
+    pub fn new(device_address: u16, capacity: usize) -> Self {
+        Scheduler {
+            device_address,
+            capacity,
+            utc_offset: 0,
+            entries: BTreeMap::new(),
+            last_tick: None,
+        }
+    }
+
+    /// Sets the offset of the local time from UTC in seconds, used for daily and weekly entries
+    pub fn set_utc_offset(&mut self, utc_offset: i32) {
+        self.utc_offset = utc_offset;
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &ScheduleEntry> {
+        self.entries.values()
+    }
+
+    /// Adds an entry, replacing any entry with the same id
+    ///
+    /// Returns the entry back if the table is full or if the days of a weekly entry have bit 7 set.
+    pub fn add(&mut self, entry: ScheduleEntry) -> Result<(), ScheduleEntry> {
+        if let ScheduleRecurrence::Weekly(days) = entry.recurrence {
+            if days & !0x7f != 0 {
+                return Err(entry);
+            }
+        }
+
+        if !self.entries.contains_key(&entry.id) && self.entries.len() >= self.capacity {
+            return Err(entry);
+        }
+
+        self.entries.insert(entry.id, entry);
+
+        Ok(())
+    }
+
+    pub fn remove(&mut self, id: u8) -> Option<ScheduleEntry> {
+        self.entries.remove(&id)
+    }
+
+    /// Handles schedule events addressed to this device, returning the replies to send
+    ///
+    /// Adding to a full table and removing a missing entry are answered with an `ErrorEvent`.
+    /// Any other packet is ignored.
+    pub fn handle_packet(&mut self, packet: &Packet) -> Vec<Packet> {
+        if packet.device_address != self.device_address {
+            return vec![];
+        }
+
+        if let Ok(event) = ScheduleAddEvent::try_from_packet(packet) {
+            match self.add(event.entry) {
+                Ok(()) => vec![],
+                Err(_) => vec![self.error(
+                    event.transmitter_address,
+                    SCHEDULE_ADD_EVENT_CODE,
+                    ErrorCode::InvalidState,
+                )],
+            }
+        } else if let Ok(event) = ScheduleRemoveEvent::try_from_packet(packet) {
+            match self.remove(event.id) {
+                Some(_) => vec![],
+                None => vec![self.error(
+                    event.transmitter_address,
+                    SCHEDULE_REMOVE_EVENT_CODE,
+                    ErrorCode::InvalidValue,
+                )],
+            }
+        } else if let Ok(event) = ScheduleListEvent::try_from_packet(packet) {
+            self.entries
+                .values()
+                .map(|entry| {
+                    ScheduleReportEvent {
+                        receiver_address: event.transmitter_address,
+                        scheduler_address: self.device_address,
+                        entry: entry.clone(),
+                    }
+                    .to_packet()
+                })
+                .collect()
+        } else {
+            vec![]
+        }
+    }
+
+    /// Returns the packets of all entries that became due since the last tick
+    ///
+    /// `now` is in seconds since the unix epoch.
+    pub fn tick(&mut self, now: u32) -> Vec<Packet> {
+        let last_tick = self.last_tick.replace(now);
+        let mut packets = vec![];
+
+        let last_tick = match last_tick {
+            Some(last_tick) if last_tick < now => last_tick,
+            _ => return packets,
+        };
+
+        let utc_offset = self.utc_offset;
+
+        self.entries.retain(
+            |_, entry| match next_occurrence(entry, last_tick, utc_offset) {
+                Some(time) if time <= now as i64 => {
+                    packets.push(entry.packet.clone());
+
+                    entry.recurrence != ScheduleRecurrence::Once
+                }
+                _ => true,
+            },
+        );
+
+        packets
+    }
+
+    /// Sends the packets of all entries that became due since the last run through the protocol
+    ///
+    /// Does nothing while the clock isn't synchronised.
+    pub fn run<I: Interface>(
+        &mut self,
+        protocol: &mut Protocol<I>,
+        clock: &SyncedClock,
+        now: u32,
+    ) -> Result<(), ProtocolError> {
+        let timestamp = match clock.now(now) {
+            Some(timestamp) => timestamp,
+            None => return Ok(()),
+        };
+
+        for packet in self.tick((timestamp / 1000) as u32).iter() {
+            protocol.send_packet(packet)?;
+        }
+
+        Ok(())
+    }
+
+    fn error(&self, receiver_address: u16, event_code: u16, error_code: ErrorCode) -> Packet {
+        ErrorEvent {
+            receiver_address,
+            transmitter_address: self.device_address,
+            event_code,
+            error_code,
+            detail: vec![],
+        }
+        .to_packet()
+    }
+}
+
+/// Returns the first time after `after` the entry fires at, in seconds since the unix epoch
+fn next_occurrence(entry: &ScheduleEntry, after: u32, utc_offset: i32) -> Option<i64> {
+    let time = entry.time as i64;
+    let after = after as i64;
+
+    match entry.recurrence {
+        ScheduleRecurrence::Once => {
+            if time > after {
+                Some(time)
+            } else {
+                None
+            }
+        }
+        ScheduleRecurrence::Daily => next_time_of_day(time, after, utc_offset, 0x7f),
+        ScheduleRecurrence::Weekly(days) => next_time_of_day(time, after, utc_offset, days),
+        ScheduleRecurrence::Interval(0) => None,
+        ScheduleRecurrence::Interval(period) => {
+            let period = period as i64;
+
+            if time > after {
+                Some(time)
+            } else {
+                Some(time + ((after - time) / period + 1) * period)
+            }
+        }
+    }
+}
+
+/// Returns the first time after `after` at the local time of day of `time` on one of `days`
+fn next_time_of_day(time: i64, after: i64, utc_offset: i32, days: u8) -> Option<i64> {
+    if days & 0x7f == 0 {
+        return None;
+    }
+
+    let utc_offset = utc_offset as i64;
+    let time_of_day = (time + utc_offset).rem_euclid(SECONDS_PER_DAY);
+    let local_after = after + utc_offset;
+    let mut day = local_after.div_euclid(SECONDS_PER_DAY);
+
+    if day * SECONDS_PER_DAY + time_of_day <= local_after {
+        day += 1;
+    }
+
+    for _ in 0..7 {
+        // The unix epoch was a Thursday
+        let weekday = (day + 3).rem_euclid(7);
+
+        if days & (1 << weekday) != 0 {
+            return Some(day * SECONDS_PER_DAY + time_of_day - utc_offset);
+        }
+
+        day += 1;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEDULER_ADDRESS: u16 = 0x0123;
+    /// Monday, 2024-01-01 00:00:00 UTC
+    const MONDAY: u32 = 1_704_067_200;
+
+    fn entry(id: u8, time: u32, recurrence: ScheduleRecurrence) -> ScheduleEntry {
+        ScheduleEntry {
+            id,
+            time,
+            recurrence,
+            packet: Packet {
+                is_error: false,
+                device_address: 0x0456,
+                data: vec![id],
+            },
+        }
+    }
+
+    fn fired(packets: Vec<Packet>) -> Vec<u8> {
+        packets.iter().map(|packet| packet.data[0]).collect()
+    }
+
+    #[test]
+    fn once_test() {
+        let mut scheduler = Scheduler::new(SCHEDULER_ADDRESS, 8);
+
+        scheduler
+            .add(entry(1, MONDAY + 100, ScheduleRecurrence::Once))
+            .unwrap();
+
+        assert_eq!(fired(scheduler.tick(MONDAY)), vec![]);
+        assert_eq!(fired(scheduler.tick(MONDAY + 99)), vec![]);
+        assert_eq!(fired(scheduler.tick(MONDAY + 105)), vec![1]);
+        assert_eq!(scheduler.entries().count(), 0);
+    }
+
+    #[test]
+    fn daily_test() {
+        let mut scheduler = Scheduler::new(SCHEDULER_ADDRESS, 8);
+
+        // 23:00 local time, an hour ahead of UTC
+        scheduler.set_utc_offset(3600);
+        scheduler
+            .add(entry(1, 23 * 3600 - 3600, ScheduleRecurrence::Daily))
+            .unwrap();
+
+        assert_eq!(fired(scheduler.tick(MONDAY)), vec![]);
+        assert_eq!(fired(scheduler.tick(MONDAY + 22 * 3600 - 1)), vec![]);
+        assert_eq!(fired(scheduler.tick(MONDAY + 22 * 3600)), vec![1]);
+        assert_eq!(fired(scheduler.tick(MONDAY + 22 * 3600 + 1)), vec![]);
+        assert_eq!(fired(scheduler.tick(MONDAY + 46 * 3600 + 30)), vec![1]);
+        assert_eq!(scheduler.entries().count(), 1);
+    }
+
+    #[test]
+    fn weekly_test() {
+        let mut scheduler = Scheduler::new(SCHEDULER_ADDRESS, 8);
+
+        // Noon on Wednesdays and Sundays
+        scheduler
+            .add(entry(1, 12 * 3600, ScheduleRecurrence::Weekly(0b0100_0100)))
+            .unwrap();
+
+        let mut fired_days = vec![];
+
+        scheduler.tick(MONDAY);
+
+        for hour in 1..=(14 * 24) {
+            if !scheduler.tick(MONDAY + hour * 3600).is_empty() {
+                fired_days.push((hour - 12) / 24);
+            }
+        }
+
+        assert_eq!(fired_days, vec![2, 6, 9, 13]);
+    }
+
+    #[test]
+    fn weekly_invalid_days_test() {
+        let mut scheduler = Scheduler::new(SCHEDULER_ADDRESS, 8);
+        let invalid = entry(1, 12 * 3600, ScheduleRecurrence::Weekly(0b1000_0100));
+
+        assert_eq!(scheduler.add(invalid.clone()), Err(invalid));
+        assert_eq!(scheduler.entries().count(), 0);
+    }
+
+    #[test]
+    fn interval_test() {
+        let mut scheduler = Scheduler::new(SCHEDULER_ADDRESS, 8);
+
+        scheduler
+            .add(entry(1, MONDAY + 10, ScheduleRecurrence::Interval(60)))
+            .unwrap();
+
+        assert_eq!(fired(scheduler.tick(MONDAY)), vec![]);
+        assert_eq!(fired(scheduler.tick(MONDAY + 10)), vec![1]);
+        assert_eq!(fired(scheduler.tick(MONDAY + 69)), vec![]);
+        assert_eq!(fired(scheduler.tick(MONDAY + 70)), vec![1]);
+        // Missed occurrences fire only once
+        assert_eq!(fired(scheduler.tick(MONDAY + 1000)), vec![1]);
+    }
+
+    #[test]
+    fn clock_jumps_test() {
+        let mut scheduler = Scheduler::new(SCHEDULER_ADDRESS, 8);
+
+        scheduler
+            .add(entry(1, MONDAY + 100, ScheduleRecurrence::Once))
+            .unwrap();
+
+        // Nothing fires on the first tick
+        assert_eq!(fired(scheduler.tick(MONDAY + 200)), vec![]);
+
+        scheduler
+            .add(entry(2, MONDAY + 300, ScheduleRecurrence::Once))
+            .unwrap();
+
+        // Nothing fires when the clock goes back
+        assert_eq!(fired(scheduler.tick(MONDAY + 400)), vec![2]);
+        assert_eq!(fired(scheduler.tick(MONDAY + 50)), vec![]);
+        assert_eq!(fired(scheduler.tick(MONDAY + 150)), vec![1]);
+    }
+
+    #[test]
+    fn handle_packet_test() {
+        let mut scheduler = Scheduler::new(SCHEDULER_ADDRESS, 1);
+
+        let add = |id| {
+            ScheduleAddEvent {
+                scheduler_address: SCHEDULER_ADDRESS,
+                transmitter_address: 0xabab,
+                entry: entry(id, MONDAY, ScheduleRecurrence::Daily),
+            }
+            .to_packet()
+        };
+
+        assert_eq!(scheduler.handle_packet(&add(1)), vec![]);
+        assert_eq!(scheduler.handle_packet(&add(1)), vec![]);
+
+        let replies = scheduler.handle_packet(&add(2));
+        let error = ErrorEvent::try_from_packet(&replies[0]).unwrap();
+
+        assert_eq!(error.receiver_address, 0xabab);
+        assert_eq!(error.event_code, SCHEDULE_ADD_EVENT_CODE);
+        assert_eq!(error.error_code, ErrorCode::InvalidState);
+
+        let list = ScheduleListEvent {
+            scheduler_address: SCHEDULER_ADDRESS,
+            transmitter_address: 0xabab,
+        };
+
+        assert_eq!(
+            scheduler.handle_packet(&list.to_packet()),
+            vec![ScheduleReportEvent {
+                receiver_address: 0xabab,
+                scheduler_address: SCHEDULER_ADDRESS,
+                entry: entry(1, MONDAY, ScheduleRecurrence::Daily),
+            }
+            .to_packet()]
+        );
+
+        let remove = ScheduleRemoveEvent {
+            scheduler_address: SCHEDULER_ADDRESS,
+            transmitter_address: 0xabab,
+            id: 1,
+        };
+
+        assert_eq!(scheduler.handle_packet(&remove.to_packet()), vec![]);
+        assert_eq!(scheduler.entries().count(), 0);
+
+        let replies = scheduler.handle_packet(&remove.to_packet());
+        let error = ErrorEvent::try_from_packet(&replies[0]).unwrap();
+
+        assert_eq!(error.error_code, ErrorCode::InvalidValue);
+    }
+}
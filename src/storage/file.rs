@@ -0,0 +1,96 @@
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use crate::storage::*;
+
+/// Storage kept in a file that behaves like NOR flash, for running device code on Linux
+///
+/// The file is created erased if it doesn't exist and grown to the capacity if it is shorter.
+pub struct FileStorage {
+    file: File,
+    capacity: u32,
+    sector_size: u32,
+}
+
+impl FileStorage {
+    pub fn open<P: AsRef<Path>>(
+        path: P,
+        sector_count: u32,
+        sector_size: u32,
+    ) -> Result<Self, StorageError> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .map_err(StorageError::IoError)?;
+
+        let capacity = sector_count * sector_size;
+        let length = file.metadata().map_err(StorageError::IoError)?.len();
+
+        if length < capacity as u64 {
+            file.seek(SeekFrom::Start(length))
+                .map_err(StorageError::IoError)?;
+            file.write_all(&vec![0xff; (capacity as u64 - length) as usize])
+                .map_err(StorageError::IoError)?;
+        }
+
+        Ok(FileStorage {
+            file,
+            capacity,
+            sector_size,
+        })
+    }
+}
+
+impl Storage for FileStorage {
+    fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    fn sector_size(&self) -> u32 {
+        self.sector_size
+    }
+
+    fn write_size(&self) -> u32 {
+        1
+    }
+
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), StorageError> {
+        check_range(self, offset, bytes.len())?;
+
+        self.file
+            .seek(SeekFrom::Start(offset as u64))
+            .map_err(StorageError::IoError)?;
+        self.file.read_exact(bytes).map_err(StorageError::IoError)
+    }
+
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), StorageError> {
+        check_write(self, offset, bytes.len())?;
+
+        let mut stored = vec![0x00; bytes.len()];
+        self.read(offset, &mut stored)?;
+
+        for (stored, byte) in stored.iter_mut().zip(bytes.iter()) {
+            *stored &= *byte;
+        }
+
+        self.file
+            .seek(SeekFrom::Start(offset as u64))
+            .map_err(StorageError::IoError)?;
+        self.file.write_all(&stored).map_err(StorageError::IoError)
+    }
+
+    fn erase(&mut self, from: u32, to: u32) -> Result<(), StorageError> {
+        check_erase(self, from, to)?;
+
+        self.file
+            .seek(SeekFrom::Start(from as u64))
+            .map_err(StorageError::IoError)?;
+        self.file
+            .write_all(&vec![0xff; (to - from) as usize])
+            .map_err(StorageError::IoError)
+    }
+}
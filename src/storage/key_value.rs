@@ -0,0 +1,533 @@
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+use crate::storage::*;
+
+/// Key of the device address
+pub const DEVICE_ADDRESS_KEY: u16 = 0x0000;
+/// Key of the device configuration
+pub const CONFIG_KEY: u16 = 0x0001;
+/// Key of the remotely writable parameters of an `ObjectDictionary`
+pub const PARAMETERS_KEY: u16 = 0x0002;
+/// First key free for firmware to use, the keys below it are reserved for the crate
+pub const FIRST_APPLICATION_KEY: u16 = 0x0100;
+/// Longest value that can be stored
+pub const MAX_VALUE_LENGTH: usize = TOMBSTONE_LENGTH as usize - 1;
+
+/// "ROSS" in ASCII
+const BANK_MAGIC: u32 = 0x524f_5353;
+const BANK_HEADER_SIZE: u32 = 8;
+const RECORD_HEADER_SIZE: u32 = 6;
+/// Key of an erased record header, marks the end of the log
+const ERASED_KEY: u16 = 0xffff;
+/// Length of a record removing its key
+const TOMBSTONE_LENGTH: u16 = 0xfffe;
+
+/// Key-value store spreading writes evenly over a `Storage`
+///
+/// The storage is split into two banks. Records are appended to the active bank, so updating a
+/// value never erases anything. Once the active bank is full, the live records are copied to the
+/// other bank, which then becomes the active one. Every sector is therefore erased once per two
+/// bank switches, no matter which keys are written.
+///
+/// Banks carry a sequence number written after all records were copied, and records carry a
+/// checksum, so losing power during a write or a bank switch leaves the last complete state
+/// behind. Key `0xffff` is reserved.
+pub struct KeyValueStore<S: Storage> {
+    storage: S,
+    bank_size: u32,
+    active_bank: u32,
+    sequence: u32,
+    write_offset: u32,
+    index: BTreeMap<u16, (u32, u16)>,
+}
+
+impl<S: Storage> KeyValueStore<S> {
+    /// Opens the store kept in the storage, formatting the storage if it holds no store
+    pub fn mount(storage: S) -> Result<Self, StorageError> {
+        let sector_size = storage.sector_size();
+        let bank_size = storage.capacity() / 2 / sector_size * sector_size;
+
+        let mut store = KeyValueStore {
+            storage,
+            bank_size,
+            active_bank: 0,
+            sequence: 0,
+            write_offset: 0,
+            index: BTreeMap::new(),
+        };
+
+        if bank_size < store.align(BANK_HEADER_SIZE) + store.align(RECORD_HEADER_SIZE) {
+            return Err(StorageError::OutOfBounds);
+        }
+
+        let first = store.read_bank_sequence(0)?;
+        let second = store.read_bank_sequence(1)?;
+
+        match (first, second) {
+            (Some(first), Some(second)) => {
+                if (second.wrapping_sub(first) as i32) > 0 {
+                    store.active_bank = 1;
+                    store.sequence = second;
+                } else {
+                    store.sequence = first;
+                }
+            }
+            (Some(first), None) => store.sequence = first,
+            (None, Some(second)) => {
+                store.active_bank = 1;
+                store.sequence = second;
+            }
+            (None, None) => store.format()?,
+        }
+
+        store.scan()?;
+
+        Ok(store)
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.storage
+    }
+
+    pub fn into_inner(self) -> S {
+        self.storage
+    }
+
+    pub fn contains_key(&self, key: u16) -> bool {
+        self.index.contains_key(&key)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &u16> {
+        self.index.keys()
+    }
+
+    pub fn get(&mut self, key: u16) -> Result<Option<Vec<u8>>, StorageError> {
+        match self.index.get(&key) {
+            Some(&(offset, length)) => {
+                let mut value = vec![0x00; length as usize];
+                self.storage.read(offset, &mut value)?;
+
+                Ok(Some(value))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Stores a value, skipping the write if the same value is already stored
+    pub fn set(&mut self, key: u16, value: &[u8]) -> Result<(), StorageError> {
+        if key == ERASED_KEY {
+            return Err(StorageError::OutOfBounds);
+        }
+
+        if value.len() > MAX_VALUE_LENGTH {
+            return Err(StorageError::ValueTooLong);
+        }
+
+        if self.get(key)?.as_deref() == Some(value) {
+            return Ok(());
+        }
+
+        self.append(key, Some(value))
+    }
+
+    pub fn remove(&mut self, key: u16) -> Result<(), StorageError> {
+        if !self.contains_key(key) {
+            return Ok(());
+        }
+
+        self.append(key, None)
+    }
+
+    pub fn device_address(&mut self) -> Result<Option<u16>, StorageError> {
+        Ok(self
+            .get(DEVICE_ADDRESS_KEY)?
+            .and_then(|value| value.as_slice().try_into().ok())
+            .map(u16::from_be_bytes))
+    }
+
+    pub fn set_device_address(&mut self, device_address: u16) -> Result<(), StorageError> {
+        self.set(DEVICE_ADDRESS_KEY, &device_address.to_be_bytes())
+    }
+
+    fn align(&self, length: u32) -> u32 {
+        let write_size = self.storage.write_size();
+
+        (length + write_size - 1) / write_size * write_size
+    }
+
+    fn bank_start(&self, bank: u32) -> u32 {
+        bank * self.bank_size
+    }
+
+    fn read_bank_sequence(&mut self, bank: u32) -> Result<Option<u32>, StorageError> {
+        let mut header = [0x00; BANK_HEADER_SIZE as usize];
+        self.storage.read(self.bank_start(bank), &mut header)?;
+
+        if u32::from_be_bytes(header[0..=3].try_into().unwrap()) != BANK_MAGIC {
+            return Ok(None);
+        }
+
+        Ok(Some(u32::from_be_bytes(header[4..=7].try_into().unwrap())))
+    }
+
+    fn write_bank_header(&mut self, bank: u32, sequence: u32) -> Result<(), StorageError> {
+        let mut header = vec![];
+        header.extend_from_slice(&BANK_MAGIC.to_be_bytes());
+        header.extend_from_slice(&sequence.to_be_bytes());
+        header.resize(self.align(BANK_HEADER_SIZE) as usize, 0xff);
+
+        self.storage.write(self.bank_start(bank), &header)
+    }
+
+    fn format(&mut self) -> Result<(), StorageError> {
+        self.storage.erase(0, self.bank_size)?;
+        self.write_bank_header(0, 0)?;
+
+        self.active_bank = 0;
+        self.sequence = 0;
+
+        Ok(())
+    }
+
+    /// Rebuilds the index from the records of the active bank
+    fn scan(&mut self) -> Result<(), StorageError> {
+        let start = self.bank_start(self.active_bank);
+        let mut offset = self.align(BANK_HEADER_SIZE);
+
+        self.index.clear();
+
+        while offset + RECORD_HEADER_SIZE <= self.bank_size {
+            let mut header = [0x00; RECORD_HEADER_SIZE as usize];
+            self.storage.read(start + offset, &mut header)?;
+
+            let key = u16::from_be_bytes(header[0..=1].try_into().unwrap());
+            let length = u16::from_be_bytes(header[2..=3].try_into().unwrap());
+            let checksum = u16::from_be_bytes(header[4..=5].try_into().unwrap());
+
+            if key == ERASED_KEY {
+                break;
+            }
+
+            let data_length = if length == TOMBSTONE_LENGTH {
+                0
+            } else {
+                length
+            };
+            let record_size = self.align(RECORD_HEADER_SIZE + data_length as u32);
+
+            if offset + record_size > self.bank_size {
+                // A torn record header, nothing can be appended after it
+                offset = self.bank_size;
+
+                break;
+            }
+
+            let mut data = vec![0x00; data_length as usize];
+            self.storage
+                .read(start + offset + RECORD_HEADER_SIZE, &mut data)?;
+
+            if record_checksum(key, length, &data) == checksum {
+                if length == TOMBSTONE_LENGTH {
+                    self.index.remove(&key);
+                } else {
+                    self.index
+                        .insert(key, (start + offset + RECORD_HEADER_SIZE, length));
+                }
+            }
+
+            offset += record_size;
+        }
+
+        self.write_offset = offset;
+
+        Ok(())
+    }
+
+    fn record(&self, key: u16, value: Option<&[u8]>) -> Vec<u8> {
+        let length = match value {
+            Some(value) => value.len() as u16,
+            None => TOMBSTONE_LENGTH,
+        };
+        let data = value.unwrap_or(&[]);
+
+        let mut record = vec![];
+        record.extend_from_slice(&key.to_be_bytes());
+        record.extend_from_slice(&length.to_be_bytes());
+        record.extend_from_slice(&record_checksum(key, length, data).to_be_bytes());
+        record.extend_from_slice(data);
+        record.resize(self.align(record.len() as u32) as usize, 0xff);
+
+        record
+    }
+
+    fn append(&mut self, key: u16, value: Option<&[u8]>) -> Result<(), StorageError> {
+        let record = self.record(key, value);
+
+        if self.write_offset + record.len() as u32 > self.bank_size {
+            return self.compact(key, value);
+        }
+
+        let offset = self.bank_start(self.active_bank) + self.write_offset;
+        self.storage.write(offset, &record)?;
+        self.write_offset += record.len() as u32;
+
+        match value {
+            Some(value) => {
+                self.index
+                    .insert(key, (offset + RECORD_HEADER_SIZE, value.len() as u16));
+            }
+            None => {
+                self.index.remove(&key);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Copies the live records and the new value of `key` to the other bank and switches to it
+    fn compact(&mut self, key: u16, value: Option<&[u8]>) -> Result<(), StorageError> {
+        let bank = 1 - self.active_bank;
+        let start = self.bank_start(bank);
+
+        self.storage.erase(start, start + self.bank_size)?;
+
+        let mut index = BTreeMap::new();
+        let mut offset = self.align(BANK_HEADER_SIZE);
+
+        let mut records = vec![];
+
+        let keys: Vec<u16> = self.index.keys().copied().collect();
+
+        for record_key in keys {
+            if record_key != key {
+                records.push((record_key, self.get(record_key)?.unwrap_or_default()));
+            }
+        }
+
+        if let Some(value) = value {
+            records.push((key, value.to_vec()));
+        }
+
+        for (record_key, record_value) in records.iter() {
+            let record = self.record(*record_key, Some(record_value));
+
+            if offset + record.len() as u32 > self.bank_size {
+                return Err(StorageError::Full);
+            }
+
+            self.storage.write(start + offset, &record)?;
+            index.insert(
+                *record_key,
+                (
+                    start + offset + RECORD_HEADER_SIZE,
+                    record_value.len() as u16,
+                ),
+            );
+            offset += record.len() as u32;
+        }
+
+        let sequence = self.sequence.wrapping_add(1);
+        self.write_bank_header(bank, sequence)?;
+
+        self.active_bank = bank;
+        self.sequence = sequence;
+        self.write_offset = offset;
+        self.index = index;
+
+        Ok(())
+    }
+}
+
+/// CRC-16/CCITT-FALSE of a record
+fn record_checksum(key: u16, length: u16, data: &[u8]) -> u16 {
+    let mut crc: u16 = 0xffff;
+
+    for byte in key
+        .to_be_bytes()
+        .iter()
+        .chain(length.to_be_bytes().iter())
+        .chain(data.iter())
+    {
+        crc ^= (*byte as u16) << 8;
+
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+
+    crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::storage::memory::MemoryStorage;
+
+    fn store(write_size: u32) -> KeyValueStore<MemoryStorage> {
+        KeyValueStore::mount(MemoryStorage::new(4, 128, write_size)).unwrap()
+    }
+
+    #[test]
+    fn checksum_test() {
+        assert_eq!(record_checksum(0x3132, 0x3334, b"56789"), 0x29b1);
+    }
+
+    #[test]
+    fn set_get_test() {
+        for &write_size in [1, 4, 8].iter() {
+            let mut store = store(write_size);
+
+            assert_eq!(store.get(0x0100).unwrap(), None);
+
+            store.set(0x0100, &[0x01, 0x02, 0x03]).unwrap();
+            store.set(0x0101, &[]).unwrap();
+            store.set(0x0100, &[0x04]).unwrap();
+
+            assert_eq!(store.get(0x0100).unwrap(), Some(vec![0x04]));
+            assert_eq!(store.get(0x0101).unwrap(), Some(vec![]));
+
+            store.remove(0x0101).unwrap();
+
+            assert_eq!(store.get(0x0101).unwrap(), None);
+            assert_eq!(store.keys().copied().collect::<Vec<_>>(), vec![0x0100]);
+        }
+    }
+
+    #[test]
+    fn remount_test() {
+        let mut store = store(4);
+
+        store.set_device_address(0xabcd).unwrap();
+        store.set(CONFIG_KEY, &[0x01, 0x02]).unwrap();
+        store.set(0x0100, &[0x03]).unwrap();
+        store.remove(0x0100).unwrap();
+
+        let mut store = KeyValueStore::mount(store.into_inner()).unwrap();
+
+        assert_eq!(store.device_address().unwrap(), Some(0xabcd));
+        assert_eq!(store.get(CONFIG_KEY).unwrap(), Some(vec![0x01, 0x02]));
+        assert_eq!(store.get(0x0100).unwrap(), None);
+    }
+
+    #[test]
+    fn unchanged_value_test() {
+        let mut store = store(1);
+
+        store.set(0x0100, &[0x01]).unwrap();
+
+        let data = store.get_ref().data().to_vec();
+
+        store.set(0x0100, &[0x01]).unwrap();
+        store.remove(0x0200).unwrap();
+
+        assert_eq!(store.get_ref().data(), &data[..]);
+    }
+
+    #[test]
+    fn wear_levelling_test() {
+        let mut store = store(4);
+
+        store.set(CONFIG_KEY, &[0xaa; 40]).unwrap();
+
+        for counter in 0u32..1000 {
+            store.set(0x0100, &counter.to_be_bytes()).unwrap();
+        }
+
+        assert_eq!(store.get(CONFIG_KEY).unwrap(), Some(vec![0xaa; 40]));
+        assert_eq!(
+            store.get(0x0100).unwrap(),
+            Some(999u32.to_be_bytes().to_vec())
+        );
+
+        let erase_counts = store.into_inner().erase_counts().to_vec();
+        let min = *erase_counts.iter().min().unwrap();
+        let max = *erase_counts.iter().max().unwrap();
+
+        assert!(min > 0);
+        assert!(max - min <= 1);
+    }
+
+    #[test]
+    fn full_test() {
+        let mut store = store(1);
+
+        store.set(0x0100, &[0x00; 200]).unwrap();
+
+        assert!(matches!(
+            store.set(0x0101, &[0x00; 100]),
+            Err(StorageError::Full)
+        ));
+        assert_eq!(store.get(0x0100).unwrap(), Some(vec![0x00; 200]));
+        assert!(matches!(
+            store.set(ERASED_KEY, &[]),
+            Err(StorageError::OutOfBounds)
+        ));
+    }
+
+    #[test]
+    fn torn_record_test() {
+        let mut store = store(1);
+
+        store.set(0x0100, &[0x01]).unwrap();
+
+        let mut storage = store.into_inner();
+
+        // Power was lost after writing only the key of the next record
+        let offset = BANK_HEADER_SIZE + RECORD_HEADER_SIZE + 1;
+        storage.write(offset, &[0x01, 0x01]).unwrap();
+
+        let mut store = KeyValueStore::mount(storage).unwrap();
+
+        assert_eq!(store.get(0x0100).unwrap(), Some(vec![0x01]));
+
+        store.set(0x0101, &[0x02]).unwrap();
+
+        let mut store = KeyValueStore::mount(store.into_inner()).unwrap();
+
+        assert_eq!(store.get(0x0100).unwrap(), Some(vec![0x01]));
+        assert_eq!(store.get(0x0101).unwrap(), Some(vec![0x02]));
+    }
+
+    #[test]
+    fn corrupted_record_test() {
+        let mut store = store(1);
+
+        store.set(0x0100, &[0x01]).unwrap();
+        store.set(0x0100, &[0x02, 0x03]).unwrap();
+
+        let mut storage = store.into_inner();
+
+        // Power was lost while writing the data of the second record
+        let offset = BANK_HEADER_SIZE + (RECORD_HEADER_SIZE + 1) + RECORD_HEADER_SIZE + 1;
+        storage.write(offset, &[0x00]).unwrap();
+
+        let mut store = KeyValueStore::mount(storage).unwrap();
+
+        assert_eq!(store.get(0x0100).unwrap(), Some(vec![0x01]));
+    }
+
+    #[test]
+    fn interrupted_compaction_test() {
+        let mut store = store(1);
+
+        store.set(0x0100, &[0x01]).unwrap();
+
+        let mut storage = store.into_inner();
+
+        // Power was lost after copying a record to the other bank, before writing its header
+        let record = [0x01, 0x00, 0x00, 0x01, 0x00, 0x00, 0x02];
+        storage.write(256 + BANK_HEADER_SIZE, &record).unwrap();
+
+        let mut store = KeyValueStore::mount(storage).unwrap();
+
+        assert_eq!(store.get(0x0100).unwrap(), Some(vec![0x01]));
+    }
+}
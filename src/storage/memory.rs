@@ -0,0 +1,127 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::storage::*;
+
+/// Storage kept in RAM that behaves like NOR flash, for tests and simulations
+///
+/// Writes clear bits just like flash does, so code that forgets to erase before writing fails the
+/// same way it would on a device. Erases are counted per sector to check wear levelling.
+pub struct MemoryStorage {
+    data: Vec<u8>,
+    sector_size: u32,
+    write_size: u32,
+    erase_counts: Vec<u32>,
+}
+
+impl MemoryStorage {
+    /// Creates an erased storage of `sector_count` sectors
+    pub fn new(sector_count: u32, sector_size: u32, write_size: u32) -> Self {
+        MemoryStorage {
+            data: vec![0xff; (sector_count * sector_size) as usize],
+            sector_size,
+            write_size,
+            erase_counts: vec![0; sector_count as usize],
+        }
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns how many times every sector was erased
+    pub fn erase_counts(&self) -> &[u32] {
+        &self.erase_counts
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn capacity(&self) -> u32 {
+        self.data.len() as u32
+    }
+
+    fn sector_size(&self) -> u32 {
+        self.sector_size
+    }
+
+    fn write_size(&self) -> u32 {
+        self.write_size
+    }
+
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), StorageError> {
+        check_range(self, offset, bytes.len())?;
+
+        let offset = offset as usize;
+        bytes.copy_from_slice(&self.data[offset..offset + bytes.len()]);
+
+        Ok(())
+    }
+
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), StorageError> {
+        check_write(self, offset, bytes.len())?;
+
+        for (stored, byte) in self.data[offset as usize..].iter_mut().zip(bytes.iter()) {
+            *stored &= *byte;
+        }
+
+        Ok(())
+    }
+
+    fn erase(&mut self, from: u32, to: u32) -> Result<(), StorageError> {
+        check_erase(self, from, to)?;
+
+        for byte in self.data[from as usize..to as usize].iter_mut() {
+            *byte = 0xff;
+        }
+
+        for sector in (from / self.sector_size)..(to / self.sector_size) {
+            self.erase_counts[sector as usize] += 1;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flash_semantics_test() {
+        let mut storage = MemoryStorage::new(2, 16, 2);
+
+        storage.write(2, &[0xf0, 0x0f]).unwrap();
+        storage.write(2, &[0x3c, 0xff]).unwrap();
+
+        let mut bytes = [0x00; 3];
+        storage.read(1, &mut bytes).unwrap();
+        assert_eq!(bytes, [0xff, 0x30, 0x0f]);
+
+        storage.erase(0, 16).unwrap();
+        storage.read(1, &mut bytes).unwrap();
+        assert_eq!(bytes, [0xff, 0xff, 0xff]);
+        assert_eq!(storage.erase_counts(), &[1, 0]);
+    }
+
+    #[test]
+    fn alignment_test() {
+        let mut storage = MemoryStorage::new(2, 16, 2);
+
+        assert!(matches!(
+            storage.write(1, &[0x00, 0x00]),
+            Err(StorageError::NotAligned)
+        ));
+        assert!(matches!(
+            storage.write(30, &[0x00, 0x00, 0x00, 0x00]),
+            Err(StorageError::OutOfBounds)
+        ));
+        assert!(matches!(
+            storage.erase(8, 16),
+            Err(StorageError::NotAligned)
+        ));
+        assert!(matches!(
+            storage.erase(16, 48),
+            Err(StorageError::OutOfBounds)
+        ));
+    }
+}
@@ -0,0 +1,83 @@
+#[cfg(feature = "std")]
+use std::io;
+
+pub mod key_value;
+pub mod memory;
+pub mod nor_flash;
+
+#[cfg(feature = "std")]
+pub mod file;
+
+#[derive(Debug)]
+pub enum StorageError {
+    /// The accessed range is outside of the storage
+    OutOfBounds,
+    /// The offset or length is not a multiple of the write or sector size
+    NotAligned,
+    /// There is no space left for the value, even after compacting
+    Full,
+    /// The value is too long to be stored
+    ValueTooLong,
+    /// The underlying flash failed for another reason
+    Other,
+    #[cfg(feature = "std")]
+    IoError(io::Error),
+}
+
+/// Non-volatile memory with the semantics of NOR flash
+///
+/// Erasing sets whole sectors to `0xff` and writing can only clear bits, so a byte has to be
+/// erased before it can be written again. Offsets of writes and their lengths have to be multiples
+/// of `write_size`, erased ranges have to be whole sectors. Reads may start and end anywhere.
+pub trait Storage {
+    /// Size of the storage in bytes
+    fn capacity(&self) -> u32;
+    /// Smallest erasable unit in bytes
+    fn sector_size(&self) -> u32;
+    /// Smallest writable unit in bytes
+    fn write_size(&self) -> u32;
+
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), StorageError>;
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), StorageError>;
+    /// Erases the sectors from `from` up to, but not including, `to`
+    fn erase(&mut self, from: u32, to: u32) -> Result<(), StorageError>;
+}
+
+/// Checks a write against the size and alignment rules of a `Storage`
+fn check_write<S: Storage + ?Sized>(
+    storage: &S,
+    offset: u32,
+    length: usize,
+) -> Result<(), StorageError> {
+    check_range(storage, offset, length)?;
+
+    if offset % storage.write_size() != 0 || length as u32 % storage.write_size() != 0 {
+        return Err(StorageError::NotAligned);
+    }
+
+    Ok(())
+}
+
+/// Checks an erase against the size and alignment rules of a `Storage`
+fn check_erase<S: Storage + ?Sized>(storage: &S, from: u32, to: u32) -> Result<(), StorageError> {
+    if from > to || to > storage.capacity() {
+        return Err(StorageError::OutOfBounds);
+    }
+
+    if from % storage.sector_size() != 0 || to % storage.sector_size() != 0 {
+        return Err(StorageError::NotAligned);
+    }
+
+    Ok(())
+}
+
+fn check_range<S: Storage + ?Sized>(
+    storage: &S,
+    offset: u32,
+    length: usize,
+) -> Result<(), StorageError> {
+    match (offset as usize).checked_add(length) {
+        Some(end) if end <= storage.capacity() as usize => Ok(()),
+        _ => Err(StorageError::OutOfBounds),
+    }
+}
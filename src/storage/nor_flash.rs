@@ -0,0 +1,180 @@
+use alloc::vec;
+use embedded_storage::nor_flash::{NorFlash, NorFlashError, NorFlashErrorKind};
+
+use crate::storage::*;
+
+/// `Storage` backed by a NOR flash driver implementing `embedded-storage`
+///
+/// Reads that are not aligned to the read size of the flash are done through a buffer.
+pub struct NorFlashStorage<F: NorFlash> {
+    flash: F,
+}
+
+impl<F: NorFlash> NorFlashStorage<F> {
+    pub fn new(flash: F) -> Self {
+        NorFlashStorage { flash }
+    }
+
+    pub fn get_ref(&self) -> &F {
+        &self.flash
+    }
+
+    pub fn get_mut(&mut self) -> &mut F {
+        &mut self.flash
+    }
+
+    pub fn into_inner(self) -> F {
+        self.flash
+    }
+}
+
+impl<F: NorFlash> Storage for NorFlashStorage<F> {
+    fn capacity(&self) -> u32 {
+        self.flash.capacity() as u32
+    }
+
+    fn sector_size(&self) -> u32 {
+        F::ERASE_SIZE as u32
+    }
+
+    fn write_size(&self) -> u32 {
+        F::WRITE_SIZE as u32
+    }
+
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), StorageError> {
+        check_range(self, offset, bytes.len())?;
+
+        let read_size = F::READ_SIZE as u32;
+        let start = offset - offset % read_size;
+        let end = offset + bytes.len() as u32;
+        let end = end + (read_size - end % read_size) % read_size;
+
+        if start == offset && end == offset + bytes.len() as u32 {
+            return self.flash.read(offset, bytes).map_err(convert_error);
+        }
+
+        let mut buffer = vec![0x00; (end - start) as usize];
+        self.flash.read(start, &mut buffer).map_err(convert_error)?;
+
+        let buffer_offset = (offset - start) as usize;
+        bytes.copy_from_slice(&buffer[buffer_offset..buffer_offset + bytes.len()]);
+
+        Ok(())
+    }
+
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), StorageError> {
+        check_write(self, offset, bytes.len())?;
+
+        self.flash.write(offset, bytes).map_err(convert_error)
+    }
+
+    fn erase(&mut self, from: u32, to: u32) -> Result<(), StorageError> {
+        check_erase(self, from, to)?;
+
+        self.flash.erase(from, to).map_err(convert_error)
+    }
+}
+
+fn convert_error<E: NorFlashError>(err: E) -> StorageError {
+    match err.kind() {
+        NorFlashErrorKind::NotAligned => StorageError::NotAligned,
+        NorFlashErrorKind::OutOfBounds => StorageError::OutOfBounds,
+        _ => StorageError::Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use embedded_storage::nor_flash::{ErrorType, ReadNorFlash};
+
+    use crate::storage::memory::MemoryStorage;
+
+    /// Flash that can only be read in words of 4 bytes
+    struct WordFlash {
+        storage: MemoryStorage,
+    }
+
+    impl ErrorType for WordFlash {
+        type Error = NorFlashErrorKind;
+    }
+
+    impl ReadNorFlash for WordFlash {
+        const READ_SIZE: usize = 4;
+
+        fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+            if offset % 4 != 0 || bytes.len() % 4 != 0 {
+                return Err(NorFlashErrorKind::NotAligned);
+            }
+
+            self.storage
+                .read(offset, bytes)
+                .map_err(|_| NorFlashErrorKind::OutOfBounds)
+        }
+
+        fn capacity(&self) -> usize {
+            self.storage.capacity() as usize
+        }
+    }
+
+    impl NorFlash for WordFlash {
+        const WRITE_SIZE: usize = 4;
+        const ERASE_SIZE: usize = 64;
+
+        fn erase(&mut self, from: u32, to: u32) -> Result<(), Self::Error> {
+            self.storage
+                .erase(from, to)
+                .map_err(|_| NorFlashErrorKind::Other)
+        }
+
+        fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+            self.storage
+                .write(offset, bytes)
+                .map_err(|_| NorFlashErrorKind::Other)
+        }
+    }
+
+    fn storage() -> NorFlashStorage<WordFlash> {
+        NorFlashStorage::new(WordFlash {
+            storage: MemoryStorage::new(4, 64, 4),
+        })
+    }
+
+    #[test]
+    fn read_write_test() {
+        let mut storage = storage();
+
+        assert_eq!(storage.capacity(), 256);
+        assert_eq!(storage.sector_size(), 64);
+        assert_eq!(storage.write_size(), 4);
+
+        storage.write(4, &[0x01, 0x02, 0x03, 0x04]).unwrap();
+
+        let mut bytes = [0x00; 3];
+        storage.read(5, &mut bytes).unwrap();
+        assert_eq!(bytes, [0x02, 0x03, 0x04]);
+
+        let mut bytes = [0x00; 4];
+        storage.read(4, &mut bytes).unwrap();
+        assert_eq!(bytes, [0x01, 0x02, 0x03, 0x04]);
+    }
+
+    #[test]
+    fn errors_test() {
+        let mut storage = storage();
+
+        assert!(matches!(
+            storage.write(2, &[0x00; 4]),
+            Err(StorageError::NotAligned)
+        ));
+        assert!(matches!(
+            storage.read(254, &mut [0x00; 4]),
+            Err(StorageError::OutOfBounds)
+        ));
+        assert!(matches!(
+            storage.erase(0, 32),
+            Err(StorageError::NotAligned)
+        ));
+    }
+}
@@ -0,0 +1,193 @@
+use bxcan::{Data, ExtendedId, Frame as BxFrame, StandardId};
+use proptest::prelude::*;
+
+use ross_protocol::convert_packet::ConvertPacket;
+use ross_protocol::event::analog::*;
+use ross_protocol::event::bcm::*;
+use ross_protocol::event::bootloader::*;
+use ross_protocol::event::button::*;
+use ross_protocol::event::configurator::*;
+use ross_protocol::event::cover::*;
+use ross_protocol::event::diagnostics::*;
+use ross_protocol::event::encoder::*;
+use ross_protocol::event::error::*;
+use ross_protocol::event::gateway::*;
+use ross_protocol::event::general::*;
+use ross_protocol::event::group::*;
+use ross_protocol::event::internal::*;
+use ross_protocol::event::message::*;
+use ross_protocol::event::parameter::*;
+use ross_protocol::event::programmer::*;
+use ross_protocol::event::relay::*;
+use ross_protocol::event::schedule::*;
+use ross_protocol::event::sensor::*;
+use ross_protocol::event::time::*;
+use ross_protocol::frame::Frame;
+use ross_protocol::interface::usart_decoder::{UsartDecoder, UsartFraming};
+use ross_protocol::packet::{Packet, PacketBuilder};
+
+/// Runs every event decoder on the packet, none of them may panic
+fn decode_events(packet: &Packet) {
+    let _ = BootloaderHelloEvent::try_from_packet(packet);
+    let _ = ProgrammerHelloEvent::try_from_packet(packet);
+    let _ = ProgrammerStartFirmwareUpgradeEvent::try_from_packet(packet);
+    let _ = ProgrammerStartConfigUpgradeEvent::try_from_packet(packet);
+    let _ = ProgrammerSetDeviceAddressEvent::try_from_packet(packet);
+    let _ = AckEvent::try_from_packet(packet);
+    let _ = ExtendedAckEvent::try_from_packet(packet);
+    let _ = DataEvent::try_from_packet(packet);
+    let _ = SequencedEvent::try_from_packet(packet);
+    let _ = ConfiguratorHelloEvent::try_from_packet(packet);
+    let _ = BcmChangeBrightnessEvent::try_from_packet(packet);
+    let _ = BcmAnimateBrightnessEvent::try_from_packet(packet);
+    let _ = BcmAnimateSequenceEvent::try_from_packet(packet);
+    let _ = ButtonPressedEvent::try_from_packet(packet);
+    let _ = ButtonReleasedEvent::try_from_packet(packet);
+    let _ = SystemTickEvent::try_from_packet(packet);
+    let _ = MessageEvent::try_from_packet(packet);
+    let _ = RelaySetValueEvent::try_from_packet(packet);
+    let _ = RelayGetValueEvent::try_from_packet(packet);
+    let _ = RelayReportValueEvent::try_from_packet(packet);
+    let _ = RelayValueChangedEvent::try_from_packet(packet);
+    let _ = RelayReportPowerEvent::try_from_packet(packet);
+    let _ = RelayReportEnergyEvent::try_from_packet(packet);
+    let _ = RelayOverCurrentEvent::try_from_packet(packet);
+    let _ = RelayResetEnergyEvent::try_from_packet(packet);
+    let _ = GatewayDiscoverEvent::try_from_packet(packet);
+    let _ = ErrorEvent::try_from_packet(packet);
+    let _ = CoverSetPositionEvent::try_from_packet(packet);
+    let _ = CoverSetTiltEvent::try_from_packet(packet);
+    let _ = CoverStopEvent::try_from_packet(packet);
+    let _ = CoverCalibrateEvent::try_from_packet(packet);
+    let _ = CoverReportPositionEvent::try_from_packet(packet);
+    let _ = BcmGetValueEvent::try_from_packet(packet);
+    let _ = BcmReportValueEvent::try_from_packet(packet);
+    let _ = BcmStoreSceneEvent::try_from_packet(packet);
+    let _ = BcmRecallSceneEvent::try_from_packet(packet);
+    let _ = ButtonClickedEvent::try_from_packet(packet);
+    let _ = ButtonDoubleClickedEvent::try_from_packet(packet);
+    let _ = ButtonLongPressedEvent::try_from_packet(packet);
+    let _ = ButtonHeldEvent::try_from_packet(packet);
+    let _ = EncoderRotatedEvent::try_from_packet(packet);
+    let _ = AnalogValueEvent::try_from_packet(packet);
+    let _ = SensorGetReadingEvent::try_from_packet(packet);
+    let _ = SensorReadingEvent::try_from_packet(packet);
+    let _ = TimeGetEvent::try_from_packet(packet);
+    let _ = TimeSyncEvent::try_from_packet(packet);
+    let _ = ScheduleAddEvent::try_from_packet(packet);
+    let _ = ScheduleRemoveEvent::try_from_packet(packet);
+    let _ = ScheduleListEvent::try_from_packet(packet);
+    let _ = ScheduleReportEvent::try_from_packet(packet);
+    let _ = GroupAddEvent::try_from_packet(packet);
+    let _ = GroupRemoveEvent::try_from_packet(packet);
+    let _ = GroupGetEvent::try_from_packet(packet);
+    let _ = GroupReportEvent::try_from_packet(packet);
+    let _ = ParameterReadEvent::try_from_packet(packet);
+    let _ = ParameterWriteEvent::try_from_packet(packet);
+    let _ = ParameterReportEvent::try_from_packet(packet);
+    let _ = ParameterDescribeEvent::try_from_packet(packet);
+    let _ = ParameterDescriptionEvent::try_from_packet(packet);
+    let _ = DiagnosticsGetEvent::try_from_packet(packet);
+    let _ = DiagnosticsReportEvent::try_from_packet(packet);
+}
+
+/// Packet data that starts with a known event code more often than not
+fn packet_data_strategy() -> impl Strategy<Value = Vec<u8>> {
+    prop_oneof![
+        prop::collection::vec(any::<u8>(), 0..64),
+        (0u16..0x40, prop::collection::vec(any::<u8>(), 0..64)).prop_map(|(code, rest)| {
+            let mut data = code.to_be_bytes().to_vec();
+            data.extend(rest);
+            data
+        }),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn decode_events_test(
+        is_error in any::<bool>(),
+        device_address in any::<u16>(),
+        data in packet_data_strategy(),
+    ) {
+        decode_events(&Packet {
+            is_error,
+            device_address,
+            data,
+        });
+    }
+
+    #[test]
+    fn from_usart_frame_test(bytes in prop::collection::vec(any::<u8>(), 0..32)) {
+        let _ = Frame::from_usart_frame(bytes);
+    }
+
+    #[test]
+    fn from_bxcan_frame_test(
+        id in any::<u32>(),
+        extended in any::<bool>(),
+        remote in any::<bool>(),
+        data in prop::collection::vec(any::<u8>(), 0..=8),
+    ) {
+        let frame = if extended {
+            let id = ExtendedId::new(id & 0x1fff_ffff).unwrap();
+
+            if remote {
+                BxFrame::new_remote(id, data.len() as u8)
+            } else {
+                BxFrame::new_data(id, Data::new(&data).unwrap())
+            }
+        } else {
+            let id = StandardId::new((id & 0x07ff) as u16).unwrap();
+
+            BxFrame::new_data(id, Data::new(&data).unwrap())
+        };
+
+        let _ = Frame::from_bxcan_frame(frame);
+    }
+
+    #[test]
+    fn packet_builder_test(
+        encoded_frames in prop::collection::vec(prop::collection::vec(1u8..=255, 0..16), 0..16),
+    ) {
+        let mut packet_builder: Option<PacketBuilder> = None;
+
+        for encoded_frame in encoded_frames {
+            let frame = match Frame::from_usart_frame(encoded_frame) {
+                Ok(frame) => frame,
+                Err(_) => continue,
+            };
+
+            match packet_builder {
+                Some(ref mut builder) => {
+                    if builder.add_frame(frame).is_err() {
+                        packet_builder = None;
+                    }
+                }
+                None => packet_builder = PacketBuilder::new(frame).ok(),
+            }
+
+            if let Some(ref builder) = packet_builder {
+                if builder.frames_left() == 0 {
+                    decode_events(&builder.build().unwrap());
+                    packet_builder = None;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn usart_decoder_test(
+        bytes in prop::collection::vec(prop_oneof![Just(0x00u8), any::<u8>()], 0..256),
+    ) {
+        for framing in [UsartFraming::LengthPrefixed, UsartFraming::ZeroDelimited].iter() {
+            let mut decoder = UsartDecoder::with_framing(*framing);
+
+            for byte in bytes.iter() {
+                if let Ok(Some(packet)) = decoder.decode_byte(*byte) {
+                    decode_events(&packet);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,1116 @@
+use proptest::prelude::*;
+
+use ross_protocol::convert_packet::{ConvertPacket, ConvertPacketError};
+use ross_protocol::event::analog::*;
+use ross_protocol::event::bcm::*;
+use ross_protocol::event::bootloader::*;
+use ross_protocol::event::button::*;
+use ross_protocol::event::configurator::*;
+use ross_protocol::event::cover::*;
+use ross_protocol::event::diagnostics::*;
+use ross_protocol::event::encoder::*;
+use ross_protocol::event::error::*;
+use ross_protocol::event::gateway::*;
+use ross_protocol::event::general::*;
+use ross_protocol::event::group::*;
+use ross_protocol::event::internal::*;
+use ross_protocol::event::message::*;
+use ross_protocol::event::parameter::*;
+use ross_protocol::event::programmer::*;
+use ross_protocol::event::relay::*;
+use ross_protocol::event::schedule::*;
+use ross_protocol::event::sensor::*;
+use ross_protocol::event::time::*;
+use ross_protocol::frame::Frame;
+use ross_protocol::interface::InterfaceStatistics;
+use ross_protocol::packet::{Packet, PacketBuilder};
+use ross_protocol::protocol::ProtocolStatistics;
+
+/// Checks that `try_from_packet` gives back the event that `to_packet` was called on
+macro_rules! roundtrip_test {
+    ($name:ident, $event:ty, $strategy:expr) => {
+        proptest! {
+            #[test]
+            fn $name(event in $strategy) {
+                let packet = event.to_packet();
+
+                prop_assert_eq!(<$event>::try_from_packet(&packet), Ok(event));
+            }
+        }
+    };
+}
+
+fn packet_strategy(max_data_len: usize) -> impl Strategy<Value = Packet> {
+    (
+        any::<bool>(),
+        any::<u16>(),
+        prop::collection::vec(any::<u8>(), 0..=max_data_len),
+    )
+        .prop_map(|(is_error, device_address, data)| Packet {
+            is_error,
+            device_address,
+            data,
+        })
+}
+
+fn bcm_value_strategy() -> impl Strategy<Value = BcmValue> {
+    prop_oneof![
+        any::<bool>().prop_map(BcmValue::Binary),
+        any::<u8>().prop_map(BcmValue::Single),
+        any::<(u8, u8, u8)>().prop_map(|(r, g, b)| BcmValue::Rgb(r, g, b)),
+        any::<(u8, u8, u8, u8)>().prop_map(|(r, g, b, br)| BcmValue::RgbB(r, g, b, br)),
+        any::<(u8, u8, u8, u8)>().prop_map(|(r, g, b, w)| BcmValue::Rgbw(r, g, b, w)),
+        any::<(u8, u8, u8, u8, u8)>().prop_map(|(r, g, b, w, br)| BcmValue::RgbwB(r, g, b, w, br)),
+        any::<(u16, u8)>().prop_map(|(k, br)| BcmValue::ColorTemperature(k, br)),
+        any::<(u8, u8)>().prop_map(|(w, c)| BcmValue::WarmColdWhite(w, c)),
+        any::<(u16, u8, u8)>().prop_map(|(h, s, v)| BcmValue::Hsv(h, s, v)),
+    ]
+}
+
+fn bcm_easing_strategy() -> impl Strategy<Value = BcmEasing> {
+    prop_oneof![
+        Just(BcmEasing::Linear),
+        Just(BcmEasing::EaseIn),
+        Just(BcmEasing::EaseOut),
+        Just(BcmEasing::EaseInOut),
+        Just(BcmEasing::Gamma),
+    ]
+}
+
+fn relay_double_exclusive_value_strategy() -> impl Strategy<Value = RelayDoubleExclusiveValue> {
+    prop_oneof![
+        Just(RelayDoubleExclusiveValue::FirstChannelOn),
+        Just(RelayDoubleExclusiveValue::SecondChannelOn),
+        Just(RelayDoubleExclusiveValue::NoChannelOn),
+    ]
+}
+
+fn relay_value_strategy() -> impl Strategy<Value = RelayValue> {
+    prop_oneof![
+        any::<bool>().prop_map(RelayValue::Single),
+        relay_double_exclusive_value_strategy().prop_map(RelayValue::DoubleExclusive),
+        Just(RelayValue::Toggle),
+        any::<u32>().prop_map(RelayValue::Pulse),
+        any::<u32>().prop_map(RelayValue::TimedOn),
+        (relay_double_exclusive_value_strategy(), any::<u32>())
+            .prop_map(|(value, duration)| RelayValue::DoubleExclusiveForDuration(value, duration)),
+    ]
+}
+
+fn relay_state_strategy() -> impl Strategy<Value = RelayState> {
+    prop_oneof![
+        any::<bool>().prop_map(RelayState::Single),
+        relay_double_exclusive_value_strategy().prop_map(RelayState::DoubleExclusive),
+    ]
+}
+
+fn message_value_strategy() -> impl Strategy<Value = MessageValue> {
+    prop_oneof![
+        any::<u8>().prop_map(MessageValue::U8),
+        any::<u16>().prop_map(MessageValue::U16),
+        any::<u32>().prop_map(MessageValue::U32),
+        any::<bool>().prop_map(MessageValue::Bool),
+    ]
+}
+
+fn ack_details_strategy() -> impl Strategy<Value = Option<AckDetails>> {
+    prop::option::of(
+        (
+            any::<(u16, u16)>(),
+            prop_oneof![
+                Just(AckStatus::Ok),
+                Just(AckStatus::Busy),
+                Just(AckStatus::Invalid),
+                Just(AckStatus::Unsupported),
+            ],
+        )
+            .prop_map(|((event_code, sequence), status)| AckDetails {
+                event_code,
+                sequence,
+                status,
+            }),
+    )
+}
+
+fn error_code_strategy() -> impl Strategy<Value = ErrorCode> {
+    prop_oneof![
+        Just(ErrorCode::UnsupportedEvent),
+        Just(ErrorCode::MalformedEvent),
+        Just(ErrorCode::InvalidValue),
+        Just(ErrorCode::Busy),
+        Just(ErrorCode::InvalidState),
+        Just(ErrorCode::StorageError),
+        Just(ErrorCode::InternalError),
+        any::<u8>().prop_filter_map("reserved error code", |code| {
+            CustomErrorCode::new(code).map(ErrorCode::Custom)
+        }),
+    ]
+}
+
+fn cover_state_strategy() -> impl Strategy<Value = CoverState> {
+    prop_oneof![
+        Just(CoverState::Stopped),
+        Just(CoverState::Opening),
+        Just(CoverState::Closing),
+    ]
+}
+
+fn sensor_kind_strategy() -> impl Strategy<Value = SensorKind> {
+    prop_oneof![
+        Just(SensorKind::Temperature),
+        Just(SensorKind::Humidity),
+        Just(SensorKind::Illuminance),
+        Just(SensorKind::Motion),
+        Just(SensorKind::Power),
+        Just(SensorKind::Energy),
+        Just(SensorKind::Voltage),
+        Just(SensorKind::Current),
+        Just(SensorKind::Pressure),
+        Just(SensorKind::Co2),
+    ]
+}
+
+fn sensor_unit_strategy() -> impl Strategy<Value = SensorUnit> {
+    prop_oneof![
+        Just(SensorUnit::None),
+        Just(SensorUnit::Celsius),
+        Just(SensorUnit::Percent),
+        Just(SensorUnit::Lux),
+        Just(SensorUnit::Watt),
+        Just(SensorUnit::WattHour),
+        Just(SensorUnit::Volt),
+        Just(SensorUnit::Ampere),
+        Just(SensorUnit::Pascal),
+        Just(SensorUnit::PartsPerMillion),
+    ]
+}
+
+fn schedule_recurrence_strategy() -> impl Strategy<Value = ScheduleRecurrence> {
+    prop_oneof![
+        Just(ScheduleRecurrence::Once),
+        Just(ScheduleRecurrence::Daily),
+        (0x00u8..=0x7f).prop_map(ScheduleRecurrence::Weekly),
+        any::<u32>().prop_map(ScheduleRecurrence::Interval),
+    ]
+}
+
+fn schedule_entry_strategy() -> impl Strategy<Value = ScheduleEntry> {
+    (
+        any::<(u8, u32)>(),
+        schedule_recurrence_strategy(),
+        packet_strategy(32),
+    )
+        .prop_map(|((id, time), recurrence, packet)| ScheduleEntry {
+            id,
+            time,
+            recurrence,
+            packet,
+        })
+}
+
+fn parameter_value_strategy() -> impl Strategy<Value = ParameterValue> {
+    prop_oneof![
+        any::<bool>().prop_map(ParameterValue::Bool),
+        any::<u8>().prop_map(ParameterValue::U8),
+        any::<u16>().prop_map(ParameterValue::U16),
+        any::<u32>().prop_map(ParameterValue::U32),
+        any::<i16>().prop_map(ParameterValue::I16),
+        any::<i32>().prop_map(ParameterValue::I32),
+    ]
+}
+
+fn interface_statistics_strategy() -> impl Strategy<Value = InterfaceStatistics> {
+    any::<[u32; 12]>().prop_map(|counters| InterfaceStatistics {
+        frames_received: counters[0],
+        frames_sent: counters[1],
+        packets_received: counters[2],
+        packets_sent: counters[3],
+        cobs_errors: counters[4],
+        frame_errors: counters[5],
+        out_of_order_frames: counters[6],
+        builder_resets: counters[7],
+        timeouts: counters[8],
+        mailbox_full: counters[9],
+        overruns: counters[10],
+        io_errors: counters[11],
+    })
+}
+
+fn protocol_statistics_strategy() -> impl Strategy<Value = ProtocolStatistics> {
+    any::<[u32; 5]>().prop_map(|counters| ProtocolStatistics {
+        packets_received: counters[0],
+        packets_handled: counters[1],
+        packets_sent: counters[2],
+        handler_invocations: counters[3],
+        interface_errors: counters[4],
+    })
+}
+
+proptest! {
+    #[test]
+    fn frames_roundtrip_test(packet in packet_strategy(300)) {
+        let frames = packet.to_frames();
+        prop_assert_eq!(frames.len(), packet.frame_count());
+
+        for frame in frames.iter() {
+            prop_assert_eq!(&Frame::from_usart_frame(frame.to_usart_frame()).unwrap(), frame);
+            prop_assert_eq!(&Frame::from_bxcan_frame(frame.to_bxcan_frame()).unwrap(), frame);
+        }
+
+        let mut frames = frames.into_iter();
+        let mut packet_builder = PacketBuilder::new(frames.next().unwrap()).unwrap();
+
+        for frame in frames {
+            packet_builder.add_frame(frame).unwrap();
+        }
+
+        prop_assert_eq!(packet_builder.build(), Ok(packet));
+    }
+}
+
+roundtrip_test!(
+    bootloader_hello_roundtrip_test,
+    BootloaderHelloEvent,
+    any::<(u16, u16)>().prop_map(|(programmer_address, bootloader_address)| {
+        BootloaderHelloEvent {
+            programmer_address,
+            bootloader_address,
+        }
+    })
+);
+
+roundtrip_test!(
+    programmer_hello_roundtrip_test,
+    ProgrammerHelloEvent,
+    any::<u16>().prop_map(|programmer_address| ProgrammerHelloEvent { programmer_address })
+);
+
+roundtrip_test!(
+    programmer_start_firmware_upgrade_roundtrip_test,
+    ProgrammerStartFirmwareUpgradeEvent,
+    any::<(u16, u16, u32)>().prop_map(|(receiver_address, programmer_address, firmware_size)| {
+        ProgrammerStartFirmwareUpgradeEvent {
+            receiver_address,
+            programmer_address,
+            firmware_size,
+        }
+    })
+);
+
+roundtrip_test!(
+    programmer_start_config_upgrade_roundtrip_test,
+    ProgrammerStartConfigUpgradeEvent,
+    any::<(u16, u16, u32)>().prop_map(|(receiver_address, programmer_address, config_size)| {
+        ProgrammerStartConfigUpgradeEvent {
+            receiver_address,
+            programmer_address,
+            config_size,
+        }
+    })
+);
+
+roundtrip_test!(
+    programmer_set_device_address_roundtrip_test,
+    ProgrammerSetDeviceAddressEvent,
+    any::<(u16, u16, u16)>().prop_map(|(receiver_address, programmer_address, new_address)| {
+        ProgrammerSetDeviceAddressEvent {
+            receiver_address,
+            programmer_address,
+            new_address,
+        }
+    })
+);
+
+roundtrip_test!(
+    ack_roundtrip_test,
+    AckEvent,
+    any::<(u16, u16)>().prop_map(|(receiver_address, transmitter_address)| AckEvent {
+        receiver_address,
+        transmitter_address,
+    })
+);
+
+roundtrip_test!(
+    extended_ack_roundtrip_test,
+    ExtendedAckEvent,
+    (any::<(u16, u16)>(), ack_details_strategy()).prop_map(
+        |((receiver_address, transmitter_address), details)| ExtendedAckEvent {
+            receiver_address,
+            transmitter_address,
+            details,
+        }
+    )
+);
+
+roundtrip_test!(
+    data_roundtrip_test,
+    DataEvent,
+    (
+        any::<(u16, u16)>(),
+        prop::collection::vec(any::<u8>(), 0..300)
+    )
+        .prop_map(
+            |((receiver_address, transmitter_address), data)| DataEvent {
+                receiver_address,
+                transmitter_address,
+                data_len: data.len() as u16,
+                data,
+            }
+        )
+);
+
+roundtrip_test!(
+    sequenced_roundtrip_test,
+    SequencedEvent,
+    (
+        any::<(u16, u16, u8, u16)>(),
+        prop::collection::vec(any::<u8>(), 0..64)
+    )
+        .prop_map(
+            |((receiver_address, transmitter_address, session, sequence), data)| SequencedEvent {
+                receiver_address,
+                transmitter_address,
+                session,
+                sequence,
+                data,
+            }
+        )
+);
+
+roundtrip_test!(
+    configurator_hello_roundtrip_test,
+    ConfiguratorHelloEvent,
+    Just(()).prop_map(|_| ConfiguratorHelloEvent {})
+);
+
+roundtrip_test!(
+    bcm_change_brightness_roundtrip_test,
+    BcmChangeBrightnessEvent,
+    (any::<(u16, u16, u8)>(), bcm_value_strategy()).prop_map(
+        |((bcm_address, transmitter_address, index), value)| BcmChangeBrightnessEvent {
+            bcm_address,
+            transmitter_address,
+            index,
+            value,
+        }
+    )
+);
+
+roundtrip_test!(
+    bcm_animate_brightness_roundtrip_test,
+    BcmAnimateBrightnessEvent,
+    (
+        any::<(u16, u16, u8, u32)>(),
+        bcm_value_strategy(),
+        bcm_easing_strategy()
+    )
+        .prop_map(
+            |((bcm_address, transmitter_address, index, duration), target_value, easing)| {
+                BcmAnimateBrightnessEvent {
+                    bcm_address,
+                    transmitter_address,
+                    index,
+                    duration,
+                    target_value,
+                    easing,
+                }
+            }
+        )
+);
+
+roundtrip_test!(
+    bcm_animate_sequence_roundtrip_test,
+    BcmAnimateSequenceEvent,
+    (
+        any::<(u16, u16, u8, u8)>(),
+        prop::collection::vec(
+            (any::<u32>(), bcm_easing_strategy(), bcm_value_strategy()).prop_map(
+                |(duration, easing, value)| BcmKeyframe {
+                    duration,
+                    easing,
+                    value,
+                }
+            ),
+            0..16
+        )
+    )
+        .prop_map(
+            |((bcm_address, transmitter_address, index, loop_count), keyframes)| {
+                BcmAnimateSequenceEvent {
+                    bcm_address,
+                    transmitter_address,
+                    index,
+                    loop_count,
+                    keyframes,
+                }
+            }
+        )
+);
+
+roundtrip_test!(
+    button_pressed_roundtrip_test,
+    ButtonPressedEvent,
+    any::<(u16, u16, u8)>().prop_map(|(receiver_address, button_address, index)| {
+        ButtonPressedEvent {
+            receiver_address,
+            button_address,
+            index,
+        }
+    })
+);
+
+roundtrip_test!(
+    button_released_roundtrip_test,
+    ButtonReleasedEvent,
+    any::<(u16, u16, u8)>().prop_map(|(receiver_address, button_address, index)| {
+        ButtonReleasedEvent {
+            receiver_address,
+            button_address,
+            index,
+        }
+    })
+);
+
+roundtrip_test!(
+    system_tick_roundtrip_test,
+    SystemTickEvent,
+    any::<u16>().prop_map(|receiver_address| SystemTickEvent { receiver_address })
+);
+
+roundtrip_test!(
+    message_roundtrip_test,
+    MessageEvent,
+    (any::<(u16, u16, u16)>(), message_value_strategy()).prop_map(
+        |((receiver_address, transmitter_address, code), value)| MessageEvent {
+            receiver_address,
+            transmitter_address,
+            code,
+            value,
+        }
+    )
+);
+
+roundtrip_test!(
+    relay_set_value_roundtrip_test,
+    RelaySetValueEvent,
+    (any::<(u16, u16, u8)>(), relay_value_strategy()).prop_map(
+        |((relay_address, transmitter_address, index), value)| RelaySetValueEvent {
+            relay_address,
+            transmitter_address,
+            index,
+            value,
+        }
+    )
+);
+
+roundtrip_test!(
+    relay_get_value_roundtrip_test,
+    RelayGetValueEvent,
+    any::<(u16, u16, u8)>().prop_map(|(relay_address, transmitter_address, index)| {
+        RelayGetValueEvent {
+            relay_address,
+            transmitter_address,
+            index,
+        }
+    })
+);
+
+roundtrip_test!(
+    relay_report_value_roundtrip_test,
+    RelayReportValueEvent,
+    (any::<(u16, u16, u8)>(), relay_state_strategy()).prop_map(
+        |((receiver_address, relay_address, index), value)| RelayReportValueEvent {
+            receiver_address,
+            relay_address,
+            index,
+            value,
+        }
+    )
+);
+
+roundtrip_test!(
+    relay_value_changed_roundtrip_test,
+    RelayValueChangedEvent,
+    (any::<(u16, u16, u8)>(), relay_state_strategy()).prop_map(
+        |((receiver_address, relay_address, index), value)| RelayValueChangedEvent {
+            receiver_address,
+            relay_address,
+            index,
+            value,
+        }
+    )
+);
+
+roundtrip_test!(
+    gateway_discover_roundtrip_test,
+    GatewayDiscoverEvent,
+    any::<(u16, u16)>().prop_map(|(device_address, gateway_address)| {
+        GatewayDiscoverEvent {
+            device_address,
+            gateway_address,
+        }
+    })
+);
+
+roundtrip_test!(
+    error_roundtrip_test,
+    ErrorEvent,
+    (
+        any::<(u16, u16, u16)>(),
+        error_code_strategy(),
+        prop::collection::vec(any::<u8>(), 0..64)
+    )
+        .prop_map(
+            |((receiver_address, transmitter_address, event_code), error_code, detail)| {
+                ErrorEvent {
+                    receiver_address,
+                    transmitter_address,
+                    event_code,
+                    error_code,
+                    detail,
+                }
+            }
+        )
+);
+
+#[test]
+fn error_code_byte_roundtrip_test() {
+    for code in 0x00..=0xff {
+        let packet = Packet {
+            is_error: true,
+            device_address: 0xabab,
+            data: vec![0x00, 0x00, 0x01, 0x23, code],
+        };
+
+        match ErrorEvent::try_from_packet(&packet) {
+            Ok(event) => assert_eq!(event.to_packet(), packet),
+            Err(err) => {
+                assert_eq!(err, ConvertPacketError::UnknownEnumVariant);
+                assert!((0x07..0x80).contains(&code));
+            }
+        }
+    }
+}
+
+roundtrip_test!(
+    cover_set_position_roundtrip_test,
+    CoverSetPositionEvent,
+    any::<(u16, u16, u8, u8)>().prop_map(
+        |(cover_address, transmitter_address, index, position)| {
+            CoverSetPositionEvent {
+                cover_address,
+                transmitter_address,
+                index,
+                position,
+            }
+        }
+    )
+);
+
+roundtrip_test!(
+    cover_set_tilt_roundtrip_test,
+    CoverSetTiltEvent,
+    any::<(u16, u16, u8, u8)>().prop_map(|(cover_address, transmitter_address, index, tilt)| {
+        CoverSetTiltEvent {
+            cover_address,
+            transmitter_address,
+            index,
+            tilt,
+        }
+    })
+);
+
+roundtrip_test!(
+    cover_stop_roundtrip_test,
+    CoverStopEvent,
+    any::<(u16, u16, u8)>().prop_map(|(cover_address, transmitter_address, index)| {
+        CoverStopEvent {
+            cover_address,
+            transmitter_address,
+            index,
+        }
+    })
+);
+
+roundtrip_test!(
+    cover_calibrate_roundtrip_test,
+    CoverCalibrateEvent,
+    any::<(u16, u16, u8, u32, u32, u32)>().prop_map(
+        |(
+            cover_address,
+            transmitter_address,
+            index,
+            opening_duration,
+            closing_duration,
+            tilt_duration,
+        )| {
+            CoverCalibrateEvent {
+                cover_address,
+                transmitter_address,
+                index,
+                opening_duration,
+                closing_duration,
+                tilt_duration,
+            }
+        }
+    )
+);
+
+roundtrip_test!(
+    cover_report_position_roundtrip_test,
+    CoverReportPositionEvent,
+    (any::<(u16, u16, u8, u8, u8)>(), cover_state_strategy()).prop_map(
+        |((receiver_address, cover_address, index, position, tilt), state)| {
+            CoverReportPositionEvent {
+                receiver_address,
+                cover_address,
+                index,
+                position,
+                tilt,
+                state,
+            }
+        }
+    )
+);
+
+roundtrip_test!(
+    bcm_get_value_roundtrip_test,
+    BcmGetValueEvent,
+    any::<(u16, u16, u8)>().prop_map(|(bcm_address, transmitter_address, index)| {
+        BcmGetValueEvent {
+            bcm_address,
+            transmitter_address,
+            index,
+        }
+    })
+);
+
+roundtrip_test!(
+    bcm_report_value_roundtrip_test,
+    BcmReportValueEvent,
+    (any::<(u16, u16, u8)>(), bcm_value_strategy()).prop_map(
+        |((receiver_address, bcm_address, index), value)| BcmReportValueEvent {
+            receiver_address,
+            bcm_address,
+            index,
+            value,
+        }
+    )
+);
+
+roundtrip_test!(
+    bcm_store_scene_roundtrip_test,
+    BcmStoreSceneEvent,
+    any::<(u16, u16, u8)>().prop_map(|(bcm_address, transmitter_address, scene)| {
+        BcmStoreSceneEvent {
+            bcm_address,
+            transmitter_address,
+            scene,
+        }
+    })
+);
+
+roundtrip_test!(
+    bcm_recall_scene_roundtrip_test,
+    BcmRecallSceneEvent,
+    any::<(u16, u16, u8, u32)>().prop_map(|(bcm_address, transmitter_address, scene, duration)| {
+        BcmRecallSceneEvent {
+            bcm_address,
+            transmitter_address,
+            scene,
+            duration,
+        }
+    })
+);
+
+roundtrip_test!(
+    button_clicked_roundtrip_test,
+    ButtonClickedEvent,
+    any::<(u16, u16, u8)>().prop_map(|(receiver_address, button_address, index)| {
+        ButtonClickedEvent {
+            receiver_address,
+            button_address,
+            index,
+        }
+    })
+);
+
+roundtrip_test!(
+    button_double_clicked_roundtrip_test,
+    ButtonDoubleClickedEvent,
+    any::<(u16, u16, u8)>().prop_map(|(receiver_address, button_address, index)| {
+        ButtonDoubleClickedEvent {
+            receiver_address,
+            button_address,
+            index,
+        }
+    })
+);
+
+roundtrip_test!(
+    button_long_pressed_roundtrip_test,
+    ButtonLongPressedEvent,
+    any::<(u16, u16, u8)>().prop_map(|(receiver_address, button_address, index)| {
+        ButtonLongPressedEvent {
+            receiver_address,
+            button_address,
+            index,
+        }
+    })
+);
+
+roundtrip_test!(
+    button_held_roundtrip_test,
+    ButtonHeldEvent,
+    any::<(u16, u16, u8, u16)>().prop_map(|(receiver_address, button_address, index, repeat)| {
+        ButtonHeldEvent {
+            receiver_address,
+            button_address,
+            index,
+            repeat,
+        }
+    })
+);
+
+roundtrip_test!(
+    encoder_rotated_roundtrip_test,
+    EncoderRotatedEvent,
+    any::<(u16, u16, u8, i16)>().prop_map(|(receiver_address, encoder_address, index, delta)| {
+        EncoderRotatedEvent {
+            receiver_address,
+            encoder_address,
+            index,
+            delta,
+        }
+    })
+);
+
+roundtrip_test!(
+    analog_value_roundtrip_test,
+    AnalogValueEvent,
+    any::<(u16, u16, u8, u16)>().prop_map(|(receiver_address, analog_address, index, value)| {
+        AnalogValueEvent {
+            receiver_address,
+            analog_address,
+            index,
+            value,
+        }
+    })
+);
+
+roundtrip_test!(
+    sensor_get_reading_roundtrip_test,
+    SensorGetReadingEvent,
+    any::<(u16, u16, u8)>().prop_map(|(sensor_address, transmitter_address, index)| {
+        SensorGetReadingEvent {
+            sensor_address,
+            transmitter_address,
+            index,
+        }
+    })
+);
+
+roundtrip_test!(
+    sensor_reading_roundtrip_test,
+    SensorReadingEvent,
+    (
+        any::<(u16, u16, u8, i8, i32)>(),
+        sensor_kind_strategy(),
+        sensor_unit_strategy()
+    )
+        .prop_map(
+            |((receiver_address, sensor_address, index, exponent, value), kind, unit)| {
+                SensorReadingEvent {
+                    receiver_address,
+                    sensor_address,
+                    index,
+                    kind,
+                    unit,
+                    exponent,
+                    value,
+                }
+            }
+        )
+);
+
+roundtrip_test!(
+    relay_report_power_roundtrip_test,
+    RelayReportPowerEvent,
+    any::<(u16, u16, u8, u32)>().prop_map(|(receiver_address, relay_address, index, power)| {
+        RelayReportPowerEvent {
+            receiver_address,
+            relay_address,
+            index,
+            power,
+        }
+    })
+);
+
+roundtrip_test!(
+    relay_report_energy_roundtrip_test,
+    RelayReportEnergyEvent,
+    any::<(u16, u16, u8, u32)>().prop_map(|(receiver_address, relay_address, index, energy)| {
+        RelayReportEnergyEvent {
+            receiver_address,
+            relay_address,
+            index,
+            energy,
+        }
+    })
+);
+
+roundtrip_test!(
+    relay_over_current_roundtrip_test,
+    RelayOverCurrentEvent,
+    any::<(u16, u16, u8, u32)>().prop_map(|(receiver_address, relay_address, index, current)| {
+        RelayOverCurrentEvent {
+            receiver_address,
+            relay_address,
+            index,
+            current,
+        }
+    })
+);
+
+roundtrip_test!(
+    relay_reset_energy_roundtrip_test,
+    RelayResetEnergyEvent,
+    any::<(u16, u16, u8)>().prop_map(|(relay_address, transmitter_address, index)| {
+        RelayResetEnergyEvent {
+            relay_address,
+            transmitter_address,
+            index,
+        }
+    })
+);
+
+roundtrip_test!(
+    time_get_roundtrip_test,
+    TimeGetEvent,
+    any::<(u16, u16)>().prop_map(|(time_master_address, transmitter_address)| {
+        TimeGetEvent {
+            time_master_address,
+            transmitter_address,
+        }
+    })
+);
+
+roundtrip_test!(
+    time_sync_roundtrip_test,
+    TimeSyncEvent,
+    any::<(u16, u16, u32, u16)>().prop_map(
+        |(receiver_address, time_master_address, seconds, milliseconds)| {
+            TimeSyncEvent {
+                receiver_address,
+                time_master_address,
+                seconds,
+                milliseconds,
+            }
+        }
+    )
+);
+
+roundtrip_test!(
+    schedule_add_roundtrip_test,
+    ScheduleAddEvent,
+    (any::<(u16, u16)>(), schedule_entry_strategy()).prop_map(
+        |((scheduler_address, transmitter_address), entry)| ScheduleAddEvent {
+            scheduler_address,
+            transmitter_address,
+            entry,
+        }
+    )
+);
+
+roundtrip_test!(
+    schedule_remove_roundtrip_test,
+    ScheduleRemoveEvent,
+    any::<(u16, u16, u8)>().prop_map(|(scheduler_address, transmitter_address, id)| {
+        ScheduleRemoveEvent {
+            scheduler_address,
+            transmitter_address,
+            id,
+        }
+    })
+);
+
+roundtrip_test!(
+    schedule_list_roundtrip_test,
+    ScheduleListEvent,
+    any::<(u16, u16)>().prop_map(|(scheduler_address, transmitter_address)| {
+        ScheduleListEvent {
+            scheduler_address,
+            transmitter_address,
+        }
+    })
+);
+
+roundtrip_test!(
+    schedule_report_roundtrip_test,
+    ScheduleReportEvent,
+    (any::<(u16, u16)>(), schedule_entry_strategy()).prop_map(
+        |((receiver_address, scheduler_address), entry)| ScheduleReportEvent {
+            receiver_address,
+            scheduler_address,
+            entry,
+        }
+    )
+);
+
+roundtrip_test!(
+    group_add_roundtrip_test,
+    GroupAddEvent,
+    any::<(u16, u16, u16)>().prop_map(|(device_address, transmitter_address, group_address)| {
+        GroupAddEvent {
+            device_address,
+            transmitter_address,
+            group_address,
+        }
+    })
+);
+
+roundtrip_test!(
+    group_remove_roundtrip_test,
+    GroupRemoveEvent,
+    any::<(u16, u16, u16)>().prop_map(|(device_address, transmitter_address, group_address)| {
+        GroupRemoveEvent {
+            device_address,
+            transmitter_address,
+            group_address,
+        }
+    })
+);
+
+roundtrip_test!(
+    group_get_roundtrip_test,
+    GroupGetEvent,
+    any::<(u16, u16)>().prop_map(|(device_address, transmitter_address)| {
+        GroupGetEvent {
+            device_address,
+            transmitter_address,
+        }
+    })
+);
+
+roundtrip_test!(
+    group_report_roundtrip_test,
+    GroupReportEvent,
+    (
+        any::<(u16, u16)>(),
+        prop::collection::vec(any::<u16>(), 0..16)
+    )
+        .prop_map(
+            |((receiver_address, device_address), group_addresses)| GroupReportEvent {
+                receiver_address,
+                device_address,
+                group_addresses,
+            }
+        )
+);
+
+roundtrip_test!(
+    parameter_read_roundtrip_test,
+    ParameterReadEvent,
+    any::<(u16, u16, u16)>().prop_map(|(device_address, transmitter_address, index)| {
+        ParameterReadEvent {
+            device_address,
+            transmitter_address,
+            index,
+        }
+    })
+);
+
+roundtrip_test!(
+    parameter_describe_roundtrip_test,
+    ParameterDescribeEvent,
+    any::<(u16, u16, u16)>().prop_map(|(device_address, transmitter_address, index)| {
+        ParameterDescribeEvent {
+            device_address,
+            transmitter_address,
+            index,
+        }
+    })
+);
+
+roundtrip_test!(
+    parameter_write_roundtrip_test,
+    ParameterWriteEvent,
+    (any::<(u16, u16, u16)>(), parameter_value_strategy()).prop_map(
+        |((device_address, transmitter_address, index), value)| ParameterWriteEvent {
+            device_address,
+            transmitter_address,
+            index,
+            value,
+        }
+    )
+);
+
+roundtrip_test!(
+    parameter_report_roundtrip_test,
+    ParameterReportEvent,
+    (any::<(u16, u16, u16)>(), parameter_value_strategy()).prop_map(
+        |((receiver_address, device_address, index), value)| ParameterReportEvent {
+            receiver_address,
+            device_address,
+            index,
+            value,
+        }
+    )
+);
+
+roundtrip_test!(
+    parameter_description_roundtrip_test,
+    ParameterDescriptionEvent,
+    (
+        any::<(u16, u16, u16)>(),
+        prop_oneof![
+            Just(ParameterAccess::ReadOnly),
+            Just(ParameterAccess::ReadWrite)
+        ],
+        parameter_value_strategy(),
+        parameter_value_strategy(),
+    )
+        .prop_map(
+            |((receiver_address, device_address, index), access, min, max)| {
+                ParameterDescriptionEvent {
+                    receiver_address,
+                    device_address,
+                    index,
+                    access,
+                    min,
+                    max,
+                }
+            }
+        )
+);
+
+roundtrip_test!(
+    diagnostics_get_roundtrip_test,
+    DiagnosticsGetEvent,
+    any::<(u16, u16)>().prop_map(|(device_address, transmitter_address)| {
+        DiagnosticsGetEvent {
+            device_address,
+            transmitter_address,
+        }
+    })
+);
+
+roundtrip_test!(
+    diagnostics_report_roundtrip_test,
+    DiagnosticsReportEvent,
+    (
+        any::<(u16, u16)>(),
+        interface_statistics_strategy(),
+        protocol_statistics_strategy(),
+    )
+        .prop_map(
+            |((receiver_address, device_address), interface_statistics, protocol_statistics)| {
+                DiagnosticsReportEvent {
+                    receiver_address,
+                    device_address,
+                    interface_statistics,
+                    protocol_statistics,
+                }
+            }
+        )
+);